@@ -1,4 +1,5 @@
-use clap::Parser;
+use crate::generator::{BigIntType, GenOptions, SetType};
+use clap::{Parser, ValueEnum};
 use std::path::PathBuf;
 
 #[derive(Parser, Debug)]
@@ -9,4 +10,154 @@ pub struct Args {
 
     #[arg(short, long, default_value = "./generated")]
     pub output: PathBuf,
+
+    /// Skip structs without fields instead of emitting empty structs
+    #[arg(long)]
+    pub prune_empty_structs: bool,
+
+    /// Generate FromStr and Display impls for enums, plus as_str() and TryFrom<&str> for string enums
+    #[arg(long)]
+    pub enum_helpers: bool,
+
+    /// Represent single-value enums as unit structs with an associated VALUE const
+    #[arg(long)]
+    pub single_enum_as_const: bool,
+
+    /// Skip serializing None fields and default optional arrays to empty
+    #[arg(long)]
+    pub skip_none: bool,
+
+    /// Also generate FooRead/FooWrite structs for schemas with readOnly/writeOnly properties
+    #[arg(long)]
+    pub split_rw: bool,
+
+    /// Generate bytes::Bytes instead of Vec<u8> for binary strings (needs the bytes crate with serde)
+    #[arg(long)]
+    pub bytes: bool,
+
+    /// Generate Email/Hostname newtypes and url::Url for the email, hostname and uri string formats
+    #[arg(long)]
+    pub format_newtypes: bool,
+
+    /// Keep ipv4/ipv6 strings as String instead of std::net::Ipv4Addr/Ipv6Addr
+    #[arg(long)]
+    pub ip_as_string: bool,
+
+    /// Generate `uniqueItems` arrays of scalars as sets instead of Vec
+    #[arg(long, value_enum)]
+    pub unique_items: Option<SetType>,
+    /// Generate [T; N] for arrays whose minItems and maxItems are both N (up to 32)
+    #[arg(long)]
+    pub fixed_arrays: bool,
+    /// Generate rust_decimal::Decimal for format: decimal, serialized as strings (needs rust_decimal with serde-with-str)
+    #[arg(long)]
+    pub decimal: bool,
+    /// Generate iso8601_duration::Duration for format: duration (needs iso8601-duration with serde)
+    #[arg(long)]
+    pub duration: bool,
+    /// Generate secrecy::SecretString for format: password and x-secret strings (needs secrecy with serde)
+    #[arg(long)]
+    pub secrets: bool,
+    /// Generate allOf compositions embedding each referenced schema as a #[serde(flatten)] field instead of copying its fields
+    #[arg(long)]
+    pub flatten_all_of: bool,
+    /// Generate a {Base}Fields getter trait for every schema extended through allOf, implemented by the base and each schema extending it
+    #[arg(long)]
+    pub all_of_traits: bool,
+    /// Generate Option<Option<T>> for optional nullable fields, telling an absent field (None) from null (Some(None))
+    #[arg(long)]
+    pub double_option: bool,
+    /// Generate i128 or num_bigint::BigInt for format: bigint/int128 integers, serialized as strings
+    #[arg(long, value_enum)]
+    pub big_integers: Option<BigIntType>,
+    /// Generate u8..u64 for uint8..uint64 formats and integers with a non-negative minimum
+    #[arg(long)]
+    pub unsigned: bool,
+    /// Reject unknown fields with #[serde(deny_unknown_fields)] on structs of schemas with additionalProperties: false
+    #[arg(long)]
+    pub deny_unknown_fields: bool,
+    /// Name fields and structs after the schemas' xml objects for quick-xml (attributes become @name); the names apply to JSON too
+    #[arg(long)]
+    pub xml: bool,
+    /// Mark generated enums #[non_exhaustive] so downstream matches need a wildcard arm for values added later
+    #[arg(long)]
+    pub non_exhaustive: bool,
+    /// Add an Other(String) variant to string enums that catches values the spec does not list (x-extensible-enum does this per schema)
+    #[arg(long)]
+    pub open_enums: bool,
+    /// Derive typed_builder::TypedBuilder for structs with at least this many fields (needs the typed-builder crate)
+    #[arg(long, value_name = "MIN_FIELDS")]
+    pub builders: Option<usize>,
+    /// Generate a `new` constructor taking the required fields on every struct that has some
+    #[arg(long)]
+    pub constructors: bool,
+    /// Extra derives for every generated struct and enum, e.g. PartialEq,Eq,Hash; x-rust-derives overrides them per schema
+    #[arg(long, value_delimiter = ',')]
+    pub derives: Vec<String>,
+    /// Derive validator's Validate with attributes from spec constraints (needs the validator and regex crates)
+    #[arg(long)]
+    pub validate: bool,
+
+    /// Split the models into one module per OpenAPI tag, with a shared `common` module
+    #[arg(long, value_enum)]
+    pub group_by: Option<GroupBy>,
+
+    /// Generate a `fixtures` module with the spec's JSON examples and tests deserializing them
+    #[arg(long)]
+    pub fixtures: bool,
+    /// Print progress information while parsing and generating
+    #[arg(short, long)]
+    pub verbose: bool,
+
+    /// Report schema constructs that cannot be fully represented
+    #[arg(long)]
+    pub strict: bool,
+
+    /// Only generate the given component schemas and the schemas they reference
+    #[arg(long, value_delimiter = ',')]
+    pub only: Vec<String>,
+    /// Wrap `id`, `*Id` and `*Ids` fields in distinct newtypes such as `UserId(Uuid)`
+    #[arg(long)]
+    pub id_newtypes: bool,
+}
+
+/// How generated models are split into modules
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GroupBy {
+    /// One module per operation tag
+    Tag,
+}
+
+impl From<&Args> for GenOptions {
+    fn from(args: &Args) -> Self {
+        GenOptions {
+            prune_empty_structs: args.prune_empty_structs,
+            enum_helpers: args.enum_helpers,
+            single_enum_as_const: args.single_enum_as_const,
+            skip_none: args.skip_none,
+            split_rw: args.split_rw,
+            validate: args.validate,
+            bytes: args.bytes,
+            format_newtypes: args.format_newtypes,
+            ip_as_string: args.ip_as_string,
+            unique_items: args.unique_items,
+            fixed_arrays: args.fixed_arrays,
+            decimal: args.decimal,
+            duration: args.duration,
+            secrets: args.secrets,
+            flatten_all_of: args.flatten_all_of,
+            all_of_traits: args.all_of_traits,
+            double_option: args.double_option,
+            big_integers: args.big_integers,
+            unsigned: args.unsigned,
+            deny_unknown_fields: args.deny_unknown_fields,
+            xml: args.xml,
+            non_exhaustive: args.non_exhaustive,
+            open_enums: args.open_enums,
+            builder_threshold: args.builders,
+            constructors: args.constructors,
+            derives: args.derives.clone(),
+            spec_fingerprint: None,
+        }
+    }
 }
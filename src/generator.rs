@@ -1,18 +1,37 @@
-use std::sync::OnceLock;
+use sha2::{Digest, Sha256};
+use std::{
+    collections::{BTreeSet, HashMap, HashSet},
+    sync::OnceLock,
+};
 
 use crate::{
     models::{
-        CompositionModel, EnumModel, Model, ModelType, RequestModel, ResponseModel, TypeAliasModel,
-        UnionModel, UnionType,
+        CompositionModel, EnumModel, ExampleModel, Field, Model, ModelType, NewtypeModel,
+        OAuth2FlowKind, ParameterLocation, ParamsModel, RequestModel, ResponseModel,
+        SecuritySchemeKind, SecuritySchemeModel, ServerModel, TypeAliasModel, UnionModel,
+        UnionType,
     },
-    Result,
+    parser::DEPRECATED_ATTR,
+    Error, Result,
 };
 
 bitflags::bitflags! {
-    struct RequiredUses: u8 {
+    struct RequiredUses: u16 {
         const UUID = 0b00000001;
         const DATETIME = 0b00000010;
         const DATE = 0b00000100;
+        const PARSE_ERROR = 0b00001000;
+        const VALIDATE = 0b00010000;
+        const BASE64 = 0b00100000;
+        const EMAIL = 0b01000000;
+        const HOSTNAME = 0b10000000;
+        const FILE_PART = 0b1_00000000;
+        const DECIMAL = 0b10_00000000;
+        const TIME = 0b100_00000000;
+        const SECRET = 0b1000_00000000;
+        const DOUBLE_OPTION = 0b10000_00000000;
+        const BIG_INTEGER = 0b100000_00000000;
+        const REQUEST_URL = 0b1000000_00000000;
     }
 }
 
@@ -34,6 +53,16 @@ fn create_header() -> String {
     .clone()
 }
 
+/// Computes the hex-encoded SHA-256 fingerprint of a specification serialized as JSON.
+/// Pass the document returned by `parser::resolve_external_refs()`, so that changes to
+/// the files it references are caught as well.
+pub fn spec_fingerprint(openapi: &openapiv3::OpenAPI) -> Result<String> {
+    Ok(Sha256::digest(serde_json::to_vec(openapi)?)
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect())
+}
+
 const RUST_RESERVED_KEYWORDS: &[&str] = &[
     "as", "break", "const", "continue", "crate", "else", "enum", "extern", "false", "fn", "for",
     "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut", "pub", "ref", "return",
@@ -49,42 +78,67 @@ fn is_reserved_word(string_to_check: &str) -> bool {
     RUST_RESERVED_KEYWORDS.contains(&string_to_check.to_lowercase().as_str())
 }
 
+/// Renders a description as `///` lines. Leading whitespace is dropped so indented
+/// text is not taken for a code block, and bare code fences are marked `text` so
+/// rustdoc does not compile them as doctests.
 fn generate_description_docs(
     description: &Option<String>,
     fallback_str: &str,
     indent: &str,
 ) -> String {
     let mut output = String::new();
-    if let Some(desc) = description {
-        for line in desc.lines() {
-            output.push_str(&format!("{}/// {}\n", indent, line.trim()));
+    match description.as_deref().map(str::trim) {
+        Some(desc) if !desc.is_empty() => {
+            let mut in_fence = false;
+            for line in desc.lines() {
+                let mut line = line.trim();
+                if line.starts_with("```") {
+                    if !in_fence && (line == "```" || line == "```rust") {
+                        line = "```text";
+                    }
+                    in_fence = !in_fence;
+                }
+                if line.is_empty() {
+                    output.push_str(&format!("{indent}///\n"));
+                } else {
+                    output.push_str(&format!("{indent}/// {line}\n"));
+                }
+            }
         }
-    } else if !fallback_str.is_empty() {
-        output.push_str(&format!("{}/// {}\n", indent, fallback_str));
+        _ if !fallback_str.is_empty() => {
+            output.push_str(&format!("{indent}/// {fallback_str}\n"));
+        }
+        _ => {}
     }
 
     output
 }
 
+/// Converts a camelCase, PascalCase or kebab-case name to snake_case. A run of capitals
+/// is kept as one word, so `userID` -> `user_id` and `HTTPStatus` -> `http_status`.
 fn to_snake_case(name: &str) -> String {
-    let cleaned: String = name
+    let cleaned: Vec<char> = name
         .chars()
         .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
         .collect();
 
     let mut snake = String::new();
 
-    for (i, c) in cleaned.chars().enumerate() {
+    for (i, &c) in cleaned.iter().enumerate() {
         if c.is_ascii_uppercase() {
-            if i != 0 {
+            let prev = i.checked_sub(1).map(|j| cleaned[j]);
+            let next = cleaned.get(i + 1);
+            let starts_word = prev.is_some_and(|p| p.is_ascii_lowercase() || p.is_ascii_digit())
+                || (prev.is_some_and(|p| p.is_ascii_uppercase())
+                    && next.is_some_and(|n| n.is_ascii_lowercase()));
+            if starts_word {
                 snake.push('_');
             }
             snake.push(c.to_ascii_lowercase());
-        } else {
+        } else if c != '_' || !snake.ends_with('_') {
             snake.push(c);
         }
     }
-    snake = snake.replace("__", "_");
 
     if snake == "self" {
         snake.push('_');
@@ -113,6 +167,104 @@ fn has_custom_derive(custom_attrs: &Option<Vec<String>>) -> bool {
     }
 }
 
+/// Whether a type derives `Default`, through a custom `#[derive(...)]` in `x-rust-attrs`
+/// or else its `x-rust-derives` or the `--derives` list
+fn derives_default(
+    custom_attrs: &Option<Vec<String>>,
+    derives: &Option<Vec<String>>,
+    options: &GenOptions,
+) -> bool {
+    let custom = custom_attrs
+        .iter()
+        .flatten()
+        .any(|attr| attr.contains("Default"));
+    let listed = !has_custom_derive(custom_attrs)
+        && derives
+            .as_ref()
+            .unwrap_or(&options.derives)
+            .iter()
+            .any(|derive| derive == "Default");
+    custom || listed
+}
+
+/// Rejects configured derives that `secrecy::SecretString` does not implement on a struct
+/// with a secret field, which would otherwise only fail when the generated code is built
+fn check_secret_derives(
+    struct_name: &str,
+    fields: &[(&Field, bool)],
+    derives: &Option<Vec<String>>,
+    options: &GenOptions,
+) -> Result<()> {
+    let Some((secret, _)) = fields.iter().find(|(field, _)| is_secret(field, options)) else {
+        return Ok(());
+    };
+    let unsupported = derives
+        .as_ref()
+        .unwrap_or(&options.derives)
+        .iter()
+        .find(|derive| {
+            matches!(
+                derive.as_str(),
+                "PartialEq" | "Eq" | "Hash" | "PartialOrd" | "Ord"
+            )
+        });
+    match unsupported {
+        Some(derive) => Err(Error::Generation(format!(
+            "Cannot derive `{derive}` for `{struct_name}`: its field `{}` is a \
+             `secrecy::SecretString` under --secrets, which does not implement it. \
+             Remove it from --derives or the schema's x-rust-derives, or drop --secrets",
+            secret.name
+        ))),
+        None => Ok(()),
+    }
+}
+
+/// Default derive line for generated structs
+fn struct_derive(
+    derives: &Option<Vec<String>>,
+    builder: bool,
+    options: &GenOptions,
+    required_uses: &mut RequiredUses,
+) -> String {
+    let mut base = vec!["Debug", "Clone", "Serialize", "Deserialize"];
+    if options.validate {
+        *required_uses |= RequiredUses::VALIDATE;
+        base.push("Validate");
+    }
+    if builder {
+        base.push("typed_builder::TypedBuilder");
+    }
+    derive_attr(&base, derives, options)
+}
+
+/// Whether a struct with `field_count` fields derives a builder under `--builders`
+fn wants_builder(field_count: usize, options: &GenOptions) -> bool {
+    options
+        .builder_threshold
+        .is_some_and(|min_fields| field_count >= min_fields)
+}
+
+/// `#[non_exhaustive]` for enums of spec values under `--non-exhaustive`
+fn non_exhaustive_attr(options: &GenOptions) -> &'static str {
+    if options.non_exhaustive {
+        "#[non_exhaustive]\n"
+    } else {
+        ""
+    }
+}
+
+/// Derive line for a generated model: the `base` derives it needs, then the type's
+/// `x-rust-derives` or else the `--derives` list, skipping any already present
+fn derive_attr(base: &[&str], derives: &Option<Vec<String>>, options: &GenOptions) -> String {
+    let mut all: Vec<&str> = base.to_vec();
+    for derive in derives.as_ref().unwrap_or(&options.derives) {
+        if !all.contains(&derive.as_str()) {
+            all.push(derive);
+        }
+    }
+    format!("#[derive({})]\n", all.join(", "))
+}
+
 /// Checks if custom attributes contain a serde attribute
 fn has_custom_serde(custom_attrs: &Option<Vec<String>>) -> bool {
     if let Some(attrs) = custom_attrs {
@@ -134,57 +286,377 @@ fn generate_custom_attrs(custom_attrs: &Option<Vec<String>>) -> String {
     }
 }
 
+/// Options controlling how models are rendered
+#[derive(Debug, Clone, Default)]
+pub struct GenOptions {
+    /// Skip structs that have no fields instead of emitting `pub struct Foo {}`.
+    /// Any field referencing a pruned struct is left dangling, so this is only
+    /// useful when those types are provided elsewhere.
+    pub prune_empty_structs: bool,
+    /// Emit `FromStr` and `Display` impls for enums, plus `as_str` and `TryFrom<&str>`
+    /// for string enums
+    pub enum_helpers: bool,
+    /// Represent single-value enums as unit structs with an associated `VALUE` const
+    pub single_enum_as_const: bool,
+    /// Skip serializing `None` fields; non-required arrays become `Vec<T>` that
+    /// default to empty and are skipped when empty
+    pub skip_none: bool,
+    /// Additionally emit `FooRead`/`FooWrite` structs for schemas with readOnly or
+    /// writeOnly properties
+    pub split_rw: bool,
+    /// Derive `validator::Validate` and emit `#[validate(...)]` attributes from the
+    /// spec's length, range, pattern and item-count constraints
+    pub validate: bool,
+    /// Generate `bytes::Bytes` instead of `Vec<u8>` for `format: binary` strings
+    pub bytes: bool,
+    /// Generate `Email`/`Hostname` newtypes and `url::Url` for the `email`, `hostname`
+    /// and `uri` string formats
+    pub format_newtypes: bool,
+    /// Keep `ipv4`/`ipv6` strings as `String` instead of `std::net` address types
+    pub ip_as_string: bool,
+    /// Set type for `uniqueItems: true` arrays of scalars; `None` keeps `Vec<T>`
+    pub unique_items: Option<SetType>,
+    /// Generate `[T; N]` for arrays whose `minItems` and `maxItems` are both `N` (1 to 32)
+    pub fixed_arrays: bool,
+    /// Generate `rust_decimal::Decimal` for `format: decimal` strings and numbers,
+    /// (de)serialized as strings
+    pub decimal: bool,
+    /// Generate `iso8601_duration::Duration` for `format: duration` strings
+    pub duration: bool,
+    /// Generate `secrecy::SecretString` for `format: password` and `x-secret` strings
+    pub secrets: bool,
+    /// Generate allOf compositions that embed each referenced schema as a
+    /// `#[serde(flatten)]` field instead of copying its fields
+    pub flatten_all_of: bool,
+    /// Generate a `{Base}Fields` getter trait for every schema extended through allOf,
+    /// implemented by the base and each schema extending it
+    pub all_of_traits: bool,
+    /// Generate `Option<Option<T>>` for optional nullable fields, so an absent field
+    /// (`None`) is told apart from an explicit `null` (`Some(None)`)
+    pub double_option: bool,
+    /// Type for `format: bigint`/`int128` integers, (de)serialized as strings; `None`
+    /// keeps `i64`
+    pub big_integers: Option<BigIntType>,
+    /// Generate unsigned integers for `uint8`..`uint64` formats and integers with a
+    /// non-negative `minimum`
+    pub unsigned: bool,
+    /// Add `#[serde(deny_unknown_fields)]` to structs of schemas with
+    /// `additionalProperties: false`
+    pub deny_unknown_fields: bool,
+    /// Rename fields and structs after the schemas' `xml` objects for quick-xml: `@name`
+    /// for attributes, `xml.name` for elements and root elements
+    pub xml: bool,
+    /// Mark enums generated from `enum` values `#[non_exhaustive]`, so code matching on
+    /// them outside the crate keeps compiling when the API adds values
+    pub non_exhaustive: bool,
+    /// Add an untagged `Other(String)` variant to every string enum, so values the spec
+    /// does not list deserialize instead of failing; `x-extensible-enum` does this per schema
+    pub open_enums: bool,
+    /// Derive `typed_builder::TypedBuilder` for structs with at least this many fields;
+    /// optional and defaulted fields can be left out of the builder
+    pub builder_threshold: Option<usize>,
+    /// Generate `new` on structs with required fields, taking those fields and leaving
+    /// the rest `None` or defaulted
+    pub constructors: bool,
+    /// Derives added to every generated struct and enum, such as `PartialEq` or `Hash`;
+    /// a schema's `x-rust-derives` replaces them for its type
+    pub derives: Vec<String>,
+    /// Fingerprint of the source specification, emitted as a `spec-sha256` header line
+    pub spec_fingerprint: Option<String>,
+}
+
+/// Collection generated for `uniqueItems: true` arrays
+#[derive(clap::ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SetType {
+    /// `std::collections::BTreeSet<T>`, iterating in sorted order
+    BtreeSet,
+    /// `indexmap::IndexSet<T>`, keeping the order of the payload (needs indexmap with serde)
+    IndexSet,
+}
+
+/// Type generated for integers too large for `i64`
+#[derive(clap::ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BigIntType {
+    /// `i128`, up to 38 digits
+    I128,
+    /// `num_bigint::BigInt`, unbounded (needs num-bigint)
+    Bigint,
+}
+
+/// Which side of the API a struct is generated for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum StructVariant {
+    /// Single struct shared by requests and responses
+    Combined,
+    /// Response side: readOnly fields included, writeOnly fields omitted
+    Read,
+    /// Request side: writeOnly fields included, readOnly fields omitted
+    Write,
+}
+
+impl StructVariant {
+    fn struct_name(self, name: &str) -> String {
+        match self {
+            StructVariant::Combined => name.to_string(),
+            StructVariant::Read => format!("{name}Read"),
+            StructVariant::Write => format!("{name}Write"),
+        }
+    }
+
+    fn includes(self, field: &Field) -> bool {
+        match self {
+            StructVariant::Combined => true,
+            StructVariant::Read => !field.write_only,
+            StructVariant::Write => !field.read_only,
+        }
+    }
+
+    /// Points references to other split schemas at the struct for the same side
+    fn retarget(self, type_name: &str, split_models: &HashSet<String>) -> String {
+        if matches!(self, StructVariant::Combined) {
+            return type_name.to_string();
+        }
+
+        let mut output = String::new();
+        let mut ident = String::new();
+        let flush = |ident: &mut String, output: &mut String| {
+            if split_models.contains(ident.as_str()) {
+                *output += &self.struct_name(ident);
+            } else {
+                *output += ident;
+            }
+            ident.clear();
+        };
+        for c in type_name.chars() {
+            if c.is_alphanumeric() || c == '_' {
+                ident.push(c);
+            } else {
+                flush(&mut ident, &mut output);
+                output.push(c);
+            }
+        }
+        flush(&mut ident, &mut output);
+        output
+    }
+
+    /// `required` on a readOnly property only applies to responses, and on a
+    /// writeOnly property only to requests
+    fn is_required(self, field: &Field) -> bool {
+        match self {
+            StructVariant::Combined => field.is_required,
+            StructVariant::Read => field.is_required && !field.write_only,
+            StructVariant::Write => field.is_required && !field.read_only,
+        }
+    }
+}
+
+/// Returns the variants to generate for a struct with the given fields
+fn struct_variants(fields: &[Field], options: &GenOptions) -> Vec<StructVariant> {
+    let has_rw_fields = fields.iter().any(|f| f.read_only || f.write_only);
+    if options.split_rw && has_rw_fields {
+        vec![
+            StructVariant::Combined,
+            StructVariant::Read,
+            StructVariant::Write,
+        ]
+    } else {
+        vec![StructVariant::Combined]
+    }
+}
+
+/// Collects the `x-rust-use` paths of all models and their fields
+fn collect_rust_uses(models: &[ModelType]) -> BTreeSet<String> {
+    let mut uses = BTreeSet::new();
+    for model_type in models {
+        let fields: Vec<&Field> = match model_type {
+            ModelType::Struct(model) => model.fields.iter().collect(),
+            ModelType::Composition(comp) => comp.all_fields.iter().collect(),
+            ModelType::Union(union) => union.variants.iter().flat_map(|v| &v.fields).collect(),
+            ModelType::Params(params) => params.fields.iter().map(|p| &p.field).collect(),
+            ModelType::TypeAlias(type_alias) => {
+                uses.extend(type_alias.rust_uses.iter().cloned());
+                Vec::new()
+            }
+            ModelType::Enum(_) | ModelType::Newtype(_) => Vec::new(),
+        };
+        for field in fields {
+            uses.extend(field.rust_uses.iter().cloned());
+        }
+    }
+    uses
+}
+
+/// Generates Rust code for the parsed models using the default [`GenOptions`]
 pub fn generate_models(
     models: &[ModelType],
     requests: &[RequestModel],
     responses: &[ResponseModel],
+) -> Result<String> {
+    generate_models_with(models, requests, responses, &GenOptions::default())
+}
+
+/// Generates Rust code for the parsed models with explicit generation options
+pub fn generate_models_with(
+    models: &[ModelType],
+    requests: &[RequestModel],
+    responses: &[ResponseModel],
+    options: &GenOptions,
+) -> Result<String> {
+    generate_module(models, models, requests, responses, options, &[])
+}
+
+/// Generates one module of models; `extra_uses` are emitted after the serde import.
+/// `all_models` holds the models of every module, which allOf traits are resolved against.
+fn generate_module(
+    models: &[ModelType],
+    all_models: &[ModelType],
+    requests: &[RequestModel],
+    responses: &[ResponseModel],
+    options: &GenOptions,
+    extra_uses: &[&str],
 ) -> Result<String> {
     // First, generate all model code to determine which imports are needed
     let mut models_code = String::new();
     let mut required_uses = RequiredUses::empty();
 
-    for model_type in models {
+    // Schemas that get FooRead/FooWrite structs under --split-rw
+    let split_models: HashSet<String> = models
+        .iter()
+        .filter_map(|model_type| match model_type {
+            ModelType::Struct(model) if struct_variants(&model.fields, options).len() > 1 => {
+                Some(model.name.clone())
+            }
+            ModelType::Composition(comp)
+                if struct_variants(composition_fields(comp, options), options).len() > 1 =>
+            {
+                Some(comp.name.clone())
+            }
+            _ => None,
+        })
+        .collect();
+
+    for (index, model_type) in models.iter().enumerate() {
+        crate::parser::report_progress("Generating models", index + 1, models.len());
         match model_type {
             ModelType::Struct(model) => {
-                models_code.push_str(&generate_model(model, &mut required_uses)?);
+                if options.prune_empty_structs && model.fields.is_empty() {
+                    continue;
+                }
+                for variant in struct_variants(&model.fields, options) {
+                    models_code.push_str(&generate_model(
+                        model,
+                        variant,
+                        &split_models,
+                        options,
+                        &mut required_uses,
+                    )?);
+                }
             }
             ModelType::Union(union) => {
-                models_code.push_str(&generate_union(union)?);
+                models_code.push_str(&generate_union(union, options)?);
             }
             ModelType::Composition(comp) => {
-                models_code.push_str(&generate_composition(comp, &mut required_uses)?);
+                for variant in struct_variants(composition_fields(comp, options), options) {
+                    models_code.push_str(&generate_composition(
+                        comp,
+                        variant,
+                        &split_models,
+                        options,
+                        &mut required_uses,
+                    )?);
+                }
             }
             ModelType::Enum(enum_model) => {
-                models_code.push_str(&generate_enum(enum_model)?);
+                models_code.push_str(&generate_enum(enum_model, options, &mut required_uses)?);
             }
             ModelType::TypeAlias(type_alias) => {
+                if uses_base64_bytes(&type_alias.target_type) {
+                    required_uses |= RequiredUses::BASE64;
+                }
+                required_uses |= type_expr_uses(&type_alias.target_type);
                 models_code.push_str(&generate_type_alias(type_alias)?);
             }
+            ModelType::Params(params) => {
+                models_code.push_str(&generate_params(params, options, &mut required_uses)?);
+            }
+            ModelType::Newtype(newtype) => {
+                models_code.push_str(&generate_newtype(newtype, options, &mut required_uses));
+            }
         }
     }
 
+    if options.all_of_traits {
+        models_code.push_str(&generate_all_of_traits(
+            models,
+            all_models,
+            options,
+            &mut required_uses,
+        ));
+    }
+
     for request in requests {
-        models_code.push_str(&generate_request_model(request)?);
+        if uses_base64_bytes(&request.schema) {
+            required_uses |= RequiredUses::BASE64;
+        }
+        required_uses |= type_expr_uses(&request.schema);
+        models_code.push_str(&generate_request_model(request, &split_models, options)?);
     }
 
+    // A status offering several content types gets one enum instead of colliding structs
+    let mut response_groups: Vec<Vec<&ResponseModel>> = Vec::new();
     for response in responses {
-        models_code.push_str(&generate_response_model(response)?);
+        if uses_base64_bytes(&response.schema) {
+            required_uses |= RequiredUses::BASE64;
+        }
+        required_uses |= type_expr_uses(&response.schema);
+        let group = response_groups.iter_mut().find(|group| {
+            group[0].name == response.name && group[0].status_code == response.status_code
+        });
+        match group {
+            Some(group) => group.push(response),
+            None => response_groups.push(vec![response]),
+        }
+    }
+    for group in response_groups {
+        models_code.push_str(&match group.as_slice() {
+            [response] => generate_response_model(response, &split_models, options)?,
+            _ => generate_response_enum(&group, &split_models, options),
+        });
     }
 
     // Determine which imports are actually needed
     let needs_uuid = required_uses.contains(RequiredUses::UUID);
     let needs_datetime = required_uses.contains(RequiredUses::DATETIME);
     let needs_date = required_uses.contains(RequiredUses::DATE);
+    let needs_time = required_uses.contains(RequiredUses::TIME);
 
     // Build final output with only necessary imports
     let mut output = create_header();
+    if let Some(fingerprint) = &options.spec_fingerprint {
+        output.push_str(&format!("//! spec-sha256: {fingerprint}\n\n"));
+    }
+    // Deprecated items still reference each other; only warn where consumers use them
+    if models_code.contains("#[deprecated") {
+        output.push_str("#![allow(deprecated)]\n\n");
+    }
     output.push_str("use serde::{Serialize, Deserialize};\n");
+    for path in extra_uses {
+        output.push_str(&format!("#[allow(unused_imports)]\nuse {path};\n"));
+    }
+
+    if required_uses.contains(RequiredUses::VALIDATE) {
+        output.push_str("use validator::Validate;\n");
+    }
 
     if needs_uuid {
         output.push_str("use uuid::Uuid;\n");
     }
 
-    if needs_datetime || needs_date {
+    if required_uses.contains(RequiredUses::DECIMAL) {
+        output.push_str("use rust_decimal::Decimal;\n");
+    }
+
+    if needs_datetime || needs_date || needs_time {
         output.push_str("use chrono::{");
         let mut chrono_imports = Vec::new();
         if needs_datetime {
@@ -193,6 +665,9 @@ pub fn generate_models(
         if needs_date {
             chrono_imports.push("NaiveDate");
         }
+        if needs_time {
+            chrono_imports.push("NaiveTime");
+        }
         if needs_datetime {
             chrono_imports.push("Utc");
         }
@@ -200,352 +675,6344 @@ pub fn generate_models(
         output.push_str("};\n");
     }
 
+    // x-rust-use imports that aren't already emitted above
+    for path in collect_rust_uses(models) {
+        let line = format!("use {path};\n");
+        if !output.contains(&line) {
+            output.push_str(&line);
+        }
+    }
+
     output.push('\n');
     output.push_str(&models_code);
 
+    if required_uses.contains(RequiredUses::PARSE_ERROR) {
+        output.push_str(&generate_parse_error());
+    }
+
+    if required_uses.contains(RequiredUses::BASE64) {
+        output.push_str(&generate_base64_bytes());
+    }
+
+    if required_uses.contains(RequiredUses::FILE_PART) {
+        output.push_str(&generate_file_part(options));
+    }
+
+    if required_uses.contains(RequiredUses::SECRET) {
+        output.push_str(SECRET_SERIALIZERS);
+    }
+
+    if required_uses.contains(RequiredUses::DOUBLE_OPTION) {
+        output.push_str(DOUBLE_OPTION_DESERIALIZER);
+    }
+
+    if required_uses.contains(RequiredUses::BIG_INTEGER) {
+        output.push_str(BIG_INTEGER_STRING);
+    }
+
+    if required_uses.contains(RequiredUses::EMAIL) {
+        output.push_str(&generate_string_newtype(
+            "Email",
+            "Email address (`format: email`)",
+            "value.split_once('@').is_some_and(|(local, domain)| {\n            \
+             !local.is_empty() && !domain.is_empty() && !domain.contains('@')\n        \
+             }) && !value.contains(char::is_whitespace)",
+            "email address",
+        ));
+    }
+
+    if required_uses.contains(RequiredUses::HOSTNAME) {
+        output.push_str(&generate_string_newtype(
+            "Hostname",
+            "DNS host name (`format: hostname`)",
+            "value.len() <= 253\n            \
+             && value.split('.').all(|label| {\n                \
+             (1..=63).contains(&label.len())\n                    \
+             && label.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')\n                    \
+             && !label.starts_with('-')\n                    \
+             && !label.ends_with('-')\n            \
+             })",
+            "host name",
+        ));
+    }
+
+    if required_uses.contains(RequiredUses::REQUEST_URL) {
+        output.push_str(REQUEST_URL_PARAM);
+    }
+
     Ok(output)
 }
 
-fn generate_model(model: &Model, required_uses: &mut RequiredUses) -> Result<String> {
+fn generate_model(
+    model: &Model,
+    variant: StructVariant,
+    split_models: &HashSet<String>,
+    options: &GenOptions,
+    required_uses: &mut RequiredUses,
+) -> Result<String> {
     let mut output = String::new();
+    let struct_name = variant.struct_name(&model.name);
 
     output.push_str(&generate_description_docs(
         &model.description,
-        &model.name,
+        &struct_name,
         "",
     ));
 
     output.push_str(&generate_custom_attrs(&model.custom_attrs));
 
+    let fields: Vec<Field> = model
+        .fields
+        .iter()
+        .filter(|f| variant.includes(f))
+        .map(|field| Field {
+            field_type: variant.retarget(&field.field_type, split_models),
+            ..field.clone()
+        })
+        .collect();
+    let fields: Vec<(&Field, bool)> = fields.iter().map(|f| (f, variant.is_required(f))).collect();
+
     // Only add default derive if custom_attrs doesn't already contain a derive directive
+    let builder = !has_custom_derive(&model.custom_attrs) && wants_builder(fields.len(), options);
     if !has_custom_derive(&model.custom_attrs) {
-        output.push_str("#[derive(Debug, Clone, Serialize, Deserialize)]\n");
+        check_secret_derives(&struct_name, &fields, &model.derives, options)?;
+        output.push_str(&struct_derive(
+            &model.derives,
+            builder,
+            options,
+            required_uses,
+        ));
+    }
+    output.push_str(&xml_root_attr(&model.xml_name, &struct_name, options));
+    // serde does not support deny_unknown_fields together with flatten
+    if options.deny_unknown_fields
+        && model.deny_unknown_fields
+        && !model.fields.iter().any(Field::should_flatten)
+    {
+        output.push_str("#[serde(deny_unknown_fields)]\n");
     }
 
-    output.push_str(&format!("pub struct {} {{\n", model.name));
-
-    for field in &model.fields {
-        let field_type = match field.field_type.as_str() {
-            "String" => "String",
-            "f64" => "f64",
-            "i64" => "i64",
-            "bool" => "bool",
-            "DateTime" => {
-                *required_uses |= RequiredUses::DATETIME;
-                "DateTime<Utc>"
-            }
-            "Date" => {
-                *required_uses |= RequiredUses::DATE;
-                "NaiveDate"
-            }
-            "Uuid" => {
-                *required_uses |= RequiredUses::UUID;
-                "Uuid"
-            }
-            _ => &field.field_type,
-        };
-
-        let mut lowercased_name = to_snake_case(field.name.as_str());
-        if is_reserved_word(&lowercased_name) {
-            lowercased_name = format!("r#{lowercased_name}")
-        }
+    let rename_all = struct_rename_all(&fields, &model.custom_attrs, options);
+    if let Some(rule) = rename_all {
+        output.push_str(&format!("#[serde(rename_all = \"{rule}\")]\n"));
+    }
+    output.push_str(&format!("pub struct {struct_name} {{\n"));
 
+    for (field, is_required) in &fields {
         // Add field description if present
         output.push_str(&generate_description_docs(&field.description, "", "    "));
-
-        // Only add serde rename if the Rust field name differs from the original field name
-        if lowercased_name != field.name {
-            output.push_str(&format!("    #[serde(rename = \"{}\")]\n", field.name));
-        }
-
-        if field.should_flatten() {
-            output.push_str("    #[serde(flatten)]\n");
-        }
-
-        // If field references an array, wrap it in Vec<>
-        if field.is_array_ref {
-            if field.is_required && !field.is_nullable {
-                output.push_str(&format!("    pub {lowercased_name}: Vec<{field_type}>,\n",));
-            } else {
-                output.push_str(&format!(
-                    "    pub {lowercased_name}: Option<Vec<{field_type}>>,\n",
-                ));
-            }
-        } else if field.is_required && !field.is_nullable {
-            output.push_str(&format!("    pub {lowercased_name}: {field_type},\n",));
-        } else {
-            output.push_str(&format!(
-                "    pub {lowercased_name}: Option<{field_type}>,\n",
-            ));
-        }
+        output.push_str(&generate_field(
+            &struct_name,
+            field,
+            *is_required,
+            rename_all,
+            builder,
+            options,
+            required_uses,
+        ));
     }
 
     output.push_str("}\n\n");
+    output.push_str(&generate_default_support(
+        &struct_name,
+        &fields,
+        &model.custom_attrs,
+        &model.derives,
+        options,
+        required_uses,
+    ));
+    output.push_str(&generate_validation_support(&struct_name, &fields, options));
+    output.push_str(&generate_constructor(
+        &struct_name,
+        &fields,
+        options,
+        required_uses,
+    ));
     Ok(output)
 }
 
-fn generate_request_model(request: &RequestModel) -> Result<String> {
-    let mut output = String::new();
-    tracing::info!("Generating request model");
-    tracing::info!("{:#?}", request);
-
-    if request.name.is_empty() || request.name == EMPTY_REQUEST_NAME {
-        return Ok(String::new());
-    }
-
-    output.push_str(&format!("/// {}\n", request.name));
-    output.push_str("#[derive(Debug, Clone, Serialize)]\n");
-    output.push_str(&format!("pub struct {} {{\n", request.name));
-    output.push_str(&format!("    pub body: {},\n", request.schema));
-    output.push_str("}\n");
-    Ok(output)
+/// Name of a field under `--xml`, from its `xml` object: `@name` for attributes, the
+/// items' `xml.name` for arrays that are not wrapped, `xml.name` otherwise. Wrapped arrays
+/// keep the wrapper name and are (de)serialized as repeated elements. Returns the name
+/// and, with an `xml.prefix`, the prefixed name to serialize; quick-xml matches local
+/// names when deserializing.
+fn xml_field_name(field: &Field, options: &GenOptions) -> Option<(String, Option<String>)> {
+    let xml = field.xml.as_ref().filter(|_| options.xml)?;
+    let name = if field.is_array_ref && !xml.wrapped {
+        xml.items_name.as_deref()
+    } else {
+        xml.name.as_deref()
+    };
+    let name = name.unwrap_or(field.wire_name());
+    let marker = if xml.attribute { "@" } else { "" };
+    let prefixed = xml
+        .prefix
+        .as_ref()
+        .map(|prefix| format!("{marker}{prefix}:{name}"));
+    Some((format!("{marker}{name}"), prefixed))
 }
 
-fn generate_response_model(response: &ResponseModel) -> Result<String> {
-    if response.name.is_empty() || response.name == EMPTY_RESPONSE_NAME {
-        return Ok(String::new());
+/// `#[serde(rename)]` naming the XML root element under `--xml`, when it differs from
+/// the struct name. serde_json ignores container names, so JSON is unaffected.
+fn xml_root_attr(xml_name: &Option<String>, struct_name: &str, options: &GenOptions) -> String {
+    match xml_name {
+        Some(xml_name) if options.xml && xml_name != struct_name => {
+            format!("#[serde(rename = \"{xml_name}\")]\n")
+        }
+        _ => String::new(),
     }
-
-    let type_name = format!("{}{}", response.name, response.status_code);
-
-    let mut output = String::new();
-
-    output.push_str(&generate_description_docs(
-        &response.description,
-        &type_name,
-        "",
-    ));
-
-    output.push_str("#[derive(Debug, Clone, Deserialize)]\n");
-    output.push_str(&format!("pub struct {type_name} {{\n"));
-    output.push_str(&format!("    pub body: {},\n", response.schema));
-    output.push_str("}\n");
-
-    Ok(output)
 }
 
-fn generate_union(union: &UnionModel) -> Result<String> {
+/// Generates the serde attributes and declaration of a single struct field.
+/// `owner` is the name of the struct the field belongs to, `rename_all` the container
+/// rule already renaming its fields, if any, and `builder` whether it derives a builder.
+fn generate_field(
+    owner: &str,
+    field: &Field,
+    is_required: bool,
+    rename_all: Option<&str>,
+    builder: bool,
+    options: &GenOptions,
+    required_uses: &mut RequiredUses,
+) -> String {
     let mut output = String::new();
 
-    output.push_str(&format!(
-        "/// {} ({})\n",
-        union.name,
-        match union.union_type {
-            UnionType::OneOf => "oneOf",
-            UnionType::AnyOf => "anyOf",
-        }
-    ));
-    output.push_str(&generate_custom_attrs(&union.custom_attrs));
-
-    // Only add default derive if custom_attrs doesn't already contain a derive
-    if !has_custom_derive(&union.custom_attrs) {
-        output.push_str("#[derive(Debug, Clone, Serialize, Deserialize)]\n");
+    let mut lowercased_name = to_snake_case(field.name.as_str());
+    if is_reserved_word(&lowercased_name) {
+        lowercased_name = format!("r#{lowercased_name}")
     }
 
-    // Only add default serde(untagged) if custom_attrs doesn't already contain a serde attribute
-    if !has_custom_serde(&union.custom_attrs) {
-        output.push_str("#[serde(untagged)]\n");
+    // Only add serde rename if the Rust field name differs from the original field name;
+    // flattened fields have no key of their own
+    let (serde_name, prefixed_name) =
+        xml_field_name(field, options).unwrap_or_else(|| (field.wire_name().to_string(), None));
+    if let Some(prefixed_name) = prefixed_name {
+        output.push_str(&format!(
+            "    #[serde(rename(serialize = \"{prefixed_name}\", deserialize = \"{serde_name}\"))]\n"
+        ));
+    } else if field_wire_name(field, rename_all) != serde_name && !field.should_flatten() {
+        output.push_str(&format!("    #[serde(rename = \"{serde_name}\")]\n"));
     }
 
-    output.push_str(&format!("pub enum {} {{\n", union.name));
-
-    for variant in &union.variants {
-        match &variant.primitive_type {
-            Some(t) => output.push_str(&format!("    {}({}),\n", variant.name, t)),
-            None => output.push_str(&format!("    {}({}),\n", variant.name, variant.name)),
-        }
+    if field.deprecated {
+        output.push_str(&format!("    {DEPRECATED_ATTR}\n"));
     }
 
-    output.push_str("}\n");
-    Ok(output)
-}
-
-fn generate_composition(
-    comp: &CompositionModel,
-    required_uses: &mut RequiredUses,
-) -> Result<String> {
-    let mut output = String::new();
-
-    output.push_str(&format!("/// {} (allOf composition)\n", comp.name));
-    output.push_str(&generate_custom_attrs(&comp.custom_attrs));
+    if field.should_flatten() {
+        output.push_str("    #[serde(flatten)]\n");
+    }
 
-    // Only add default derive if custom_attrs doesn't already contain a derive
-    if !has_custom_derive(&comp.custom_attrs) {
-        output.push_str("#[derive(Debug, Clone, Serialize, Deserialize)]\n");
+    let shape = FieldShape::of(field, is_required, options);
+    if let Some(serde_with) = &field.serde_with {
+        // `with` disables serde's implicit `None` for absent `Option` fields
+        let default = if shape == FieldShape::Optional && !is_required {
+            "default, "
+        } else {
+            ""
+        };
+        output.push_str(&format!("    #[serde({default}with = \"{serde_with}\")]\n"));
+    } else if is_secret(field, options) {
+        let serializer = match shape {
+            FieldShape::Optional => "serialize_optional_secret",
+            _ => "serialize_secret",
+        };
+        output.push_str(&format!(
+            "    #[serde(serialize_with = \"{serializer}\")]\n"
+        ));
+    } else if is_big_integer(field, options) && !field.is_array_ref {
+        *required_uses |= RequiredUses::BIG_INTEGER;
+        let attr = match shape {
+            FieldShape::Optional => "default, with = \"big_integer_string::option\"",
+            _ => "with = \"big_integer_string\"",
+        };
+        output.push_str(&format!("    #[serde({attr})]\n"));
+    } else if is_decimal(field, options) && !field.is_array_ref {
+        // `with` disables serde's implicit `None` for absent `Option` fields
+        let attr = match shape {
+            FieldShape::Optional => "default, with = \"rust_decimal::serde::str_option\"",
+            _ => "with = \"rust_decimal::serde::str\"",
+        };
+        output.push_str(&format!("    #[serde({attr})]\n"));
     }
 
-    output.push_str(&format!("pub struct {} {{\n", comp.name));
+    if options.validate {
+        output.push_str(&generate_validate_attrs(owner, field, options));
+    }
 
-    for field in &comp.all_fields {
-        let field_type = match field.field_type.as_str() {
-            "String" => "String",
-            "f64" => "f64",
-            "i64" => "i64",
-            "bool" => "bool",
-            "DateTime" => {
-                *required_uses |= RequiredUses::DATETIME;
-                "DateTime<Utc>"
+    match shape {
+        FieldShape::Defaulted => {
+            output.push_str(&format!(
+                "    #[serde(default = \"{}\")]\n",
+                default_fn_name(owner, field)
+            ));
+        }
+        FieldShape::DefaultedVec => {
+            output.push_str(&format!(
+                "    #[serde(default, skip_serializing_if = \"{}::is_empty\")]\n",
+                array_collection(field, options)
+            ));
+        }
+        FieldShape::Optional => {
+            let mut attrs = Vec::new();
+            if has_optional_default(field, shape, is_required, options) {
+                attrs.push(format!("default = \"{}\"", default_fn_name(owner, field)));
             }
-            "Date" => {
-                *required_uses |= RequiredUses::DATE;
-                "NaiveDate"
+            if options.skip_none {
+                attrs.push("skip_serializing_if = \"Option::is_none\"".to_string());
             }
-            "Uuid" => {
-                *required_uses |= RequiredUses::UUID;
-                "Uuid"
+            if !attrs.is_empty() {
+                output.push_str(&format!("    #[serde({})]\n", attrs.join(", ")));
             }
-            _ => &field.field_type,
-        };
-
-        let mut lowercased_name = to_snake_case(field.name.as_str());
-        if is_reserved_word(&lowercased_name) {
-            lowercased_name = format!("r#{lowercased_name}");
         }
-
-        // Only add serde rename if the Rust field name differs from the original field name
-        if lowercased_name != field.name {
-            output.push_str(&format!("    #[serde(rename = \"{}\")]\n", field.name));
+        FieldShape::DoubleOption => {
+            *required_uses |= RequiredUses::DOUBLE_OPTION;
+            output.push_str(
+                "    #[serde(default, skip_serializing_if = \"Option::is_none\", \
+                 deserialize_with = \"deserialize_double_option\")]\n",
+            );
         }
+        _ => {}
+    }
 
-        // If field references an array, wrap it in Vec<>
-        if field.is_array_ref {
-            if field.is_required && !field.is_nullable {
-                output.push_str(&format!("    pub {lowercased_name}: Vec<{field_type}>,\n",));
-            } else {
-                output.push_str(&format!(
-                    "    pub {lowercased_name}: Option<Vec<{field_type}>>,\n",
-                ));
-            }
-        } else if field.is_required && !field.is_nullable {
-            output.push_str(&format!("    pub {lowercased_name}: {field_type},\n",));
-        } else {
-            output.push_str(&format!(
-                "    pub {lowercased_name}: Option<{field_type}>,\n",
-            ));
-        }
+    if builder {
+        output.push_str(&builder_field_attr(
+            owner,
+            field,
+            shape,
+            is_required,
+            options,
+        ));
     }
 
-    output.push_str("}\n");
-    Ok(output)
+    output.push_str(&format!(
+        "    pub {lowercased_name}: {},\n",
+        field_rust_type(field, shape, options, required_uses)
+    ));
+
+    output
 }
 
-fn generate_enum(enum_model: &EnumModel) -> Result<String> {
-    let mut output = String::new();
+/// How a field is declared, which decides its Rust type and serde defaults
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FieldShape {
+    /// Always present: `T`
+    Required,
+    /// Absent or null: `Option<T>`
+    Optional,
+    /// Falls back to the schema `default` when absent: `T`
+    Defaulted,
+    /// Under skip-none, an absent non-nullable array deserializes as empty: `Vec<T>`
+    DefaultedVec,
+    /// Under double-option, absent (`None`), null (`Some(None)`) or set: `Option<Option<T>>`
+    DoubleOption,
+}
 
-    output.push_str(&generate_description_docs(
-        &enum_model.description,
-        &enum_model.name,
-        "",
-    ));
+impl FieldShape {
+    fn of(field: &Field, is_required: bool, options: &GenOptions) -> Self {
+        // Fields (de)serialized through `with` helpers keep their single `Option`
+        let wants_double_option =
+            field.double_option || (field.is_nullable && !is_required && options.double_option);
+        if wants_double_option
+            && field.serde_with.is_none()
+            && !is_secret(field, options)
+            && !is_decimal(field, options)
+            && !is_big_integer(field, options)
+        {
+            FieldShape::DoubleOption
+        } else if field.is_nullable {
+            FieldShape::Optional
+        } else if field.default.is_some() {
+            FieldShape::Defaulted
+        } else if is_required {
+            FieldShape::Required
+        } else if options.skip_none
+            && field.is_array_ref
+            && fixed_array_len(field, options).is_none()
+        {
+            FieldShape::DefaultedVec
+        } else {
+            FieldShape::Optional
+        }
+    }
+}
 
-    output.push_str(&generate_custom_attrs(&enum_model.custom_attrs));
+/// `#[builder(...)]` for a field of a struct deriving `TypedBuilder`: setters take
+/// anything `Into` the field type, and fields that can be absent get the value serde
+/// would give them, so the builder only demands required fields
+fn builder_field_attr(
+    owner: &str,
+    field: &Field,
+    shape: FieldShape,
+    is_required: bool,
+    options: &GenOptions,
+) -> String {
+    let attr = match shape {
+        FieldShape::Required => "setter(into)".to_string(),
+        FieldShape::Defaulted => {
+            format!(
+                "default = {}(), setter(into)",
+                default_fn_name(owner, field)
+            )
+        }
+        FieldShape::Optional if has_optional_default(field, shape, is_required, options) => {
+            format!(
+                "default = {}(), setter(strip_option, into)",
+                default_fn_name(owner, field)
+            )
+        }
+        FieldShape::Optional => "default, setter(strip_option, into)".to_string(),
+        FieldShape::DefaultedVec | FieldShape::DoubleOption => "default, setter(into)".to_string(),
+    };
+    format!("    #[builder({attr})]\n")
+}
 
-    // Only add default derive if custom_attrs doesn't already contain a derive
-    if !has_custom_derive(&enum_model.custom_attrs) {
-        output.push_str("#[derive(Debug, Clone, Serialize, Deserialize)]\n");
+/// Whether an optional field falls back to its schema `default` when absent: a nullable
+/// field keeps `Option<T>` for `null`, so the default becomes `Some(value)`. Fields
+/// (de)serialized through `with` helpers keep serde's plain `None`.
+fn has_optional_default(
+    field: &Field,
+    shape: FieldShape,
+    is_required: bool,
+    options: &GenOptions,
+) -> bool {
+    shape == FieldShape::Optional
+        && !is_required
+        && field.default.as_ref().is_some_and(|value| !value.is_null())
+        && field.serde_with.is_none()
+        && !is_secret(field, options)
+        && !is_decimal(field, options)
+        && !is_big_integer(field, options)
+}
+
+/// Returns the Rust type a field is declared with
+fn field_rust_type(
+    field: &Field,
+    shape: FieldShape,
+    options: &GenOptions,
+    required_uses: &mut RequiredUses,
+) -> String {
+    let field_type = match field.field_type.as_str() {
+        _ if is_secret(field, options) => {
+            *required_uses |= RequiredUses::SECRET;
+            "secrecy::SecretString"
+        }
+        "String" if options.duration && field.format == "duration" => "iso8601_duration::Duration",
+        _ if is_decimal(field, options) => {
+            *required_uses |= RequiredUses::DECIMAL;
+            "Decimal"
+        }
+        // Other string formats fall through to the flags handling them below
+        "String" if options.format_newtypes && field.format == "email" => {
+            *required_uses |= RequiredUses::EMAIL;
+            "Email"
+        }
+        "String" if options.format_newtypes && field.format == "hostname" => {
+            *required_uses |= RequiredUses::HOSTNAME;
+            "Hostname"
+        }
+        "String" if options.format_newtypes && field.format == "uri" => "url::Url",
+        "std::net::Ipv4Addr" | "std::net::Ipv6Addr" if options.ip_as_string => "String",
+        "Vec<u8>" if options.bytes && field.format == "binary" => "bytes::Bytes",
+        "String" => "String",
+        "f64" => "f64",
+        "i32" | "i64" => integer_rust_type(field, options),
+        "bool" => "bool",
+        "DateTime<Utc>" => {
+            *required_uses |= RequiredUses::DATETIME;
+            "DateTime<Utc>"
+        }
+        "NaiveDate" => {
+            *required_uses |= RequiredUses::DATE;
+            "NaiveDate"
+        }
+        "NaiveTime" => {
+            *required_uses |= RequiredUses::TIME;
+            "NaiveTime"
+        }
+        "Uuid" => {
+            *required_uses |= RequiredUses::UUID;
+            "Uuid"
+        }
+        BASE64_BYTES => {
+            *required_uses |= RequiredUses::BASE64;
+            BASE64_BYTES
+        }
+        FILE_PART => {
+            *required_uses |= RequiredUses::FILE_PART;
+            FILE_PART
+        }
+        // Compound types such as tuples may still name chrono or uuid types
+        _ => {
+            *required_uses |= type_expr_uses(&field.field_type);
+            &field.field_type
+        }
+    };
+
+    // If field references an array, wrap it in Vec<> (or a set for unique items)
+    let inner = if let Some(len) = fixed_array_len(field, options) {
+        format!("[{field_type}; {len}]")
+    } else if field.is_array_ref {
+        format!("{}<{field_type}>", array_collection(field, options))
+    } else {
+        field_type.to_string()
+    };
+
+    match shape {
+        FieldShape::Optional => format!("Option<{inner}>"),
+        FieldShape::DoubleOption => format!("Option<Option<{inner}>>"),
+        _ => inner,
+    }
+}
+
+/// Integer type of a field: under `--unsigned`, `uint8` to `uint64` formats and integers
+/// with a non-negative `minimum` are unsigned, `int32` ones as `u32`
+fn integer_rust_type(field: &Field, options: &GenOptions) -> &'static str {
+    if is_big_integer(field, options) {
+        return match options.big_integers {
+            Some(BigIntType::Bigint) => "num_bigint::BigInt",
+            _ => "i128",
+        };
+    }
+    let signed = if field.field_type == "i32" {
+        "i32"
+    } else {
+        "i64"
+    };
+    if !options.unsigned {
+        return signed;
+    }
+    let non_negative = field
+        .constraints
+        .minimum
+        .as_ref()
+        .and_then(serde_json::Number::as_f64)
+        .is_some_and(|minimum| minimum >= 0.0);
+    match field.format.as_str() {
+        "uint8" => "u8",
+        "uint16" => "u16",
+        "uint32" => "u32",
+        "uint64" | "uint" => "u64",
+        _ if non_negative && signed == "i32" => "u32",
+        _ if non_negative => "u64",
+        _ => signed,
     }
+}
 
-    output.push_str(&format!("pub enum {} {{\n", enum_model.name));
+/// Length of an array field generated as `[T; N]`: under `--fixed-arrays`, when
+/// `minItems == maxItems` and serde can (de)serialize arrays of that length
+fn fixed_array_len(field: &Field, options: &GenOptions) -> Option<usize> {
+    let constraints = &field.constraints;
+    match (constraints.min_items, constraints.max_items) {
+        (Some(min), Some(max))
+            if options.fixed_arrays
+                && field.is_array_ref
+                && min == max
+                && (1..=32).contains(&min) =>
+        {
+            Some(min)
+        }
+        _ => None,
+    }
+}
 
-    for (i, variant) in enum_model.variants.iter().enumerate() {
-        let original = variant.clone();
+/// Collection an array field is generated as: a set when the spec asks for unique items,
+/// a set type is configured and the items are ordered, hashable scalars; `Vec` otherwise
+fn array_collection(field: &Field, options: &GenOptions) -> &'static str {
+    let scalar_items = is_plain_string(field, options)
+        || matches!(
+            field.field_type.as_str(),
+            "i32" | "i64" | "bool" | "Uuid" | "NaiveDate" | "NaiveTime" | "DateTime<Utc>"
+        );
+    match options.unique_items {
+        Some(set_type) if field.constraints.unique_items && scalar_items => match set_type {
+            SetType::BtreeSet => "std::collections::BTreeSet",
+            SetType::IndexSet => "indexmap::IndexSet",
+        },
+        _ => "Vec",
+    }
+}
 
-        let mut rust_name = crate::parser::to_pascal_case(variant);
+/// Whether a field is generated as `secrecy::SecretString` under `--secrets`. Arrays of
+/// secrets stay plain strings.
+fn is_secret(field: &Field, options: &GenOptions) -> bool {
+    options.secrets
+        && field.format == "password"
+        && field.field_type == "String"
+        && !field.is_array_ref
+}
 
-        let serde_rename = if is_reserved_word(&rust_name) {
-            rust_name.push_str("Value");
-            Some(original)
-        } else if rust_name != original {
-            Some(original)
-        } else {
-            None
+/// Whether a field is generated as `i128` or `num_bigint::BigInt` under `--big-integers`:
+/// `format: bigint` or `int128`, also set on load for an `x-precision` above 18 digits
+fn is_big_integer(field: &Field, options: &GenOptions) -> bool {
+    options.big_integers.is_some()
+        && matches!(field.format.as_str(), "bigint" | "int128")
+        && matches!(field.field_type.as_str(), "i64" | "i32")
+}
+
+/// Whether a field is generated as `rust_decimal::Decimal` under `--decimal`
+fn is_decimal(field: &Field, options: &GenOptions) -> bool {
+    options.decimal
+        && field.format == "decimal"
+        && matches!(field.field_type.as_str(), "String" | "f64" | "f32")
+}
+
+/// Whether a field is generated as a plain `String`, which string constraints apply to
+fn is_plain_string(field: &Field, options: &GenOptions) -> bool {
+    // Formats generated as other types under their flags
+    let mapped = match field.format.as_str() {
+        "email" | "hostname" | "uri" => options.format_newtypes,
+        "decimal" => options.decimal,
+        "duration" => options.duration,
+        "password" => is_secret(field, options),
+        _ => false,
+    };
+    field.field_type == "String" && !mapped
+}
+
+/// Generates the `#[validate(...)]` attributes for a field's spec constraints
+fn generate_validate_attrs(owner: &str, field: &Field, options: &GenOptions) -> String {
+    let constraints = &field.constraints;
+    let mut rules = Vec::new();
+
+    let is_string = is_plain_string(field, options);
+    let length = if fixed_array_len(field, options).is_some() {
+        // The array type already pins the length
+        (None, None)
+    } else if field.is_array_ref && array_collection(field, options) == "indexmap::IndexSet" {
+        // validator only measures an IndexSet with its own `indexmap` feature enabled
+        (None, None)
+    } else if field.is_array_ref {
+        (constraints.min_items, constraints.max_items)
+    } else if is_string {
+        (constraints.min_length, constraints.max_length)
+    } else {
+        (None, None)
+    };
+    let bounds: Vec<String> = [("min", length.0), ("max", length.1)]
+        .into_iter()
+        .filter_map(|(key, bound)| bound.map(|bound| format!("{key} = {bound}")))
+        .collect();
+    if !bounds.is_empty() {
+        rules.push(format!("length({})", bounds.join(", ")));
+    }
+
+    let min_key = if constraints.exclusive_minimum {
+        "exclusive_min"
+    } else {
+        "min"
+    };
+    let max_key = if constraints.exclusive_maximum {
+        "exclusive_max"
+    } else {
+        "max"
+    };
+    let bounds: Vec<String> = [
+        (min_key, &constraints.minimum),
+        (max_key, &constraints.maximum),
+    ]
+    .into_iter()
+    .filter_map(|(key, bound)| {
+        bound
+            .as_ref()
+            .map(|bound| format!("{key} = {}", range_literal(bound, &field.field_type)))
+    })
+    .collect();
+    if !bounds.is_empty() && !field.is_array_ref && !is_decimal(field, options) {
+        rules.push(format!("range({})", bounds.join(", ")));
+    }
+
+    if constraints.pattern.is_some() && is_string && !field.is_array_ref {
+        rules.push(format!(
+            "regex(path = *{})",
+            regex_static_name(owner, field)
+        ));
+    }
+
+    rules
+        .into_iter()
+        .map(|rule| format!("    #[validate({rule})]\n"))
+        .collect()
+}
+
+/// Renders a range bound as a literal matching the field's numeric type
+fn range_literal(bound: &serde_json::Number, rust_type: &str) -> String {
+    let literal = bound.to_string();
+    if matches!(rust_type, "f64" | "f32") && !literal.contains(['.', 'e', 'E']) {
+        format!("{literal}.0")
+    } else {
+        literal
+    }
+}
+
+/// Name of the static holding a field's compiled `pattern`
+fn regex_static_name(owner: &str, field: &Field) -> String {
+    format!(
+        "RE_{}_{}",
+        to_snake_case(owner).to_uppercase(),
+        to_snake_case(&field.name).to_uppercase()
+    )
+}
+
+/// Generates the lazily compiled regexes referenced by `#[validate(regex(...))]`
+fn generate_validation_support(
+    struct_name: &str,
+    fields: &[(&Field, bool)],
+    options: &GenOptions,
+) -> String {
+    if !options.validate {
+        return String::new();
+    }
+
+    let mut output = String::new();
+    for (field, _) in fields {
+        if field.is_array_ref || !is_plain_string(field, options) {
+            continue;
+        }
+        if let Some(pattern) = &field.constraints.pattern {
+            output.push_str(&format!(
+                "static {}: once_cell::sync::Lazy<regex::Regex> =\n    once_cell::sync::Lazy::new(|| regex::Regex::new({pattern:?}).expect(\"invalid pattern\"));\n\n",
+                regex_static_name(struct_name, field)
+            ));
+        }
+    }
+    output
+}
+
+/// Generates `new` under `--constructors`, taking the required fields in declaration
+/// order and filling the others with `None`, their schema default or an empty
+/// collection. Structs without required fields get `impl Default` instead.
+fn generate_constructor(
+    struct_name: &str,
+    fields: &[(&Field, bool)],
+    options: &GenOptions,
+    required_uses: &mut RequiredUses,
+) -> String {
+    let shapes: Vec<FieldShape> = fields
+        .iter()
+        .map(|(field, is_required)| FieldShape::of(field, *is_required, options))
+        .collect();
+    if !options.constructors || !shapes.contains(&FieldShape::Required) {
+        return String::new();
+    }
+
+    let mut params = Vec::new();
+    let mut inits = Vec::new();
+    for ((field, is_required), shape) in fields.iter().zip(&shapes) {
+        let mut name = to_snake_case(&field.name);
+        if is_reserved_word(&name) {
+            name = format!("r#{name}");
+        }
+        let value = match shape {
+            FieldShape::Required => {
+                let rust_type = field_rust_type(field, *shape, options, required_uses);
+                params.push(format!("{name}: {rust_type}"));
+                inits.push(format!("            {name},\n"));
+                continue;
+            }
+            FieldShape::Defaulted => format!("{}()", default_fn_name(struct_name, field)),
+            _ if has_optional_default(field, *shape, *is_required, options) => {
+                format!("{}()", default_fn_name(struct_name, field))
+            }
+            FieldShape::DefaultedVec => "Default::default()".to_string(),
+            _ => "None".to_string(),
         };
+        inits.push(format!("            {name}: {value},\n"));
+    }
+
+    let mut output = format!("impl {struct_name} {{\n");
+    if params.len() > 7 {
+        output.push_str("    #[allow(clippy::too_many_arguments)]\n");
+    }
+    output.push_str(&format!(
+        "    pub fn new({}) -> Self {{\n",
+        params.join(", ")
+    ));
+    output.push_str("        Self {\n");
+    output.push_str(&inits.concat());
+    output.push_str("        }\n");
+    output.push_str("    }\n");
+    output.push_str("}\n\n");
+    output
+}
+
+/// Name of the function providing a field's schema default
+fn default_fn_name(owner: &str, field: &Field) -> String {
+    format!(
+        "default_{}_{}",
+        to_snake_case(owner),
+        to_snake_case(&field.name)
+    )
+}
 
-        if let Some(rename) = serde_rename {
-            output.push_str(&format!("    #[serde(rename = \"{rename}\")]\n"));
+/// Renders a schema default as a Rust expression of the given type. Values without a
+/// plain literal form are parsed from their JSON text at runtime.
+fn default_value_expr(value: &serde_json::Value, rust_type: &str) -> String {
+    match (value, rust_type) {
+        (serde_json::Value::String(s), "String") => format!("{s:?}.to_string()"),
+        (serde_json::Value::Bool(b), "bool") => b.to_string(),
+        (serde_json::Value::Number(n), "i64" | "i32" | "u64" | "u32" | "u16" | "u8" | "i128")
+            if n.is_i64() || n.is_u64() =>
+        {
+            n.to_string()
+        }
+        (serde_json::Value::Number(n), "num_bigint::BigInt") if n.is_i64() || n.is_u64() => {
+            format!("num_bigint::BigInt::from({n}_i128)")
         }
+        (serde_json::Value::Number(n), "f64" | "f32") => {
+            let literal = n.to_string();
+            if literal.contains(['.', 'e', 'E']) {
+                literal
+            } else {
+                format!("{literal}.0")
+            }
+        }
+        _ => format!(
+            "serde_json::from_str({:?}).expect(\"invalid default\")",
+            value.to_string()
+        ),
+    }
+}
 
-        if i + 1 == enum_model.variants.len() {
-            output.push_str(&format!("    {rust_name}\n"));
-        } else {
-            output.push_str(&format!("    {rust_name},\n"));
+/// Generates the default functions referenced by `#[serde(default = "...")]` and, when
+/// every field can be defaulted and `Default` is not derived, an `impl Default` for the
+/// struct
+fn generate_default_support(
+    struct_name: &str,
+    fields: &[(&Field, bool)],
+    custom_attrs: &Option<Vec<String>>,
+    derives: &Option<Vec<String>>,
+    options: &GenOptions,
+    required_uses: &mut RequiredUses,
+) -> String {
+    let mut output = String::new();
+    let shapes: Vec<FieldShape> = fields
+        .iter()
+        .map(|(field, is_required)| FieldShape::of(field, *is_required, options))
+        .collect();
+    let optional_defaults: Vec<bool> = fields
+        .iter()
+        .zip(&shapes)
+        .map(|((field, is_required), shape)| {
+            has_optional_default(field, *shape, *is_required, options)
+        })
+        .collect();
+
+    if !shapes.contains(&FieldShape::Defaulted) && !optional_defaults.contains(&true) {
+        return output;
+    }
+
+    for (((field, _), shape), optional_default) in
+        fields.iter().zip(&shapes).zip(&optional_defaults)
+    {
+        let Some(value) = &field.default else {
+            continue;
+        };
+        if *shape == FieldShape::Defaulted {
+            let rust_type = field_rust_type(field, *shape, options, required_uses);
+            output.push_str(&format!(
+                "fn {}() -> {rust_type} {{\n    {}\n}}\n\n",
+                default_fn_name(struct_name, field),
+                default_value_expr(value, &rust_type)
+            ));
+        } else if *optional_default {
+            let rust_type = field_rust_type(field, FieldShape::Required, options, required_uses);
+            output.push_str(&format!(
+                "fn {}() -> Option<{rust_type}> {{\n    Some({})\n}}\n\n",
+                default_fn_name(struct_name, field),
+                default_value_expr(value, &rust_type)
+            ));
         }
     }
 
-    output.push_str("}\n");
-    Ok(output)
+    if derives_default(custom_attrs, derives, options) || shapes.contains(&FieldShape::Required) {
+        return output;
+    }
+
+    output.push_str(&format!("impl Default for {struct_name} {{\n"));
+    output.push_str("    fn default() -> Self {\n");
+    output.push_str("        Self {\n");
+    for (((field, _), shape), optional_default) in
+        fields.iter().zip(&shapes).zip(&optional_defaults)
+    {
+        let mut name = to_snake_case(&field.name);
+        if is_reserved_word(&name) {
+            name = format!("r#{name}");
+        }
+        let value = match shape {
+            FieldShape::Defaulted => format!("{}()", default_fn_name(struct_name, field)),
+            _ if *optional_default => format!("{}()", default_fn_name(struct_name, field)),
+            FieldShape::DefaultedVec => "Default::default()".to_string(),
+            _ => "None".to_string(),
+        };
+        output.push_str(&format!("            {name}: {value},\n"));
+    }
+    output.push_str("        }\n");
+    output.push_str("    }\n");
+    output.push_str("}\n\n");
+
+    output
 }
 
-fn generate_type_alias(type_alias: &TypeAliasModel) -> Result<String> {
+/// Fields of a params struct. Parameters in different locations may share a name, e.g. a
+/// `verbose` query parameter and header, so a field whose Rust name is already taken gets
+/// its location appended and is renamed back to the parameter's name.
+fn unique_param_fields(params: &ParamsModel) -> Vec<Field> {
+    let mut taken = HashSet::new();
+    params
+        .fields
+        .iter()
+        .map(|param| {
+            let field = &param.field;
+            if taken.insert(to_snake_case(&field.name)) {
+                return field.clone();
+            }
+            let name = format!("{}_{}", to_snake_case(&field.name), param.location.as_str());
+            taken.insert(name.clone());
+            Field {
+                name,
+                rename: Some(field.name.clone()),
+                ..field.clone()
+            }
+        })
+        .collect()
+}
+
+fn generate_params(
+    params: &ParamsModel,
+    options: &GenOptions,
+    required_uses: &mut RequiredUses,
+) -> Result<String> {
     let mut output = String::new();
 
-    output.push_str(&generate_description_docs(
-        &type_alias.description,
-        &type_alias.name,
-        "",
+    output.push_str(&format!("/// {}\n", params.name));
+    if params.deprecated {
+        output.push_str(&format!("{DEPRECATED_ATTR}\n"));
+    }
+    let fields = unique_param_fields(params);
+    let param_fields: Vec<(&Field, bool)> = fields.iter().map(|f| (f, f.is_required)).collect();
+    let builder = wants_builder(param_fields.len(), options);
+    check_secret_derives(&params.name, &param_fields, &None, options)?;
+    output.push_str(&struct_derive(&None, builder, options, required_uses));
+    let rename_all = struct_rename_all(&param_fields, &None, options);
+    if let Some(rule) = rename_all {
+        output.push_str(&format!("#[serde(rename_all = \"{rule}\")]\n"));
+    }
+    output.push_str(&format!("pub struct {} {{\n", params.name));
+
+    for (param, field) in params.fields.iter().zip(&fields) {
+        let fallback = match param.location {
+            ParameterLocation::Path => "Path parameter",
+            ParameterLocation::Query => "Query parameter",
+            ParameterLocation::Header => "Header parameter",
+            ParameterLocation::Cookie => "Cookie parameter",
+        };
+        output.push_str(&generate_description_docs(
+            &field.description,
+            fallback,
+            "    ",
+        ));
+        output.push_str(&generate_field(
+            &params.name,
+            field,
+            field.is_required,
+            rename_all,
+            builder,
+            options,
+            required_uses,
+        ));
+    }
+
+    output.push_str("}\n\n");
+    output.push_str(&generate_default_support(
+        &params.name,
+        &param_fields,
+        &None,
+        &None,
+        options,
+        required_uses,
+    ));
+    output.push_str(&generate_validation_support(
+        &params.name,
+        &param_fields,
+        options,
+    ));
+    output.push_str(&generate_constructor(
+        &params.name,
+        &param_fields,
+        options,
+        required_uses,
     ));
 
-    output.push_str(&generate_custom_attrs(&type_alias.custom_attrs));
-    output.push_str(&format!(
-        "pub type {} = {};\n\n",
-        type_alias.name, type_alias.target_type
+    // Cookies share a single header, so list them for the client to assemble it
+    let cookies: Vec<String> = params
+        .fields
+        .iter()
+        .filter(|param| param.location == ParameterLocation::Cookie)
+        .map(|param| format!("\"{}\"", param.field.name))
+        .collect();
+    if !cookies.is_empty() {
+        output.push_str(&format!("impl {} {{\n", params.name));
+        output.push_str("    /// Wire names of the fields sent in the `Cookie` header\n");
+        output.push_str(&format!(
+            "    pub const COOKIE_PARAMS: &'static [&'static str] = &[{}];\n",
+            cookies.join(", ")
+        ));
+        output.push_str("}\n\n");
+    }
+
+    output.push_str(&generate_request_url(
+        params,
+        &fields,
+        options,
+        required_uses,
     ));
 
     Ok(output)
 }
 
-pub fn generate_rust_code(models: &[Model]) -> Result<String> {
-    let mut code = create_header();
+/// Generates `to_request_url` for an operation's params, filling the path template with
+/// the path parameters and appending the query parameters that are set; arrays repeat
+/// their key; secrets are exposed into the URL. Skipped for webhooks and callbacks, which
+/// have no path.
+fn generate_request_url(
+    params: &ParamsModel,
+    fields: &[Field],
+    options: &GenOptions,
+    required_uses: &mut RequiredUses,
+) -> String {
+    let Some(path) = &params.path else {
+        return String::new();
+    };
+    let url_fields: Vec<(&Field, ParameterLocation)> = params
+        .fields
+        .iter()
+        .zip(fields)
+        .map(|(param, field)| (field, param.location))
+        .filter(|(_, location)| {
+            matches!(location, ParameterLocation::Path | ParameterLocation::Query)
+        })
+        .collect();
+    *required_uses |= RequiredUses::REQUEST_URL;
 
-    code.push_str("use serde::{Serialize, Deserialize};\n");
-    code.push_str("use uuid::Uuid;\n");
-    code.push_str("use chrono::{DateTime, NaiveDate, Utc};\n\n");
+    let ident = |field: &Field| {
+        let name = to_snake_case(&field.name);
+        if is_reserved_word(&name) {
+            format!("r#{name}")
+        } else {
+            name
+        }
+    };
+    // `SecretString` is not `Serialize`, so its value is passed as the `&str` it wraps
+    let url_param = |field: &Field, value: &str| {
+        if is_secret(field, options) {
+            format!("url_param(secrecy::ExposeSecret::expose_secret({value}))")
+        } else {
+            format!("url_param({value})")
+        }
+    };
 
-    for model in models {
-        code.push_str(&format!("/// {}\n", model.name));
-        code.push_str("#[derive(Debug, Clone, Serialize, Deserialize)]\n");
-        code.push_str(&format!("pub struct {} {{\n", model.name));
+    // Literal braces are escaped; a template variable without a path parameter stays as is
+    let mut template = String::from("{}");
+    let mut args = vec!["base.trim_end_matches('/')".to_string()];
+    let mut rest = path.as_str();
+    while let Some(start) = rest.find('{') {
+        let Some(len) = rest[start..].find('}') else {
+            break;
+        };
+        template.push_str(&rest[..start].replace('}', "}}"));
+        let variable = &rest[start + 1..start + len];
+        let path_field = url_fields.iter().find(|(field, location)| {
+            *location == ParameterLocation::Path && field.wire_name() == variable
+        });
+        match path_field {
+            Some((field, _)) => {
+                template.push_str("{}");
+                args.push(url_param(field, &format!("&self.{}", ident(field))));
+            }
+            None => template.push_str(&format!("{{{{{variable}}}}}")),
+        }
+        rest = &rest[start + len + 1..];
+    }
+    template.push_str(&rest.replace('{', "{{").replace('}', "}}"));
 
-        for field in &model.fields {
-            let field_type = match field.field_type.as_str() {
-                "String" => "String",
-                "f64" => "f64",
-                "i64" => "i64",
-                "bool" => "bool",
-                "DateTime" => "DateTime<Utc>",
-                "Date" => "NaiveDate",
-                "Uuid" => "Uuid",
-                _ => &field.field_type,
-            };
+    let query: Vec<&Field> = url_fields
+        .iter()
+        .filter(|(_, location)| *location == ParameterLocation::Query)
+        .map(|(field, _)| *field)
+        .collect();
 
-            let mut lowercased_name = to_snake_case(field.name.as_str());
-            if is_reserved_word(&lowercased_name) {
-                lowercased_name = format!("r#{lowercased_name}")
+    let mut output = format!("impl {} {{\n", params.name);
+    output.push_str(
+        "    /// URL of the operation under `base`, with the path parameters filled in and the\n",
+    );
+    output.push_str("    /// query parameters that are set appended, percent-encoded\n");
+    output.push_str("    pub fn to_request_url(&self, base: &str) -> String {\n");
+    let url = format!("format!({template:?}, {})", args.join(", "));
+    if query.is_empty() {
+        output.push_str(&format!("        {url}\n"));
+        output.push_str("    }\n}\n\n");
+        return output;
+    }
+
+    output.push_str(&format!("        let mut url = {url};\n"));
+    output.push_str("        let mut query: Vec<String> = Vec::new();\n");
+    for field in query {
+        let key = percent_encode(field.wire_name());
+        let push = |value: &str| {
+            format!(
+                "query.push(format!(\"{key}={{}}\", {}));",
+                url_param(field, value)
+            )
+        };
+        let name = ident(field);
+        // Unset and null parameters are left out of the query
+        let pattern = match FieldShape::of(field, field.is_required, options) {
+            FieldShape::Optional => Some("Some(value)"),
+            FieldShape::DoubleOption => Some("Some(Some(value))"),
+            FieldShape::Required | FieldShape::Defaulted | FieldShape::DefaultedVec => None,
+        };
+        let is_optional = pattern.is_some();
+        let (value, indent) = match pattern {
+            Some(pattern) => {
+                output.push_str(&format!("        if let {pattern} = &self.{name} {{\n"));
+                ("value".to_string(), "            ")
             }
+            None => (format!("&self.{name}"), "        "),
+        };
+        if field.is_array_ref {
+            output.push_str(&format!("{indent}for item in {value} {{\n"));
+            output.push_str(&format!("{indent}    {}\n", push("item")));
+            output.push_str(&format!("{indent}}}\n"));
+        } else {
+            output.push_str(&format!("{indent}{}\n", push(&value)));
+        }
+        if is_optional {
+            output.push_str("        }\n");
+        }
+    }
+    output.push_str("        if !query.is_empty() {\n");
+    output.push_str("            url.push('?');\n");
+    output.push_str("            url.push_str(&query.join(\"&\"));\n");
+    output.push_str("        }\n");
+    output.push_str("        url\n");
+    output.push_str("    }\n}\n\n");
+    output
+}
 
-            // Only add serde rename if the Rust field name differs from the original field name
-            if lowercased_name != field.name {
-                code.push_str(&format!("    #[serde(rename = \"{}\")]\n", field.name));
+/// Percent-encodes everything but the unreserved characters of RFC 3986, as the
+/// generated `url_param` does
+fn percent_encode(value: &str) -> String {
+    value
+        .bytes()
+        .map(|byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                char::from(byte).to_string()
             }
+            _ => format!("%{byte:02X}"),
+        })
+        .collect()
+}
 
-            if field.is_required {
-                code.push_str(&format!("    pub {lowercased_name}: {field_type},\n",));
-            } else {
-                code.push_str(&format!(
-                    "    pub {lowercased_name}: Option<{field_type}>,\n",
-                ));
+/// Formats a path or query parameter for `to_request_url`: strings as they are, other
+/// values as JSON, percent-encoding everything but the unreserved characters of RFC 3986
+const REQUEST_URL_PARAM: &str = r#"
+#[allow(dead_code)]
+fn url_param<T: Serialize + ?Sized>(value: &T) -> String {
+    let text = match serde_json::to_value(value) {
+        Ok(serde_json::Value::String(text)) => text,
+        Ok(value) => value.to_string(),
+        Err(_) => String::new(),
+    };
+    text.bytes()
+        .map(|byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                char::from(byte).to_string()
             }
-        }
+            _ => format!("%{byte:02X}"),
+        })
+        .collect()
+}
+"#;
 
-        code.push_str("}\n\n");
+fn generate_request_model(
+    request: &RequestModel,
+    split_models: &HashSet<String>,
+    options: &GenOptions,
+) -> Result<String> {
+    let mut output = String::new();
+    tracing::debug!("Generating request model");
+    tracing::debug!("{:#?}", request);
+
+    if request.name.is_empty() || request.name == EMPTY_REQUEST_NAME {
+        return Ok(String::new());
     }
 
-    Ok(code)
+    output.push_str(&format!("/// {}\n", request.name));
+    if request.deprecated {
+        output.push_str(&format!("{DEPRECATED_ATTR}\n"));
+    }
+    output.push_str("#[derive(Debug, Clone, Serialize)]\n");
+    output.push_str(&format!("pub struct {} {{\n", request.name));
+    output.push_str(&format!(
+        "    pub body: {},\n",
+        body_type(
+            StructVariant::Write.retarget(&request.schema, split_models),
+            options
+        )
+    ));
+    output.push_str("}\n");
+
+    // Tells callers how to encode the body, e.g. as a form instead of JSON
+    output.push_str(&format!("\nimpl {} {{\n", request.name));
+    output.push_str(&format!(
+        "    pub const CONTENT_TYPE: &'static str = {:?};\n",
+        request.content_type
+    ));
+    output.push_str("}\n");
+    Ok(output)
 }
 
-pub fn generate_lib() -> Result<String> {
-    let mut code = create_header();
-    code.push_str("pub mod models;\n");
+fn generate_response_model(
+    response: &ResponseModel,
+    split_models: &HashSet<String>,
+    options: &GenOptions,
+) -> Result<String> {
+    if response.name.is_empty() || response.name == EMPTY_RESPONSE_NAME {
+        return Ok(String::new());
+    }
 
-    Ok(code)
+    let type_name = format!("{}{}", response.name, response.status_code);
+    if is_stream_media_type(&response.content_type) {
+        return Ok(generate_stream_response(
+            response,
+            &type_name,
+            split_models,
+            options,
+        ));
+    }
+
+    let mut output = String::new();
+
+    output.push_str(&generate_description_docs(
+        &response.description,
+        &type_name,
+        "",
+    ));
+
+    if response.deprecated {
+        output.push_str(&format!("{DEPRECATED_ATTR}\n"));
+    }
+    output.push_str("#[derive(Debug, Clone, Deserialize)]\n");
+    output.push_str(&format!("pub struct {type_name} {{\n"));
+    output.push_str(&format!(
+        "    pub body: {},\n",
+        body_type(
+            StructVariant::Read.retarget(&response.schema, split_models),
+            options
+        )
+    ));
+    output.push_str("}\n");
+
+    Ok(output)
+}
+
+/// Media types whose body is a sequence of JSON values, one per line or event, rather
+/// than a single document
+fn is_stream_media_type(content_type: &str) -> bool {
+    let media_type = content_type.split(';').next().unwrap_or_default().trim();
+    matches!(
+        media_type,
+        "application/x-ndjson"
+            | "application/ndjson"
+            | "application/jsonl"
+            | "application/x-jsonlines"
+            | "application/json-seq"
+            | "text/event-stream"
+    )
+}
+
+/// Generates a streamed response as `{Name}Item`, the type of a single line or event,
+/// and `{Name}Stream`, the items in order. The schema describes one item; a schema
+/// that is an array describes the whole stream, so its items are used.
+fn generate_stream_response(
+    response: &ResponseModel,
+    type_name: &str,
+    split_models: &HashSet<String>,
+    options: &GenOptions,
+) -> String {
+    let item_type = stream_item_type(StructVariant::Read.retarget(&response.schema, split_models));
+
+    let mut output = String::new();
+    output.push_str(&generate_description_docs(
+        &response.description,
+        type_name,
+        "",
+    ));
+    output.push_str(&format!(
+        "///\n/// One item of the `{}` stream\n",
+        response.content_type
+    ));
+    if response.deprecated {
+        output.push_str(&format!("{DEPRECATED_ATTR}\n"));
+    }
+    output.push_str(&format!(
+        "pub type {type_name}Item = {};\n",
+        body_type(item_type, options)
+    ));
+    output.push_str(&format!(
+        "/// The items of the `{}` stream, in order\n",
+        response.content_type
+    ));
+    if response.deprecated {
+        output.push_str(&format!("{DEPRECATED_ATTR}\n"));
+    }
+    output.push_str(&format!(
+        "pub type {type_name}Stream = Vec<{type_name}Item>;\n"
+    ));
+    output
+}
+
+/// Type of a single item of a streamed body; an array schema describes the whole stream
+fn stream_item_type(schema: String) -> String {
+    schema
+        .strip_prefix("Vec<")
+        .and_then(|inner| inner.strip_suffix('>'))
+        .map(str::to_string)
+        .unwrap_or(schema)
+}
+
+/// Generates the enum of a response status with several content types, with one variant
+/// per content type named after its subtype (`Json`, `Csv`), and a `CONTENT_TYPES`
+/// constant listing the content types in variant order
+fn generate_response_enum(
+    responses: &[&ResponseModel],
+    split_models: &HashSet<String>,
+    options: &GenOptions,
+) -> String {
+    let first = responses[0];
+    if first.name.is_empty() || first.name == EMPTY_RESPONSE_NAME {
+        return String::new();
+    }
+    let type_name = format!("{}{}", first.name, first.status_code);
+
+    let mut output = String::new();
+    output.push_str(&generate_description_docs(
+        &first.description,
+        &type_name,
+        "",
+    ));
+    if first.deprecated {
+        output.push_str(&format!("{DEPRECATED_ATTR}\n"));
+    }
+    output.push_str("#[derive(Debug, Clone)]\n");
+    output.push_str(&format!("pub enum {type_name} {{\n"));
+    let mut used_names = HashSet::new();
+    for response in responses {
+        let mut variant = content_type_variant(&response.content_type, false);
+        if !used_names.insert(variant.clone()) {
+            variant = content_type_variant(&response.content_type, true);
+            used_names.insert(variant.clone());
+        }
+        let schema = StructVariant::Read.retarget(&response.schema, split_models);
+        let body = if is_stream_media_type(&response.content_type) {
+            format!("Vec<{}>", stream_item_type(schema))
+        } else {
+            body_type(schema, options)
+        };
+        output.push_str(&format!("    /// `{}`\n", response.content_type));
+        output.push_str(&format!("    {variant}({body}),\n"));
+    }
+    output.push_str("}\n\n");
+
+    let content_types: Vec<String> = responses
+        .iter()
+        .map(|response| format!("{:?}", response.content_type))
+        .collect();
+    output.push_str(&format!("impl {type_name} {{\n"));
+    output.push_str(&format!(
+        "    pub const CONTENT_TYPES: &'static [&'static str] = &[{}];\n",
+        content_types.join(", ")
+    ));
+    output.push_str("}\n");
+    output
+}
+
+/// Enum variant for a content type: its subtype, or its structured syntax suffix
+/// (`application/problem+json` is `Json`); `full` names it after the whole media type
+fn content_type_variant(content_type: &str, full: bool) -> String {
+    let media_type = content_type.split(';').next().unwrap_or_default().trim();
+    let name = if full {
+        media_type
+    } else {
+        let subtype = media_type.rsplit('/').next().unwrap_or(media_type);
+        subtype.rsplit('+').next().unwrap_or(subtype)
+    };
+    let words: String = name
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect();
+    let variant = crate::parser::to_pascal_case(&words);
+    if variant.starts_with(|c: char| c.is_ascii_alphabetic()) {
+        variant
+    } else {
+        format!("Content{variant}")
+    }
+}
+
+/// Type of a request/response `body`; binary bodies become `bytes::Bytes` under `--bytes`
+fn body_type(schema: String, options: &GenOptions) -> String {
+    if options.bytes && schema == "Vec<u8>" {
+        "bytes::Bytes".to_string()
+    } else {
+        schema
+    }
+}
+
+fn generate_union(union: &UnionModel, options: &GenOptions) -> Result<String> {
+    let mut output = String::new();
+
+    let fallback = format!(
+        "{} ({})",
+        union.name,
+        match union.union_type {
+            UnionType::OneOf => "oneOf",
+            UnionType::AnyOf => "anyOf",
+        }
+    );
+    output.push_str(&generate_description_docs(
+        &union.description,
+        &fallback,
+        "",
+    ));
+    output.push_str(&generate_custom_attrs(&union.custom_attrs));
+
+    // Only add default derive if custom_attrs doesn't already contain a derive
+    // `#[default]` only goes on unit variants, so a union cannot derive `Default`
+    if !has_custom_derive(&union.custom_attrs) {
+        let derives = union
+            .derives
+            .as_ref()
+            .unwrap_or(&options.derives)
+            .iter()
+            .filter(|derive| *derive != "Default")
+            .cloned()
+            .collect();
+        output.push_str(&derive_attr(
+            &["Debug", "Clone", "Serialize", "Deserialize"],
+            &Some(derives),
+            options,
+        ));
+    }
+
+    // Only add default serde(untagged) if custom_attrs doesn't already contain a serde attribute
+    if !has_custom_serde(&union.custom_attrs) {
+        output.push_str("#[serde(untagged)]\n");
+    }
+
+    output.push_str(&format!("pub enum {} {{\n", union.name));
+
+    for variant in &union.variants {
+        match &variant.primitive_type {
+            Some(t) => output.push_str(&format!("    {}({}),\n", variant.name, t)),
+            None => output.push_str(&format!("    {}({}),\n", variant.name, variant.name)),
+        }
+    }
+
+    output.push_str("}\n");
+    Ok(output)
+}
+
+/// The fields generated for an allOf composition: merged from all members, or with
+/// referenced members embedded under `--flatten-all-of`
+fn composition_fields<'a>(comp: &'a CompositionModel, options: &GenOptions) -> &'a [Field] {
+    if options.flatten_all_of {
+        &comp.embedded_fields
+    } else {
+        &comp.all_fields
+    }
+}
+
+fn generate_composition(
+    comp: &CompositionModel,
+    variant: StructVariant,
+    split_models: &HashSet<String>,
+    options: &GenOptions,
+    required_uses: &mut RequiredUses,
+) -> Result<String> {
+    let mut output = String::new();
+    let struct_name = variant.struct_name(&comp.name);
+
+    output.push_str(&generate_description_docs(
+        &comp.description,
+        &format!("{struct_name} (allOf composition)"),
+        "",
+    ));
+    output.push_str(&generate_custom_attrs(&comp.custom_attrs));
+
+    let fields: Vec<Field> = composition_fields(comp, options)
+        .iter()
+        .filter(|f| variant.includes(f))
+        .map(|field| Field {
+            field_type: variant.retarget(&field.field_type, split_models),
+            ..field.clone()
+        })
+        .collect();
+    let fields: Vec<(&Field, bool)> = fields.iter().map(|f| (f, variant.is_required(f))).collect();
+
+    // Only add default derive if custom_attrs doesn't already contain a derive
+    let builder = !has_custom_derive(&comp.custom_attrs) && wants_builder(fields.len(), options);
+    if !has_custom_derive(&comp.custom_attrs) {
+        check_secret_derives(&struct_name, &fields, &comp.derives, options)?;
+        output.push_str(&struct_derive(
+            &comp.derives,
+            builder,
+            options,
+            required_uses,
+        ));
+    }
+    output.push_str(&xml_root_attr(&comp.xml_name, &struct_name, options));
+
+    let rename_all = struct_rename_all(&fields, &comp.custom_attrs, options);
+    if let Some(rule) = rename_all {
+        output.push_str(&format!("#[serde(rename_all = \"{rule}\")]\n"));
+    }
+    output.push_str(&format!("pub struct {struct_name} {{\n"));
+
+    for (field, is_required) in &fields {
+        output.push_str(&generate_description_docs(&field.description, "", "    "));
+        output.push_str(&generate_field(
+            &struct_name,
+            field,
+            *is_required,
+            rename_all,
+            builder,
+            options,
+            required_uses,
+        ));
+    }
+
+    output.push_str("}\n");
+    let defaults = generate_default_support(
+        &struct_name,
+        &fields,
+        &comp.custom_attrs,
+        &comp.derives,
+        options,
+        required_uses,
+    );
+    let defaults = defaults
+        + &generate_validation_support(&struct_name, &fields, options)
+        + &generate_constructor(&struct_name, &fields, options, required_uses);
+    if !defaults.is_empty() {
+        output.push('\n');
+        output.push_str(&defaults);
+    }
+    Ok(output)
+}
+
+/// Generates the `{Base}Fields` traits of the schemas in `models` that other schemas
+/// extend through allOf, and their impls for every model of the family. Each trait has
+/// a getter per field of the base (`Option<&T>` for optional fields) and requires the
+/// traits of the base's own bases. A model whose fields cannot back every getter, e.g.
+/// because a member replaced a field's type, gets no impls.
+fn generate_all_of_traits(
+    models: &[ModelType],
+    all_models: &[ModelType],
+    options: &GenOptions,
+    required_uses: &mut RequiredUses,
+) -> String {
+    let fields_of = |name: &str| -> Option<&[Field]> {
+        match all_models.iter().find(|model| model.name() == name)? {
+            ModelType::Struct(model) => Some(&model.fields),
+            ModelType::Composition(comp) => Some(composition_fields(comp, options)),
+            _ => None,
+        }
+    };
+    let bases_of = |name: &str| -> Vec<&str> {
+        match all_models.iter().find(|model| model.name() == name) {
+            Some(ModelType::Composition(comp)) => comp
+                .bases
+                .iter()
+                .map(String::as_str)
+                .filter(|base| fields_of(base).is_some())
+                .collect(),
+            _ => Vec::new(),
+        }
+    };
+    // The model itself followed by its bases, transitively, each once
+    let family = |name: &str| -> Vec<String> {
+        let mut family: Vec<String> = Vec::new();
+        let mut stack = vec![name.to_string()];
+        while let Some(name) = stack.pop() {
+            if !family.contains(&name) {
+                stack.extend(bases_of(&name).into_iter().rev().map(str::to_string));
+                family.push(name);
+            }
+        }
+        family
+    };
+    let extended: HashSet<&str> = all_models
+        .iter()
+        .flat_map(|model| bases_of(model.name()))
+        .collect();
+    if extended.is_empty() {
+        return String::new();
+    }
+
+    // Getters of a trait, leaving out the fields its supertraits already provide
+    let own_fields = |name: &str| -> Vec<&Field> {
+        let inherited: HashSet<&str> = family(name)
+            .iter()
+            .skip(1)
+            .filter_map(|base| fields_of(base))
+            .flatten()
+            .map(|f| f.name.as_str())
+            .collect();
+        fields_of(name)
+            .into_iter()
+            .flatten()
+            .filter(|f| !f.should_flatten() && !inherited.contains(f.name.as_str()))
+            .collect()
+    };
+    let getter_name = |field: &Field| {
+        let name = to_snake_case(&field.name);
+        if is_reserved_word(&name) {
+            format!("r#{name}")
+        } else {
+            name
+        }
+    };
+    let mut output = String::new();
+    for model in models {
+        let name = model.name();
+        let Some(fields) = fields_of(name) else {
+            continue;
+        };
+
+        if extended.contains(name) {
+            let supertraits: Vec<String> = bases_of(name)
+                .into_iter()
+                .map(|base| format!("{base}Fields"))
+                .collect();
+            let supertraits = if supertraits.is_empty() {
+                String::new()
+            } else {
+                format!(": {}", supertraits.join(" + "))
+            };
+            output.push_str(&format!(
+                "/// Fields shared by `{name}` and the schemas extending it through allOf\n"
+            ));
+            output.push_str(&format!("pub trait {name}Fields{supertraits} {{\n"));
+            for field in own_fields(name) {
+                let shape = FieldShape::of(field, field.is_required, options);
+                let rust_type = field_rust_type(field, shape, options, required_uses);
+                let return_type = match rust_type.strip_prefix("Option<") {
+                    Some(inner) => format!("Option<&{inner}"),
+                    None => format!("&{rust_type}"),
+                };
+                output.push_str(&format!(
+                    "    fn {}(&self) -> {return_type};\n",
+                    getter_name(field)
+                ));
+            }
+            output.push_str("}\n\n");
+        }
+
+        let mut impls = String::new();
+        let traits = family(name)
+            .into_iter()
+            .filter(|member| extended.contains(member.as_str()));
+        'traits: for base in traits {
+            impls.push_str(&format!("impl {base}Fields for {name} {{\n"));
+            for base_field in own_fields(&base) {
+                let getter = getter_name(base_field);
+                let base_shape = FieldShape::of(base_field, base_field.is_required, options);
+                let base_type = field_rust_type(base_field, base_shape, options, required_uses);
+                let own = fields
+                    .iter()
+                    .find(|f| f.name == base_field.name && !f.should_flatten());
+                // Fields of embedded (`--flatten-all-of`) members are read through their type
+                let embedded = fields
+                    .iter()
+                    .find(|f| f.should_flatten() && family(&f.field_type).contains(&base));
+                let body = if let Some(own) = own {
+                    let shape = FieldShape::of(own, own.is_required, options);
+                    let own_type = field_rust_type(own, shape, options, required_uses);
+                    match base_type.strip_prefix("Option<") {
+                        _ if own_type == base_type && base_type.starts_with("Option<") => {
+                            format!("self.{getter}.as_ref()")
+                        }
+                        _ if own_type == base_type => format!("&self.{getter}"),
+                        Some(inner) if format!("{own_type}>") == inner => {
+                            format!("Some(&self.{getter})")
+                        }
+                        _ => {
+                            impls.clear();
+                            break 'traits;
+                        }
+                    }
+                } else if let Some(embedded) = embedded {
+                    format!("{base}Fields::{getter}(&self.{})", getter_name(embedded))
+                } else {
+                    impls.clear();
+                    break 'traits;
+                };
+                let return_type = match base_type.strip_prefix("Option<") {
+                    Some(inner) => format!("Option<&{inner}"),
+                    None => format!("&{base_type}"),
+                };
+                impls.push_str(&format!(
+                    "    fn {getter}(&self) -> {return_type} {{\n        {body}\n    }}\n"
+                ));
+            }
+            impls.push_str("}\n\n");
+        }
+        output.push_str(&impls);
+    }
+    output
+}
+
+fn generate_enum(
+    enum_model: &EnumModel,
+    options: &GenOptions,
+    required_uses: &mut RequiredUses,
+) -> Result<String> {
+    if !enum_model.integer_values.is_empty() {
+        *required_uses |= RequiredUses::PARSE_ERROR;
+        return Ok(generate_integer_enum(enum_model, options));
+    }
+    if !enum_model.mixed_values.is_empty() {
+        if options.enum_helpers {
+            *required_uses |= RequiredUses::PARSE_ERROR;
+        }
+        return Ok(generate_mixed_enum(enum_model, options));
+    }
+
+    let open = options.open_enums || enum_model.extensible;
+    if options.single_enum_as_const && enum_model.variants.len() == 1 && !open {
+        return generate_const_enum(enum_model, &enum_model.variants[0], options);
+    }
+
+    let mut output = String::new();
+
+    output.push_str(&generate_description_docs(
+        &enum_model.description,
+        &enum_model.name,
+        "",
+    ));
+
+    output.push_str(&generate_custom_attrs(&enum_model.custom_attrs));
+
+    // Only add default derive if custom_attrs doesn't already contain a derive
+    if !has_custom_derive(&enum_model.custom_attrs) {
+        output.push_str(&derive_attr(
+            &["Debug", "Clone", "Serialize", "Deserialize"],
+            &enum_model.derives,
+            options,
+        ));
+    }
+
+    // Replace per-variant renames with a single container attribute when possible
+    let rename_all = if has_custom_serde(&enum_model.custom_attrs) {
+        None
+    } else {
+        detect_variant_rename_all(&enum_model.variants)
+    };
+    if let Some(rule) = rename_all {
+        output.push_str(&format!("#[serde(rename_all = \"{rule}\")]\n"));
+    }
+
+    output.push_str(non_exhaustive_attr(options));
+    output.push_str(&format!("pub enum {} {{\n", enum_model.name));
+
+    let rust_names = enum_variant_idents(&enum_model.variants);
+    let default_attr = default_variant_attr(enum_model, options);
+    for (i, (variant, rust_name)) in enum_model.variants.iter().zip(&rust_names).enumerate() {
+        // Only add serde rename if the Rust variant name differs from the wire value
+        if rename_all.is_none() && rust_name != variant {
+            output.push_str(&format!("    #[serde(rename = {variant:?})]\n"));
+        }
+        if i == 0 {
+            output.push_str(default_attr);
+        }
+
+        if i + 1 == enum_model.variants.len() && !open {
+            output.push_str(&format!("    {rust_name}\n"));
+        } else {
+            output.push_str(&format!("    {rust_name},\n"));
+        }
+    }
+    let other = open.then(|| other_variant_name(&rust_names));
+    if let Some(other) = other {
+        output.push_str("    /// A value the specification does not list\n");
+        output.push_str("    #[serde(untagged)]\n");
+        output.push_str(&format!("    {other}(String)\n"));
+    }
+
+    output.push_str("}\n");
+
+    if options.enum_helpers {
+        *required_uses |= RequiredUses::PARSE_ERROR;
+        output.push_str(&generate_enum_helpers(enum_model, other));
+    }
+
+    Ok(output)
+}
+
+/// `#[default]` for the first variant of an enum deriving `Default`, which needs one
+fn default_variant_attr(enum_model: &EnumModel, options: &GenOptions) -> &'static str {
+    if derives_default(&enum_model.custom_attrs, &enum_model.derives, options) {
+        "    #[default]\n"
+    } else {
+        ""
+    }
+}
+
+/// Generates a `#[repr(i64)]` enum that (de)serializes as its integer value
+fn generate_integer_enum(enum_model: &EnumModel, options: &GenOptions) -> String {
+    let name = &enum_model.name;
+    let mut output = String::new();
+
+    output.push_str(&generate_description_docs(
+        &enum_model.description,
+        name,
+        "",
+    ));
+
+    output.push_str(&generate_custom_attrs(&enum_model.custom_attrs));
+
+    if !has_custom_derive(&enum_model.custom_attrs) {
+        output.push_str(&derive_attr(
+            &[
+                "Debug",
+                "Clone",
+                "Copy",
+                "PartialEq",
+                "Eq",
+                "Hash",
+                "Serialize",
+                "Deserialize",
+            ],
+            &enum_model.derives,
+            options,
+        ));
+    }
+    output.push_str("#[serde(into = \"i64\", try_from = \"i64\")]\n");
+    output.push_str("#[repr(i64)]\n");
+    output.push_str(non_exhaustive_attr(options));
+
+    output.push_str(&format!("pub enum {name} {{\n"));
+    output.push_str(default_variant_attr(enum_model, options));
+    for (variant, value) in enum_model.variants.iter().zip(&enum_model.integer_values) {
+        output.push_str(&format!("    {variant} = {value},\n"));
+    }
+    output.push_str("}\n");
+
+    output.push_str(&format!("\nimpl From<{name}> for i64 {{\n"));
+    output.push_str(&format!("    fn from(value: {name}) -> Self {{\n"));
+    output.push_str("        value as i64\n");
+    output.push_str("    }\n");
+    output.push_str("}\n");
+
+    output.push_str(&format!("\nimpl TryFrom<i64> for {name} {{\n"));
+    output.push_str("    type Error = ParseError;\n\n");
+    output.push_str("    fn try_from(value: i64) -> Result<Self, Self::Error> {\n");
+    output.push_str("        match value {\n");
+    for (variant, value) in enum_model.variants.iter().zip(&enum_model.integer_values) {
+        output.push_str(&format!("            {value} => Ok(Self::{variant}),\n"));
+    }
+    let expected = enum_model
+        .integer_values
+        .iter()
+        .map(|value| format!("\"{value}\""))
+        .collect::<Vec<_>>()
+        .join(", ");
+    output.push_str("            _ => Err(ParseError::InvalidValue {\n");
+    output.push_str(&format!("                type_name: \"{name}\",\n"));
+    output.push_str("                value: value.to_string(),\n");
+    output.push_str(&format!("                expected: &[{expected}],\n"));
+    output.push_str("            }),\n");
+    output.push_str("        }\n");
+    output.push_str("    }\n");
+    output.push_str("}\n");
+
+    if options.enum_helpers {
+        let values: Vec<String> = enum_model
+            .integer_values
+            .iter()
+            .map(|value| value.to_string())
+            .collect();
+        output.push_str(&generate_display_impl(name, &enum_model.variants, &values));
+
+        output.push_str(&format!("\nimpl std::str::FromStr for {name} {{\n"));
+        output.push_str("    type Err = ParseError;\n\n");
+        output.push_str("    fn from_str(s: &str) -> Result<Self, Self::Err> {\n");
+        output.push_str("        s.parse::<i64>()\n");
+        output.push_str("            .map_err(|_| ParseError::InvalidValue {\n");
+        output.push_str(&format!("                type_name: \"{name}\",\n"));
+        output.push_str("                value: s.to_string(),\n");
+        output.push_str(&format!("                expected: &[{expected}],\n"));
+        output.push_str("            })?\n");
+        output.push_str("            .try_into()\n");
+        output.push_str("    }\n");
+        output.push_str("}\n");
+    }
+
+    output
+}
+
+/// Generates an enum over literals of different JSON types with hand-written serde impls,
+/// since derived impls can only match variants against strings
+fn generate_mixed_enum(enum_model: &EnumModel, options: &GenOptions) -> String {
+    let name = &enum_model.name;
+    let mut output = String::new();
+
+    output.push_str(&generate_description_docs(
+        &enum_model.description,
+        name,
+        "",
+    ));
+
+    output.push_str(&generate_custom_attrs(&enum_model.custom_attrs));
+
+    if !has_custom_derive(&enum_model.custom_attrs) {
+        output.push_str(&derive_attr(
+            &["Debug", "Clone", "Copy", "PartialEq", "Eq", "Hash"],
+            &enum_model.derives,
+            options,
+        ));
+    }
+
+    let variants: Vec<(String, &serde_json::Value)> = enum_model
+        .variants
+        .iter()
+        .zip(&enum_model.mixed_values)
+        .map(|(variant, value)| {
+            let rust_name = if value.is_string() {
+                enum_variant_ident(variant)
+            } else {
+                variant.clone()
+            };
+            (rust_name, value)
+        })
+        .collect();
+
+    output.push_str(non_exhaustive_attr(options));
+    output.push_str(&format!("pub enum {name} {{\n"));
+    output.push_str(default_variant_attr(enum_model, options));
+    for (rust_name, _) in &variants {
+        output.push_str(&format!("    {rust_name},\n"));
+    }
+    output.push_str("}\n");
+
+    output.push_str(&format!("\nimpl Serialize for {name} {{\n"));
+    output.push_str(
+        "    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {\n",
+    );
+    output.push_str("        match self {\n");
+    for (rust_name, value) in &variants {
+        let call = match value {
+            serde_json::Value::String(s) => format!("serialize_str({s:?})"),
+            serde_json::Value::Bool(b) => format!("serialize_bool({b})"),
+            serde_json::Value::Number(n) if n.is_i64() => format!("serialize_i64({n})"),
+            serde_json::Value::Number(n) if n.is_u64() => format!("serialize_u64({n})"),
+            serde_json::Value::Number(n) => {
+                format!("serialize_f64({:?})", n.as_f64().unwrap_or_default())
+            }
+            _ => "serialize_unit()".to_string(),
+        };
+        output.push_str(&format!(
+            "            Self::{rust_name} => serializer.{call},\n"
+        ));
+    }
+    output.push_str("        }\n");
+    output.push_str("    }\n");
+    output.push_str("}\n");
+
+    output.push_str(&format!("\nimpl<'de> Deserialize<'de> for {name} {{\n"));
+    output.push_str(
+        "    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {\n",
+    );
+    output.push_str("        let value = serde_json::Value::deserialize(deserializer)?;\n");
+    output.push_str("        match &value {\n");
+    for (rust_name, value) in &variants {
+        let pattern = match value {
+            serde_json::Value::String(s) => format!("serde_json::Value::String(s) if s == {s:?}"),
+            serde_json::Value::Bool(b) => format!("serde_json::Value::Bool({b})"),
+            serde_json::Value::Number(n) if n.is_i64() => {
+                format!("serde_json::Value::Number(n) if n.as_i64() == Some({n})")
+            }
+            serde_json::Value::Number(n) if n.is_u64() => {
+                format!("serde_json::Value::Number(n) if n.as_u64() == Some({n})")
+            }
+            serde_json::Value::Number(n) => format!(
+                "serde_json::Value::Number(n) if n.as_f64() == Some({:?})",
+                n.as_f64().unwrap_or_default()
+            ),
+            _ => "serde_json::Value::Null".to_string(),
+        };
+        output.push_str(&format!(
+            "            {pattern} => Ok(Self::{rust_name}),\n"
+        ));
+    }
+    output.push_str(&format!(
+        "            _ => Err(serde::de::Error::custom(format!(\"invalid {name} value `{{value}}`\"))),\n"
+    ));
+    output.push_str("        }\n");
+    output.push_str("    }\n");
+    output.push_str("}\n");
+
+    if options.enum_helpers {
+        // Values are written as in JSON, without quotes around strings
+        let (rust_names, values): (Vec<String>, Vec<String>) = variants
+            .iter()
+            .map(|(rust_name, value)| match value {
+                serde_json::Value::String(s) => (rust_name.clone(), s.clone()),
+                value => (rust_name.clone(), value.to_string()),
+            })
+            .unzip();
+        output.push_str(&generate_display_impl(name, &rust_names, &values));
+        output.push_str(&generate_from_str_impl(name, &rust_names, &values));
+    }
+
+    output
+}
+
+/// Generates a unit struct that only (de)serializes from a single literal value
+fn generate_const_enum(
+    enum_model: &EnumModel,
+    value: &str,
+    options: &GenOptions,
+) -> Result<String> {
+    let name = &enum_model.name;
+    let mut output = String::new();
+
+    output.push_str(&generate_description_docs(
+        &enum_model.description,
+        name,
+        "",
+    ));
+
+    output.push_str(&generate_custom_attrs(&enum_model.custom_attrs));
+
+    if !has_custom_derive(&enum_model.custom_attrs) {
+        output.push_str(&derive_attr(
+            &["Debug", "Clone", "Copy", "PartialEq", "Eq", "Default"],
+            &enum_model.derives,
+            options,
+        ));
+    }
+
+    output.push_str(&format!("pub struct {name};\n\n"));
+
+    output.push_str(&format!("impl {name} {{\n"));
+    output.push_str(&format!("    pub const VALUE: &'static str = {value:?};\n"));
+    output.push_str("}\n\n");
+
+    output.push_str(&format!("impl Serialize for {name} {{\n"));
+    output.push_str(
+        "    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {\n",
+    );
+    output.push_str("        serializer.serialize_str(Self::VALUE)\n");
+    output.push_str("    }\n");
+    output.push_str("}\n\n");
+
+    output.push_str(&format!("impl<'de> Deserialize<'de> for {name} {{\n"));
+    output.push_str(
+        "    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {\n",
+    );
+    output.push_str("        let value = String::deserialize(deserializer)?;\n");
+    output.push_str("        if value == Self::VALUE {\n");
+    output.push_str("            Ok(Self)\n");
+    output.push_str("        } else {\n");
+    output.push_str("            Err(serde::de::Error::invalid_value(\n");
+    output.push_str("                serde::de::Unexpected::Str(&value),\n");
+    output.push_str("                &Self::VALUE,\n");
+    output.push_str("            ))\n");
+    output.push_str("        }\n");
+    output.push_str("    }\n");
+    output.push_str("}\n");
+
+    Ok(output)
+}
+
+/// serde `rename_all` rules, in order of preference
+const RENAME_ALL_RULES: &[&str] = &[
+    "lowercase",
+    "UPPERCASE",
+    "camelCase",
+    "snake_case",
+    "SCREAMING_SNAKE_CASE",
+    "kebab-case",
+    "SCREAMING-KEBAB-CASE",
+];
+
+/// Applies a serde `rename_all` rule to a PascalCase variant name, mirroring serde's behaviour
+fn apply_rename_all_rule(rule: &str, variant: &str) -> String {
+    let snake = || {
+        let mut snake = String::new();
+        for (i, c) in variant.char_indices() {
+            if c.is_uppercase() && i != 0 {
+                snake.push('_');
+            }
+            snake.push(c.to_ascii_lowercase());
+        }
+        snake
+    };
+
+    match rule {
+        "lowercase" => variant.to_ascii_lowercase(),
+        "UPPERCASE" => variant.to_ascii_uppercase(),
+        "camelCase" => {
+            let mut chars = variant.chars();
+            match chars.next() {
+                Some(first) => first.to_ascii_lowercase().to_string() + chars.as_str(),
+                None => String::new(),
+            }
+        }
+        "snake_case" => snake(),
+        "SCREAMING_SNAKE_CASE" => snake().to_ascii_uppercase(),
+        "kebab-case" => snake().replace('_', "-"),
+        "SCREAMING-KEBAB-CASE" => snake().replace('_', "-").to_ascii_uppercase(),
+        _ => variant.to_string(),
+    }
+}
+
+/// Finds a `rename_all` rule that maps every variant identifier to its wire value.
+/// Returns `None` when no renaming is needed or the values are not uniform.
+fn detect_variant_rename_all(variants: &[String]) -> Option<&'static str> {
+    let pairs: Vec<(String, &String)> = enum_variant_idents(variants)
+        .into_iter()
+        .zip(variants)
+        .collect();
+
+    if pairs.iter().all(|(rust_name, wire)| rust_name == *wire) {
+        return None;
+    }
+    if pairs.iter().any(|(rust_name, _)| rust_name.is_empty()) {
+        return None;
+    }
+
+    RENAME_ALL_RULES.iter().copied().find(|rule| {
+        pairs
+            .iter()
+            .all(|(rust_name, wire)| apply_rename_all_rule(rule, rust_name) == **wire)
+    })
+}
+
+/// serde `rename_all` rules that change a snake_case field name, in order of preference
+const FIELD_RENAME_ALL_RULES: &[&str] = &[
+    "camelCase",
+    "PascalCase",
+    "kebab-case",
+    "SCREAMING_SNAKE_CASE",
+    "SCREAMING-KEBAB-CASE",
+];
+
+/// Applies a serde `rename_all` rule to a snake_case field name, mirroring serde's behaviour
+fn apply_field_rename_all_rule(rule: &str, field: &str) -> String {
+    let pascal = || {
+        let mut pascal = String::new();
+        let mut capitalize = true;
+        for c in field.chars() {
+            if c == '_' {
+                capitalize = true;
+            } else if capitalize {
+                pascal.push(c.to_ascii_uppercase());
+                capitalize = false;
+            } else {
+                pascal.push(c);
+            }
+        }
+        pascal
+    };
+
+    match rule {
+        "PascalCase" => pascal(),
+        "camelCase" => {
+            let pascal = pascal();
+            let mut chars = pascal.chars();
+            match chars.next() {
+                Some(first) => first.to_ascii_lowercase().to_string() + chars.as_str(),
+                None => String::new(),
+            }
+        }
+        "SCREAMING_SNAKE_CASE" => field.to_ascii_uppercase(),
+        "kebab-case" => field.replace('_', "-"),
+        "SCREAMING-KEBAB-CASE" => field.replace('_', "-").to_ascii_uppercase(),
+        _ => field.to_string(),
+    }
+}
+
+/// Key serde uses for a field: its Rust name (without `r#`) under the struct's
+/// `rename_all` rule, or the Rust name as written when there is no rule
+fn field_wire_name(field: &Field, rename_all: Option<&str>) -> String {
+    let name = to_snake_case(&field.name);
+    match rename_all {
+        Some(rule) => apply_field_rename_all_rule(rule, &name),
+        None if is_reserved_word(&name) => format!("r#{name}"),
+        None => name,
+    }
+}
+
+/// Picks the `rename_all` rule that saves the most per-field renames on a struct, counting
+/// the fields the rule would rename wrongly, which then need a `rename` of their own.
+/// Returns `None` when no rule removes at least two renames more than it adds, since the
+/// container attribute is a line of its own, or when `x-rust-attrs` already sets serde
+/// attributes.
+fn struct_rename_all(
+    fields: &[(&Field, bool)],
+    custom_attrs: &Option<Vec<String>>,
+    options: &GenOptions,
+) -> Option<&'static str> {
+    if has_custom_serde(custom_attrs) {
+        return None;
+    }
+    let keys: Vec<(String, String)> = fields
+        .iter()
+        .filter(|(field, _)| !field.should_flatten())
+        .map(|(field, _)| {
+            let wire = xml_field_name(field, options)
+                .map(|(name, _)| name)
+                .unwrap_or_else(|| field.wire_name().to_string());
+            (to_snake_case(&field.name), wire)
+        })
+        .collect();
+
+    FIELD_RENAME_ALL_RULES
+        .iter()
+        .copied()
+        .map(|rule| {
+            let saved = keys
+                .iter()
+                .map(|(name, wire)| {
+                    let renamed = apply_field_rename_all_rule(rule, name) == *wire;
+                    match (name == wire, renamed) {
+                        (false, true) => 1,
+                        (true, false) => -1,
+                        _ => 0,
+                    }
+                })
+                .sum::<i32>();
+            (rule, saved)
+        })
+        .filter(|(_, saved)| *saved > 1)
+        .max_by_key(|(rule, saved)| {
+            let preference = FIELD_RENAME_ALL_RULES.iter().position(|r| r == rule);
+            (*saved, std::cmp::Reverse(preference))
+        })
+        .map(|(rule, _)| rule)
+}
+
+/// Converts an enum value into a valid Rust variant identifier. Words split on anything
+/// but letters and digits are joined in PascalCase (`in-progress` → `InProgress`,
+/// `N/A` → `NA`), a leading digit gets a `Value` prefix (`2xx` → `Value2xx`) and a value
+/// without letters or digits becomes `Empty`.
+fn enum_variant_ident(variant: &str) -> String {
+    let mut rust_name: String = variant
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(|word| {
+            let mut chars = word.chars();
+            match chars.next() {
+                Some(first) => first.to_uppercase().collect::<String>() + chars.as_str(),
+                None => String::new(),
+            }
+        })
+        .collect();
+    if rust_name.is_empty() {
+        rust_name = "Empty".to_string();
+    } else if rust_name.starts_with(|c: char| c.is_ascii_digit()) {
+        rust_name.insert_str(0, "Value");
+    }
+    if is_reserved_word(&rust_name) {
+        rust_name.push_str("Value");
+    }
+    rust_name
+}
+
+/// Variant identifiers of an enum's values; values sanitized to the same identifier
+/// (`in-progress`, `in_progress`) are numbered from the second one on
+fn enum_variant_idents(variants: &[String]) -> Vec<String> {
+    let mut used = HashSet::new();
+    variants
+        .iter()
+        .map(|variant| {
+            let base = enum_variant_ident(variant);
+            let mut rust_name = base.clone();
+            let mut n = 2;
+            while !used.insert(rust_name.clone()) {
+                rust_name = format!("{base}{n}");
+                n += 1;
+            }
+            rust_name
+        })
+        .collect()
+}
+
+/// Generates `FromStr` and `TryFrom<&str>` impls matching the serialized enum values
+/// `Display` for an enum whose variants are written as the given text
+fn generate_display_impl(name: &str, rust_names: &[String], texts: &[String]) -> String {
+    let mut output = format!("\nimpl std::fmt::Display for {name} {{\n");
+    output.push_str("    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {\n");
+    output.push_str("        f.write_str(match self {\n");
+    for (rust_name, text) in rust_names.iter().zip(texts) {
+        output.push_str(&format!("            Self::{rust_name} => {text:?},\n"));
+    }
+    output.push_str("        })\n");
+    output.push_str("    }\n");
+    output.push_str("}\n");
+    output
+}
+
+/// `FromStr` for an enum whose variants are parsed from the given text; when two
+/// variants share a text, the first one wins
+fn generate_from_str_impl(name: &str, rust_names: &[String], texts: &[String]) -> String {
+    let mut output = format!("\nimpl std::str::FromStr for {name} {{\n");
+    output.push_str("    type Err = ParseError;\n\n");
+    output.push_str("    fn from_str(s: &str) -> Result<Self, Self::Err> {\n");
+    output.push_str("        match s {\n");
+    let mut expected = Vec::new();
+    for (rust_name, text) in rust_names.iter().zip(texts) {
+        let text = format!("{text:?}");
+        if !expected.contains(&text) {
+            output.push_str(&format!("            {text} => Ok(Self::{rust_name}),\n"));
+            expected.push(text);
+        }
+    }
+    output.push_str("            _ => Err(ParseError::InvalidValue {\n");
+    output.push_str(&format!("                type_name: \"{name}\",\n"));
+    output.push_str("                value: s.to_string(),\n");
+    output.push_str(&format!(
+        "                expected: &[{}],\n",
+        expected.join(", ")
+    ));
+    output.push_str("            }),\n");
+    output.push_str("        }\n");
+    output.push_str("    }\n");
+    output.push_str("}\n");
+    output
+}
+
+/// Name of the catch-all variant of an open enum: `Other`, unless a listed value already
+/// uses that identifier
+fn other_variant_name(rust_names: &[String]) -> &'static str {
+    ["Other", "Unknown", "OtherValue"]
+        .into_iter()
+        .find(|name| !rust_names.iter().any(|rust_name| rust_name == name))
+        .unwrap_or("UnlistedValue")
+}
+
+/// `FromStr` and `TryFrom<&str>` for a string enum. `other` names the catch-all
+/// variant of an open enum, which takes any unlisted value instead of an error.
+fn generate_enum_helpers(enum_model: &EnumModel, other: Option<&str>) -> String {
+    let name = &enum_model.name;
+    let mut output = String::new();
+    let rust_names = enum_variant_idents(&enum_model.variants);
+
+    let lifetime = if other.is_some() { "" } else { "'static " };
+    output.push_str(&format!("\nimpl {name} {{\n"));
+    output.push_str("    /// The value as it appears in the API\n");
+    output.push_str(&format!("    pub fn as_str(&self) -> &{lifetime}str {{\n"));
+    output.push_str("        match self {\n");
+    for (variant, rust_name) in enum_model.variants.iter().zip(&rust_names) {
+        output.push_str(&format!("            Self::{rust_name} => {variant:?},\n"));
+    }
+    if let Some(other) = other {
+        output.push_str(&format!("            Self::{other}(value) => value,\n"));
+    }
+    output.push_str("        }\n");
+    output.push_str("    }\n");
+    output.push_str("}\n");
+
+    output.push_str(&format!("\nimpl std::fmt::Display for {name} {{\n"));
+    output.push_str("    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {\n");
+    output.push_str("        f.write_str(self.as_str())\n");
+    output.push_str("    }\n");
+    output.push_str("}\n");
+
+    output.push_str(&format!("\nimpl std::str::FromStr for {name} {{\n"));
+    output.push_str("    type Err = ParseError;\n\n");
+    output.push_str("    fn from_str(s: &str) -> Result<Self, Self::Err> {\n");
+    output.push_str("        match s {\n");
+    for (variant, rust_name) in enum_model.variants.iter().zip(&rust_names) {
+        output.push_str(&format!(
+            "            {variant:?} => Ok(Self::{rust_name}),\n"
+        ));
+    }
+    if let Some(other) = other {
+        output.push_str(&format!(
+            "            _ => Ok(Self::{other}(s.to_string())),\n"
+        ));
+    } else {
+        let expected = enum_model
+            .variants
+            .iter()
+            .map(|variant| format!("{variant:?}"))
+            .collect::<Vec<_>>()
+            .join(", ");
+        output.push_str("            _ => Err(ParseError::InvalidValue {\n");
+        output.push_str(&format!("                type_name: \"{name}\",\n"));
+        output.push_str("                value: s.to_string(),\n");
+        output.push_str(&format!("                expected: &[{expected}],\n"));
+        output.push_str("            }),\n");
+    }
+    output.push_str("        }\n");
+    output.push_str("    }\n");
+    output.push_str("}\n");
+
+    output.push_str(&format!("\nimpl TryFrom<&str> for {name} {{\n"));
+    output.push_str("    type Error = ParseError;\n\n");
+    output.push_str("    fn try_from(value: &str) -> Result<Self, Self::Error> {\n");
+    output.push_str("        value.parse()\n");
+    output.push_str("    }\n");
+    output.push_str("}\n");
+
+    output
+}
+
+/// Generates the error type shared by all generated parsing impls
+fn generate_parse_error() -> String {
+    r#"
+/// Error returned when a value does not match any value of a generated enum
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    InvalidValue {
+        type_name: &'static str,
+        value: String,
+        expected: &'static [&'static str],
+    },
+}
+
+impl std::fmt::Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseError::InvalidValue {
+                type_name,
+                value,
+                expected,
+            } => write!(
+                f,
+                "invalid {type_name} value `{value}`, expected one of: {}",
+                expected.join(", ")
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+"#
+    .to_string()
+}
+
+/// Generates a `String` newtype that only (de)serializes values passing `check`,
+/// an expression over `value: &String`
+fn generate_string_newtype(name: &str, doc: &str, check: &str, what: &str) -> String {
+    format!(
+        r#"
+/// {doc}
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct {name}(String);
+
+impl {name} {{
+    pub fn as_str(&self) -> &str {{
+        &self.0
+    }}
+}}
+
+impl TryFrom<String> for {name} {{
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {{
+        if {check} {{
+            Ok({name}(value))
+        }} else {{
+            Err(format!("invalid {what} `{{value}}`"))
+        }}
+    }}
+}}
+
+impl From<{name}> for String {{
+    fn from(value: {name}) -> Self {{
+        value.0
+    }}
+}}
+
+impl std::fmt::Display for {name} {{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {{
+        f.write_str(&self.0)
+    }}
+}}
+"#
+    )
+}
+
+/// Name of the generated newtype for `format: byte` strings
+const BASE64_BYTES: &str = "Base64Bytes";
+
+/// Whether a generated type expression mentions [`BASE64_BYTES`]
+fn uses_base64_bytes(type_expr: &str) -> bool {
+    type_expr
+        .split(|c: char| !(c.is_alphanumeric() || c == '_'))
+        .any(|ident| ident == BASE64_BYTES)
+}
+
+/// Serializers for `secrecy::SecretString` fields, which deliberately do not implement
+/// `Serialize`; the secret is only exposed when a model is sent
+const SECRET_SERIALIZERS: &str = r#"
+#[allow(dead_code)]
+fn serialize_secret<S: serde::Serializer>(
+    secret: &secrecy::SecretString,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    use secrecy::ExposeSecret;
+    serializer.serialize_str(secret.expose_secret())
+}
+
+#[allow(dead_code)]
+fn serialize_optional_secret<S: serde::Serializer>(
+    secret: &Option<secrecy::SecretString>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    use secrecy::ExposeSecret;
+    match secret {
+        Some(secret) => serializer.serialize_some(secret.expose_secret()),
+        None => serializer.serialize_none(),
+    }
+}
+"#;
+
+/// Deserializer for `Option<Option<T>>` fields: serde only calls it when the field is
+/// present, so `null` becomes `Some(None)`, while `#[serde(default)]` leaves an absent
+/// field `None`
+const DOUBLE_OPTION_DESERIALIZER: &str = r#"
+fn deserialize_double_option<'de, D, T>(deserializer: D) -> Result<Option<Option<T>>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+    T: serde::Deserialize<'de>,
+{
+    <Option<T> as serde::Deserialize>::deserialize(deserializer).map(Some)
+}
+"#;
+
+/// (De)serializers for `--big-integers` fields, written as strings since JSON parsers
+/// commonly read numbers as doubles; numbers are accepted when reading
+const BIG_INTEGER_STRING: &str = r#"
+#[allow(dead_code)]
+mod big_integer_string {
+    use std::fmt::Display;
+    use std::marker::PhantomData;
+    use std::str::FromStr;
+
+    pub fn serialize<T: Display, S: serde::Serializer>(
+        value: &T,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(value)
+    }
+
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+    where
+        T: FromStr,
+        T::Err: Display,
+        D: serde::Deserializer<'de>,
+    {
+        struct BigIntegerVisitor<T>(PhantomData<T>);
+
+        impl<T> serde::de::Visitor<'_> for BigIntegerVisitor<T>
+        where
+            T: FromStr,
+            T::Err: Display,
+        {
+            type Value = T;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+                formatter.write_str("an integer or a string of digits")
+            }
+
+            fn visit_str<E: serde::de::Error>(self, value: &str) -> Result<T, E> {
+                value.parse().map_err(E::custom)
+            }
+
+            fn visit_i64<E: serde::de::Error>(self, value: i64) -> Result<T, E> {
+                self.visit_str(&value.to_string())
+            }
+
+            fn visit_u64<E: serde::de::Error>(self, value: u64) -> Result<T, E> {
+                self.visit_str(&value.to_string())
+            }
+        }
+
+        deserializer.deserialize_any(BigIntegerVisitor(PhantomData))
+    }
+
+    pub mod option {
+        use std::fmt::Display;
+        use std::str::FromStr;
+
+        pub fn serialize<T: Display, S: serde::Serializer>(
+            value: &Option<T>,
+            serializer: S,
+        ) -> Result<S::Ok, S::Error> {
+            match value {
+                Some(value) => serializer.serialize_some(&value.to_string()),
+                None => serializer.serialize_none(),
+            }
+        }
+
+        pub fn deserialize<'de, T, D>(deserializer: D) -> Result<Option<T>, D::Error>
+        where
+            T: FromStr,
+            T::Err: Display,
+            D: serde::Deserializer<'de>,
+        {
+            #[derive(serde::Deserialize)]
+            #[serde(bound(deserialize = "T: FromStr, T::Err: Display"))]
+            struct Wrapper<T>(#[serde(deserialize_with = "super::deserialize")] T);
+
+            let value: Option<Wrapper<T>> = serde::Deserialize::deserialize(deserializer)?;
+            Ok(value.map(|Wrapper(value)| value))
+        }
+    }
+}
+"#;
+
+/// chrono and uuid imports needed by a type expression such as `Vec<DateTime<Utc>>`
+fn type_expr_uses(type_expr: &str) -> RequiredUses {
+    type_expr
+        .split(|c: char| !(c.is_alphanumeric() || c == '_'))
+        .fold(RequiredUses::empty(), |uses, ident| match ident {
+            "DateTime" => uses | RequiredUses::DATETIME,
+            "NaiveDate" => uses | RequiredUses::DATE,
+            "NaiveTime" => uses | RequiredUses::TIME,
+            "Uuid" => uses | RequiredUses::UUID,
+            _ => uses,
+        })
+}
+
+fn generate_base64_bytes() -> String {
+    r#"
+/// Raw bytes carried as a standard base64 string (`format: byte`)
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct Base64Bytes(pub Vec<u8>);
+
+impl From<Vec<u8>> for Base64Bytes {
+    fn from(bytes: Vec<u8>) -> Self {
+        Base64Bytes(bytes)
+    }
+}
+
+impl std::ops::Deref for Base64Bytes {
+    type Target = Vec<u8>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl Serialize for Base64Bytes {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use base64::Engine;
+        serializer.serialize_str(&base64::engine::general_purpose::STANDARD.encode(&self.0))
+    }
+}
+
+impl<'de> Deserialize<'de> for Base64Bytes {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        use base64::Engine;
+        let encoded = String::deserialize(deserializer)?;
+        base64::engine::general_purpose::STANDARD
+            .decode(encoded)
+            .map(Base64Bytes)
+            .map_err(serde::de::Error::custom)
+    }
+}
+"#
+    .to_string()
+}
+
+/// Name of the generated type for file parts of `multipart/form-data` bodies
+const FILE_PART: &str = "FilePart";
+
+fn generate_file_part(options: &GenOptions) -> String {
+    let bytes_type = if options.bytes {
+        "bytes::Bytes"
+    } else {
+        "Vec<u8>"
+    };
+    format!(
+        r#"
+/// File part of a `multipart/form-data` body (`format: binary`)
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct FilePart {{
+    pub bytes: {bytes_type},
+    /// Sent as the `filename` of the part's `Content-Disposition`
+    pub filename: Option<String>,
+    /// Sent as the part's `Content-Type`
+    pub content_type: Option<String>,
+}}
+
+impl FilePart {{
+    pub fn new(bytes: impl Into<{bytes_type}>) -> Self {{
+        FilePart {{
+            bytes: bytes.into(),
+            filename: None,
+            content_type: None,
+        }}
+    }}
+}}
+"#
+    )
+}
+
+fn generate_type_alias(type_alias: &TypeAliasModel) -> Result<String> {
+    let mut output = String::new();
+
+    output.push_str(&generate_description_docs(
+        &type_alias.description,
+        &type_alias.name,
+        "",
+    ));
+
+    output.push_str(&generate_custom_attrs(&type_alias.custom_attrs));
+    output.push_str(&format!(
+        "pub type {} = {};\n\n",
+        type_alias.name, type_alias.target_type
+    ));
+
+    Ok(output)
+}
+
+/// Generates an identifier newtype: a transparent tuple struct over the field type, with
+/// `From` the inner value and `Display` through it
+fn generate_newtype(
+    newtype: &NewtypeModel,
+    options: &GenOptions,
+    required_uses: &mut RequiredUses,
+) -> String {
+    let inner = &newtype.inner_type;
+    *required_uses |= type_expr_uses(inner);
+    let mut base = vec!["Debug", "Clone"];
+    if inner != "String" {
+        base.push("Copy");
+    }
+    base.extend([
+        "PartialEq",
+        "Eq",
+        "Hash",
+        "PartialOrd",
+        "Ord",
+        "Serialize",
+        "Deserialize",
+    ]);
+
+    let mut output = generate_description_docs(&newtype.description, &newtype.name, "");
+    output.push_str(&derive_attr(&base, &None, options));
+    output.push_str("#[serde(transparent)]\n");
+    output.push_str(&format!("pub struct {}(pub {inner});\n\n", newtype.name));
+    output.push_str(&format!(
+        "impl From<{inner}> for {name} {{\n    fn from(value: {inner}) -> Self {{\n        Self(value)\n    }}\n}}\n\n",
+        name = newtype.name
+    ));
+    output.push_str(&format!(
+        "impl std::fmt::Display for {} {{\n    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {{\n        self.0.fmt(f)\n    }}\n}}\n\n",
+        newtype.name
+    ));
+    output
+}
+
+pub fn generate_rust_code(models: &[Model]) -> Result<String> {
+    let mut code = create_header();
+
+    code.push_str("use serde::{Serialize, Deserialize};\n");
+    code.push_str("use uuid::Uuid;\n");
+    code.push_str("use chrono::{DateTime, NaiveDate, Utc};\n\n");
+
+    for model in models {
+        code.push_str(&format!("/// {}\n", model.name));
+        code.push_str("#[derive(Debug, Clone, Serialize, Deserialize)]\n");
+        code.push_str(&format!("pub struct {} {{\n", model.name));
+
+        for field in &model.fields {
+            let field_type = match field.field_type.as_str() {
+                "String" => "String",
+                "f64" => "f64",
+                "i64" => "i64",
+                "bool" => "bool",
+                "DateTime" => "DateTime<Utc>",
+                "Date" => "NaiveDate",
+                "Uuid" => "Uuid",
+                _ => &field.field_type,
+            };
+
+            let mut lowercased_name = to_snake_case(field.name.as_str());
+            if is_reserved_word(&lowercased_name) {
+                lowercased_name = format!("r#{lowercased_name}")
+            }
+
+            // Only add serde rename if the Rust field name differs from the original field name
+            if lowercased_name != field.name {
+                code.push_str(&format!("    #[serde(rename = \"{}\")]\n", field.name));
+            }
+
+            if field.is_required {
+                code.push_str(&format!("    pub {lowercased_name}: {field_type},\n",));
+            } else {
+                code.push_str(&format!(
+                    "    pub {lowercased_name}: Option<{field_type}>,\n",
+                ));
+            }
+        }
+
+        code.push_str("}\n\n");
+    }
+
+    Ok(code)
+}
+
+pub fn generate_lib() -> Result<String> {
+    generate_lib_with(&["models"])
+}
+
+/// Name of the module holding models shared between tags under `--group-by tag`
+pub const COMMON_MODULE: &str = "common";
+
+/// Splits the models into one module per OpenAPI tag. A model goes to the module of the
+/// only tag whose operations reach it; models reached from several tags, from untagged
+/// operations or from no operation at all go to [`COMMON_MODULE`], together with
+/// everything they reference. Returns `(module name, code)` pairs, `common` first.
+pub fn generate_tag_modules(
+    models: &[ModelType],
+    requests: &[RequestModel],
+    responses: &[ResponseModel],
+    operation_tags: &HashMap<String, BTreeSet<String>>,
+    options: &GenOptions,
+) -> Result<Vec<(String, String)>> {
+    let model_names: HashSet<&str> = models.iter().map(|model| model.name()).collect();
+    let references = |type_exprs: Vec<&str>| -> Vec<String> {
+        type_exprs
+            .into_iter()
+            .flat_map(|expr| expr.split(|c: char| !(c.is_alphanumeric() || c == '_')))
+            .filter(|name| model_names.contains(name))
+            .map(String::from)
+            .collect()
+    };
+    let edges: HashMap<&str, Vec<String>> = models
+        .iter()
+        .map(|model| (model.name(), references(model_type_exprs(model))))
+        .collect();
+
+    // Tags reaching each model, following references from every tagged root
+    let mut owners: HashMap<String, BTreeSet<String>> = HashMap::new();
+    let roots = models
+        .iter()
+        .map(|model| (model.name(), vec![model.name()]))
+        .chain(
+            requests
+                .iter()
+                .map(|r| (r.name.as_str(), vec![r.schema.as_str()])),
+        )
+        .chain(
+            responses
+                .iter()
+                .map(|r| (r.name.as_str(), vec![r.schema.as_str()])),
+        );
+    for (root, type_exprs) in roots {
+        let Some(tags) = operation_tags.get(root) else {
+            continue;
+        };
+        let mut stack = references(type_exprs);
+        let mut seen = HashSet::new();
+        while let Some(name) = stack.pop() {
+            if !seen.insert(name.clone()) {
+                continue;
+            }
+            stack.extend(edges.get(name.as_str()).cloned().unwrap_or_default());
+            owners.entry(name).or_default().extend(tags.iter().cloned());
+        }
+    }
+
+    let single_tag = |tags: Option<&BTreeSet<String>>| -> Option<String> {
+        tags.filter(|tags| tags.len() == 1)
+            .and_then(|tags| tags.first())
+            .map(|tag| tag_module_name(tag))
+    };
+
+    let mut module_of: HashMap<&str, String> = models
+        .iter()
+        .map(|model| {
+            let module =
+                single_tag(owners.get(model.name())).unwrap_or_else(|| COMMON_MODULE.to_string());
+            (model.name(), module)
+        })
+        .collect();
+
+    // Shared models may only reference other shared models
+    let mut stack: Vec<String> = module_of
+        .iter()
+        .filter(|(_, module)| *module == COMMON_MODULE)
+        .map(|(name, _)| name.to_string())
+        .collect();
+    while let Some(name) = stack.pop() {
+        for referenced in edges.get(name.as_str()).into_iter().flatten() {
+            if let Some(module) = module_of.get_mut(referenced.as_str()) {
+                if module != COMMON_MODULE {
+                    *module = COMMON_MODULE.to_string();
+                    stack.push(referenced.clone());
+                }
+            }
+        }
+    }
+
+    let module_of_operation = |name: &str| {
+        single_tag(operation_tags.get(name)).unwrap_or_else(|| COMMON_MODULE.to_string())
+    };
+
+    let mut module_names: BTreeSet<String> = module_of.values().cloned().collect();
+    module_names.extend(requests.iter().map(|r| module_of_operation(&r.name)));
+    module_names.extend(responses.iter().map(|r| module_of_operation(&r.name)));
+    let has_common = module_names.remove(COMMON_MODULE);
+
+    let mut modules = Vec::new();
+    for module in has_common
+        .then(|| COMMON_MODULE.to_string())
+        .into_iter()
+        .chain(module_names)
+    {
+        let module_models: Vec<ModelType> = models
+            .iter()
+            .filter(|model| module_of[model.name()] == module)
+            .cloned()
+            .collect();
+        let module_requests: Vec<RequestModel> = requests
+            .iter()
+            .filter(|r| module_of_operation(&r.name) == module)
+            .cloned()
+            .collect();
+        let module_responses: Vec<ResponseModel> = responses
+            .iter()
+            .filter(|r| module_of_operation(&r.name) == module)
+            .cloned()
+            .collect();
+        let extra_uses: &[&str] = if has_common && module != COMMON_MODULE {
+            &["super::common::*"]
+        } else {
+            &[]
+        };
+        let code = generate_module(
+            &module_models,
+            models,
+            &module_requests,
+            &module_responses,
+            options,
+            extra_uses,
+        )?;
+        modules.push((module, code));
+    }
+
+    Ok(modules)
+}
+
+/// Type expressions a model refers to other models through
+fn model_type_exprs(model: &ModelType) -> Vec<&str> {
+    match model {
+        ModelType::Struct(model) => model.fields.iter().map(|f| f.field_type.as_str()).collect(),
+        ModelType::Composition(comp) => comp
+            .all_fields
+            .iter()
+            .chain(&comp.embedded_fields)
+            .map(|f| f.field_type.as_str())
+            .chain(comp.bases.iter().map(String::as_str))
+            .collect(),
+        ModelType::Union(union) => union
+            .variants
+            .iter()
+            .flat_map(|variant| {
+                std::iter::once(variant.primitive_type.as_deref().unwrap_or(&variant.name))
+                    .chain(variant.fields.iter().map(|f| f.field_type.as_str()))
+            })
+            .collect(),
+        ModelType::Params(params) => params
+            .fields
+            .iter()
+            .map(|p| p.field.field_type.as_str())
+            .collect(),
+        ModelType::TypeAlias(alias) => vec![alias.target_type.as_str()],
+        ModelType::Newtype(newtype) => vec![newtype.inner_type.as_str()],
+        ModelType::Enum(_) => Vec::new(),
+    }
+}
+
+/// Modules the CLI writes next to the tag modules, plus `mod.rs` itself
+const RESERVED_MODULE_NAMES: &[&str] = &[COMMON_MODULE, "fixtures", "mod", "security", "servers"];
+
+/// Module name for a tag, e.g. `Pet Store` → `pet_store`. Tags named like another
+/// generated module get a `_tag` suffix (`Security` → `security_tag`), and tags without
+/// any alphanumeric character become `tag`.
+fn tag_module_name(tag: &str) -> String {
+    let name = to_snake_case(&tag.replace(' ', "_"))
+        .trim_matches('_')
+        .to_lowercase();
+    if name.is_empty() {
+        "tag".to_string()
+    } else if name.starts_with(|c: char| c.is_ascii_digit()) {
+        format!("tag_{name}")
+    } else if RESERVED_MODULE_NAMES.contains(&name.as_str()) {
+        format!("{name}_tag")
+    } else if is_reserved_word(&name) {
+        format!("{name}_")
+    } else {
+        name
+    }
+}
+
+/// Generates `mod.rs` declaring the given generated modules, each once
+pub fn generate_lib_with(modules: &[&str]) -> Result<String> {
+    let mut code = create_header();
+    let mut declared = HashSet::new();
+    for module in modules {
+        if declared.insert(module) {
+            code.push_str(&format!("pub mod {module};\n"));
+        }
+    }
+
+    Ok(code)
+}
+
+/// Generates the `fixtures` module: a `{NAME}_EXAMPLE_JSON` constant per example and a
+/// test deserializing each into its type, which is imported from the given modules
+pub fn generate_fixtures(examples: &[ExampleModel], modules: &[&str]) -> Result<String> {
+    let mut code = create_header();
+    for module in modules {
+        code.push_str(&format!(
+            "#[allow(unused_imports)]\nuse super::{module}::*;\n"
+        ));
+    }
+
+    let mut tests = String::new();
+    for example in examples {
+        let snake_name = to_snake_case(&example.name);
+        let const_name = format!("{}_EXAMPLE_JSON", snake_name.to_uppercase());
+        let json = serde_json::to_string_pretty(&example.value)?;
+        // Enough hashes that no `"#...` inside the JSON closes the raw string early
+        let mut hashes = "#".to_string();
+        while json.contains(&format!("\"{hashes}")) {
+            hashes.push('#');
+        }
+
+        if !code.ends_with("\n\n") {
+            code.push('\n');
+        }
+        code.push_str(&format!(
+            "/// Example of `{}`\npub const {const_name}: &str = r{hashes}\"{json}\"{hashes};\n",
+            example.type_name
+        ));
+        tests.push_str(&format!(
+            "\n    #[test]\n    fn {snake_name}_example_deserializes() {{\n        \
+             serde_json::from_str::<{}>({const_name}).expect(\"{const_name}\");\n    }}\n",
+            example.type_name
+        ));
+    }
+
+    if !tests.is_empty() {
+        code.push_str("\n#[cfg(test)]\nmod tests {\n    use super::*;\n");
+        code.push_str(&tests);
+        code.push_str("}\n");
+    }
+
+    Ok(code)
+}
+
+/// Types shared by every generated `security` module
+const SECURITY_SUPPORT: &str = r#"
+/// Where an API key is sent
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ApiKeyLocation {
+    Query,
+    Header,
+    Cookie,
+}
+
+/// API key passed as a named query parameter, header or cookie
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ApiKey {
+    pub name: &'static str,
+    pub location: ApiKeyLocation,
+}
+
+/// Token sent as `Authorization: Bearer <token>`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct HttpBearer {
+    pub bearer_format: Option<&'static str>,
+}
+
+/// HTTP authentication with a scheme other than bearer, such as `basic`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Http {
+    pub scheme: &'static str,
+}
+
+/// OAuth2 grant type
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum OAuth2FlowKind {
+    Implicit,
+    Password,
+    ClientCredentials,
+    AuthorizationCode,
+}
+
+/// A single OAuth2 flow with its endpoints and scopes (name, description)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct OAuth2Flow {
+    pub kind: OAuth2FlowKind,
+    pub authorization_url: Option<&'static str>,
+    pub token_url: Option<&'static str>,
+    pub refresh_url: Option<&'static str>,
+    pub scopes: &'static [(&'static str, &'static str)],
+}
+
+/// OAuth2 with the flows the API supports
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct OAuth2 {
+    pub flows: &'static [OAuth2Flow],
+}
+
+/// OpenID Connect, configured through its discovery document
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct OpenIdConnect {
+    pub url: &'static str,
+}
+
+/// Configuration of one security scheme
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SecuritySchemeConfig {
+    ApiKey(ApiKey),
+    HttpBearer(HttpBearer),
+    Http(Http),
+    OAuth2(OAuth2),
+    OpenIdConnect(OpenIdConnect),
+}
+"#;
+
+/// Generates the `security` module: a config constant per scheme in
+/// `components/securitySchemes` and a `SecurityScheme` enum over them
+pub fn generate_security(schemes: &[SecuritySchemeModel]) -> Result<String> {
+    let mut code = create_header();
+    code.push_str(SECURITY_SUPPORT.trim_start());
+
+    let mut variants = Vec::new();
+    for scheme in schemes {
+        let const_name = to_snake_case(&scheme.name).to_uppercase();
+        let variant = crate::parser::to_pascal_case(&to_snake_case(&scheme.name));
+        let (config_type, config_variant, value) = match &scheme.kind {
+            SecuritySchemeKind::ApiKey {
+                parameter_name,
+                location,
+            } => {
+                let location = match location {
+                    ParameterLocation::Query | ParameterLocation::Path => "Query",
+                    ParameterLocation::Header => "Header",
+                    ParameterLocation::Cookie => "Cookie",
+                };
+                (
+                    "ApiKey",
+                    "ApiKey",
+                    format!(
+                        "ApiKey {{\n    name: {parameter_name:?},\n    location: ApiKeyLocation::{location},\n}}"
+                    ),
+                )
+            }
+            SecuritySchemeKind::Http {
+                scheme,
+                bearer_format,
+            } if scheme == "bearer" => (
+                "HttpBearer",
+                "HttpBearer",
+                format!(
+                    "HttpBearer {{\n    bearer_format: {},\n}}",
+                    option_literal(bearer_format.as_deref())
+                ),
+            ),
+            SecuritySchemeKind::Http { scheme, .. } => (
+                "Http",
+                "Http",
+                format!("Http {{\n    scheme: {scheme:?},\n}}"),
+            ),
+            SecuritySchemeKind::OAuth2 { flows } => {
+                let mut value = String::from("OAuth2 {\n    flows: &[\n");
+                for flow in flows {
+                    let kind = match flow.kind {
+                        OAuth2FlowKind::Implicit => "Implicit",
+                        OAuth2FlowKind::Password => "Password",
+                        OAuth2FlowKind::ClientCredentials => "ClientCredentials",
+                        OAuth2FlowKind::AuthorizationCode => "AuthorizationCode",
+                    };
+                    value.push_str("        OAuth2Flow {\n");
+                    value.push_str(&format!("            kind: OAuth2FlowKind::{kind},\n"));
+                    value.push_str(&format!(
+                        "            authorization_url: {},\n",
+                        option_literal(flow.authorization_url.as_deref())
+                    ));
+                    value.push_str(&format!(
+                        "            token_url: {},\n",
+                        option_literal(flow.token_url.as_deref())
+                    ));
+                    value.push_str(&format!(
+                        "            refresh_url: {},\n",
+                        option_literal(flow.refresh_url.as_deref())
+                    ));
+                    value.push_str("            scopes: &[\n");
+                    for (scope, description) in &flow.scopes {
+                        value.push_str(&format!("                ({scope:?}, {description:?}),\n"));
+                    }
+                    value.push_str("            ],\n");
+                    value.push_str("        },\n");
+                }
+                value.push_str("    ],\n}");
+                ("OAuth2", "OAuth2", value)
+            }
+            SecuritySchemeKind::OpenIdConnect { url } => (
+                "OpenIdConnect",
+                "OpenIdConnect",
+                format!("OpenIdConnect {{\n    url: {url:?},\n}}"),
+            ),
+        };
+
+        code.push('\n');
+        code.push_str(&generate_description_docs(
+            &scheme.description,
+            &scheme.name,
+            "",
+        ));
+        code.push_str(&format!(
+            "pub const {const_name}: {config_type} = {value};\n"
+        ));
+        variants.push((variant, scheme.name.as_str(), const_name, config_variant));
+    }
+
+    code.push_str("\n/// Security schemes declared in `components/securitySchemes`\n");
+    code.push_str("#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]\n");
+    code.push_str("pub enum SecurityScheme {\n");
+    for (variant, ..) in &variants {
+        code.push_str(&format!("    {variant},\n"));
+    }
+    code.push_str("}\n");
+
+    code.push_str("\nimpl SecurityScheme {\n");
+    code.push_str("    pub const ALL: &'static [SecurityScheme] = &[\n");
+    for (variant, ..) in &variants {
+        code.push_str(&format!("        SecurityScheme::{variant},\n"));
+    }
+    code.push_str("    ];\n\n");
+    code.push_str("    /// Name of the scheme in the specification\n");
+    code.push_str("    pub fn name(&self) -> &'static str {\n");
+    code.push_str("        match self {\n");
+    for (variant, name, ..) in &variants {
+        code.push_str(&format!(
+            "            SecurityScheme::{variant} => {name:?},\n"
+        ));
+    }
+    code.push_str("        }\n");
+    code.push_str("    }\n\n");
+    code.push_str("    pub fn config(&self) -> SecuritySchemeConfig {\n");
+    code.push_str("        match self {\n");
+    for (variant, _, const_name, config_variant) in &variants {
+        code.push_str(&format!(
+            "            SecurityScheme::{variant} => SecuritySchemeConfig::{config_variant}({const_name}),\n"
+        ));
+    }
+    code.push_str("        }\n");
+    code.push_str("    }\n");
+    code.push_str("}\n");
+
+    Ok(code)
+}
+
+/// Generates the `servers` module: a URL constant per entry of the top-level `servers`
+/// array, a variables struct for templated URLs and a `Server` enum over them
+pub fn generate_servers(servers: &[ServerModel]) -> Result<String> {
+    let mut code = create_header();
+
+    for server in servers {
+        let const_name = to_snake_case(&server.name).to_uppercase();
+
+        if !code.ends_with("\n\n") {
+            code.push('\n');
+        }
+        code.push_str(&generate_description_docs(
+            &server.description,
+            &server.url,
+            "",
+        ));
+        code.push_str(&format!(
+            "pub const {const_name}: &str = {:?};\n",
+            server.url
+        ));
+
+        if server.variables.is_empty() {
+            continue;
+        }
+
+        let struct_name = format!("{}Variables", server.name);
+        code.push_str(&format!(
+            "\n/// Variables of the `{}` server URL\n",
+            server.url
+        ));
+        code.push_str("#[derive(Debug, Clone, PartialEq, Eq)]\n");
+        code.push_str(&format!("pub struct {struct_name} {{\n"));
+        for variable in &server.variables {
+            code.push_str(&generate_description_docs(
+                &variable.description,
+                "",
+                "    ",
+            ));
+            if !variable.enumeration.is_empty() {
+                if variable.description.is_some() {
+                    code.push_str("    ///\n");
+                }
+                let allowed = variable
+                    .enumeration
+                    .iter()
+                    .map(|value| format!("`{value}`"))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                code.push_str(&format!("    /// Allowed values: {allowed}\n"));
+            }
+            code.push_str(&format!(
+                "    pub {}: String,\n",
+                server_variable_ident(&variable.name)
+            ));
+        }
+        code.push_str("}\n");
+
+        code.push_str(&format!("\nimpl Default for {struct_name} {{\n"));
+        code.push_str("    fn default() -> Self {\n");
+        code.push_str("        Self {\n");
+        for variable in &server.variables {
+            code.push_str(&format!(
+                "            {}: {:?}.to_string(),\n",
+                server_variable_ident(&variable.name),
+                variable.default
+            ));
+        }
+        code.push_str("        }\n");
+        code.push_str("    }\n");
+        code.push_str("}\n");
+
+        code.push_str(&format!("\nimpl {struct_name} {{\n"));
+        code.push_str("    /// Server URL with the variables substituted\n");
+        code.push_str("    pub fn url(&self) -> String {\n");
+        code.push_str(&format!("        {const_name}\n"));
+        for variable in &server.variables {
+            code.push_str(&format!(
+                "            .replace({:?}, &self.{})\n",
+                format!("{{{}}}", variable.name),
+                server_variable_ident(&variable.name)
+            ));
+        }
+        code.push_str("    }\n");
+        code.push_str("}\n");
+    }
+
+    code.push_str("\n/// Servers from the top-level `servers` array\n");
+    code.push_str("#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]\n");
+    code.push_str("pub enum Server {\n");
+    for server in servers {
+        code.push_str(&format!("    {},\n", server.name));
+    }
+    code.push_str("}\n");
+
+    code.push_str("\nimpl Server {\n");
+    code.push_str("    pub const ALL: &'static [Server] = &[\n");
+    for server in servers {
+        code.push_str(&format!("        Server::{},\n", server.name));
+    }
+    code.push_str("    ];\n\n");
+    code.push_str(
+        "    /// URL as written in the specification, with any `{variable}` placeholders\n",
+    );
+    code.push_str("    pub fn url_template(&self) -> &'static str {\n");
+    code.push_str("        match self {\n");
+    for server in servers {
+        code.push_str(&format!(
+            "            Server::{} => {},\n",
+            server.name,
+            to_snake_case(&server.name).to_uppercase()
+        ));
+    }
+    code.push_str("        }\n");
+    code.push_str("    }\n\n");
+    code.push_str("    /// URL with every variable set to its default value\n");
+    code.push_str("    pub fn default_url(&self) -> String {\n");
+    code.push_str("        match self {\n");
+    for server in servers {
+        let value = if server.variables.is_empty() {
+            format!("{}.to_string()", to_snake_case(&server.name).to_uppercase())
+        } else {
+            format!("{}Variables::default().url()", server.name)
+        };
+        code.push_str(&format!(
+            "            Server::{} => {value},\n",
+            server.name
+        ));
+    }
+    code.push_str("        }\n");
+    code.push_str("    }\n");
+    code.push_str("}\n");
+
+    Ok(code)
+}
+
+/// Field name for a server variable
+fn server_variable_ident(name: &str) -> String {
+    let ident = to_snake_case(name);
+    if is_reserved_word(&ident) {
+        format!("r#{ident}")
+    } else {
+        ident
+    }
+}
+
+/// Renders an optional string as a `Some("...")`/`None` literal
+fn option_literal(value: Option<&str>) -> String {
+    match value {
+        Some(value) => format!("Some({value:?})"),
+        None => "None".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::parse_openapi;
+    use openapiv3::OpenAPI;
+    use serde_json::json;
+
+    fn generate_from_spec(spec: serde_json::Value, options: &GenOptions) -> String {
+        let openapi: OpenAPI =
+            serde_json::from_value(spec).expect("Failed to deserialize OpenAPI spec");
+        let (models, requests, responses) =
+            parse_openapi(&openapi).expect("Failed to parse OpenAPI spec");
+        generate_models_with(&models, &requests, &responses, options)
+            .expect("Failed to generate models")
+    }
+
+    fn empty_struct_spec() -> serde_json::Value {
+        json!({
+            "openapi": "3.0.0",
+            "info": { "title": "Test API", "version": "1.0.0" },
+            "paths": {},
+            "components": {
+                "schemas": {
+                    "Marker": { "type": "object" },
+                    "Holder": {
+                        "type": "object",
+                        "properties": {
+                            "marker": { "$ref": "#/components/schemas/Marker" }
+                        }
+                    }
+                }
+            }
+        })
+    }
+
+    #[test]
+    fn test_generate_models_uses_default_options() {
+        let openapi: OpenAPI = serde_json::from_value(empty_struct_spec())
+            .expect("Failed to deserialize OpenAPI spec");
+        let (models, requests, responses) =
+            parse_openapi(&openapi).expect("Failed to parse OpenAPI spec");
+
+        let default_code =
+            generate_models(&models, &requests, &responses).expect("Failed to generate models");
+        let with_defaults =
+            generate_models_with(&models, &requests, &responses, &GenOptions::default())
+                .expect("Failed to generate models");
+        assert_eq!(default_code, with_defaults);
+
+        let options = GenOptions {
+            prune_empty_structs: true,
+            skip_none: true,
+            ..Default::default()
+        };
+        let custom_code = generate_models_with(&models, &requests, &responses, &options)
+            .expect("Failed to generate models");
+        assert!(!custom_code.contains("pub struct Marker"));
+        assert!(custom_code
+            .contains("#[serde(skip_serializing_if = \"Option::is_none\")]\n    pub marker"));
+    }
+
+    #[test]
+    fn test_spec_fingerprint_tracks_spec_changes() {
+        let spec: OpenAPI = serde_json::from_value(empty_struct_spec()).unwrap();
+        let mut changed = spec.clone();
+        changed.info.title = "Changed API".to_string();
+        let fingerprint = spec_fingerprint(&spec).expect("Failed to fingerprint");
+
+        assert_eq!(fingerprint, spec_fingerprint(&spec).unwrap());
+        assert_ne!(fingerprint, spec_fingerprint(&changed).unwrap());
+        assert_eq!(fingerprint.len(), 64);
+
+        let options = GenOptions {
+            spec_fingerprint: Some(fingerprint.clone()),
+            ..Default::default()
+        };
+        let code = generate_from_spec(empty_struct_spec(), &options);
+        assert!(code.contains(&format!("//! spec-sha256: {fingerprint}\n")));
+    }
+
+    #[test]
+    fn test_empty_struct_emitted_by_default() {
+        let code = generate_from_spec(empty_struct_spec(), &GenOptions::default());
+
+        assert!(code.contains("pub struct Marker {\n}"));
+        assert!(code.contains("pub marker: Option<Marker>,"));
+    }
+
+    #[test]
+    fn test_x_serde_with_emits_field_attribute() {
+        let code = generate_from_spec(
+            json!({
+                "openapi": "3.0.0",
+                "info": { "title": "Test API", "version": "1.0.0" },
+                "paths": {},
+                "components": {
+                    "schemas": {
+                        "Account": {
+                            "type": "object",
+                            "properties": {
+                                "balance": {
+                                    "type": "string",
+                                    "x-serde-with": "my_crate::bignum"
+                                }
+                            },
+                            "required": ["balance"]
+                        }
+                    }
+                }
+            }),
+            &GenOptions::default(),
+        );
+
+        assert!(
+            code.contains("    #[serde(with = \"my_crate::bignum\")]\n    pub balance: String,")
+        );
+    }
+
+    #[test]
+    fn test_enum_helpers_emit_try_from() {
+        let options = GenOptions {
+            enum_helpers: true,
+            ..Default::default()
+        };
+        let code = generate_from_spec(
+            json!({
+                "openapi": "3.0.0",
+                "info": { "title": "Test API", "version": "1.0.0" },
+                "paths": {},
+                "components": {
+                    "schemas": {
+                        "Status": {
+                            "type": "string",
+                            "enum": ["active", "inactive"]
+                        }
+                    }
+                }
+            }),
+            &options,
+        );
+
+        assert!(code.contains("impl std::str::FromStr for Status {"));
+        assert!(code.contains("\"active\" => Ok(Self::Active),"));
+        assert!(code.contains("impl TryFrom<&str> for Status {"));
+        assert!(code.contains("fn try_from(value: &str) -> Result<Self, Self::Error> {"));
+    }
+
+    #[test]
+    fn test_enum_helpers_escape_values() {
+        let options = GenOptions {
+            enum_helpers: true,
+            ..Default::default()
+        };
+        let code = generate_from_spec(
+            json!({
+                "openapi": "3.0.0",
+                "info": { "title": "Test API", "version": "1.0.0" },
+                "paths": {},
+                "components": {
+                    "schemas": {
+                        "Quote": {
+                            "type": "string",
+                            "enum": ["say \"hi\"", "back\\slash"]
+                        }
+                    }
+                }
+            }),
+            &options,
+        );
+
+        assert!(code.contains("            Self::SayHi => \"say \\\"hi\\\"\",\n"));
+        assert!(code.contains("            Self::BackSlash => \"back\\\\slash\",\n"));
+        assert!(code.contains("            \"say \\\"hi\\\"\" => Ok(Self::SayHi),\n"));
+        assert!(code.contains("            \"back\\\\slash\" => Ok(Self::BackSlash),\n"));
+    }
+
+    #[test]
+    fn test_enum_helpers_parse_error_lists_variants() {
+        let options = GenOptions {
+            enum_helpers: true,
+            ..Default::default()
+        };
+        let code = generate_from_spec(
+            json!({
+                "openapi": "3.0.0",
+                "info": { "title": "Test API", "version": "1.0.0" },
+                "paths": {},
+                "components": {
+                    "schemas": {
+                        "Status": {
+                            "type": "string",
+                            "enum": ["active", "inactive"]
+                        }
+                    }
+                }
+            }),
+            &options,
+        );
+
+        assert!(code.contains("type Err = ParseError;"));
+        assert!(code.contains("type_name: \"Status\","));
+        assert!(code.contains("expected: &[\"active\", \"inactive\"],"));
+        assert!(code.contains("pub enum ParseError {"));
+        assert!(code.contains("expected one of: {}"));
+        assert_eq!(code.matches("pub enum ParseError").count(), 1);
+    }
+
+    #[test]
+    fn test_parse_error_expected_values_are_escaped() {
+        let options = GenOptions {
+            enum_helpers: true,
+            ..Default::default()
+        };
+        let code = generate_from_spec(
+            json!({
+                "openapi": "3.0.0",
+                "info": { "title": "Test API", "version": "1.0.0" },
+                "paths": {},
+                "components": {
+                    "schemas": {
+                        "Quote": {
+                            "type": "string",
+                            "enum": ["say \"hi\"", "back\\slash"]
+                        },
+                        "Mixed": { "enum": ["a\"b", 1, true] }
+                    }
+                }
+            }),
+            &options,
+        );
+
+        assert!(code.contains("expected: &[\"say \\\"hi\\\"\", \"back\\\\slash\"],"));
+        assert!(code.contains("expected: &[\"a\\\"b\", \"1\", \"true\"],"));
+    }
+
+    #[test]
+    fn test_uniform_enum_uses_container_rename_all() {
+        let code = generate_from_spec(
+            json!({
+                "openapi": "3.0.0",
+                "info": { "title": "Test API", "version": "1.0.0" },
+                "paths": {},
+                "components": {
+                    "schemas": {
+                        "Status": {
+                            "type": "string",
+                            "enum": ["active", "inactive", "pending"]
+                        },
+                        "Phase": {
+                            "type": "string",
+                            "enum": ["in_progress", "done"]
+                        },
+                        "Mixed": {
+                            "type": "string",
+                            "enum": ["in-progress", "on_hold"]
+                        }
+                    }
+                }
+            }),
+            &GenOptions::default(),
+        );
+
+        assert!(code.contains("#[serde(rename_all = \"lowercase\")]\npub enum Status {"));
+        assert!(code.contains("#[serde(rename_all = \"snake_case\")]\npub enum Phase {"));
+        assert!(!code.contains("#[serde(rename = \"active\")]"));
+        // Non-uniform values keep per-variant renames
+        assert!(code.contains("#[serde(rename = \"in-progress\")]"));
+        assert!(code.contains("#[serde(rename = \"on_hold\")]"));
+    }
+
+    #[test]
+    fn test_enum_helpers_disabled_by_default() {
+        let code = generate_from_spec(
+            json!({
+                "openapi": "3.0.0",
+                "info": { "title": "Test API", "version": "1.0.0" },
+                "paths": {},
+                "components": {
+                    "schemas": {
+                        "Status": { "type": "string", "enum": ["active"] }
+                    }
+                }
+            }),
+            &GenOptions::default(),
+        );
+
+        assert!(!code.contains("impl TryFrom<&str> for Status"));
+    }
+
+    #[test]
+    fn test_single_enum_as_const_only_accepts_literal() {
+        let options = GenOptions {
+            single_enum_as_const: true,
+            ..Default::default()
+        };
+        let code = generate_from_spec(
+            json!({
+                "openapi": "3.0.0",
+                "info": { "title": "Test API", "version": "1.0.0" },
+                "paths": {},
+                "components": {
+                    "schemas": {
+                        "ShapeKind": { "type": "string", "enum": ["circle"] },
+                        "Quoted": { "type": "string", "enum": ["say \"hi\""] },
+                        "Status": { "type": "string", "enum": ["active", "inactive"] }
+                    }
+                }
+            }),
+            &options,
+        );
+
+        assert!(code.contains("pub struct ShapeKind;"));
+        assert!(code.contains("pub const VALUE: &'static str = \"circle\";"));
+        assert!(code.contains("impl<'de> Deserialize<'de> for ShapeKind {"));
+        // Any value other than the literal is rejected
+        assert!(code.contains("if value == Self::VALUE {\n            Ok(Self)\n        } else {"));
+        assert!(code.contains("serde::de::Error::invalid_value("));
+        assert!(code.contains("pub const VALUE: &'static str = \"say \\\"hi\\\"\";"));
+        // Enums with several values are unaffected
+        assert!(code.contains("pub enum Status {"));
+
+        // The generated type, as it round-trips through serde_json
+        struct ShapeKind;
+
+        impl ShapeKind {
+            const VALUE: &'static str = "circle";
+        }
+
+        impl serde::Serialize for ShapeKind {
+            fn serialize<S: serde::Serializer>(
+                &self,
+                serializer: S,
+            ) -> std::result::Result<S::Ok, S::Error> {
+                serializer.serialize_str(Self::VALUE)
+            }
+        }
+
+        impl<'de> serde::Deserialize<'de> for ShapeKind {
+            fn deserialize<D: serde::Deserializer<'de>>(
+                deserializer: D,
+            ) -> std::result::Result<Self, D::Error> {
+                let value = String::deserialize(deserializer)?;
+                if value == Self::VALUE {
+                    Ok(Self)
+                } else {
+                    Err(serde::de::Error::invalid_value(
+                        serde::de::Unexpected::Str(&value),
+                        &Self::VALUE,
+                    ))
+                }
+            }
+        }
+
+        assert!(serde_json::from_str::<ShapeKind>("\"circle\"").is_ok());
+        let error = serde_json::from_str::<ShapeKind>("\"square\"")
+            .err()
+            .expect("only the literal deserializes");
+        assert!(error
+            .to_string()
+            .contains("invalid value: string \"square\", expected circle"));
+        assert_eq!(serde_json::to_string(&ShapeKind).unwrap(), "\"circle\"");
+    }
+
+    #[test]
+    fn test_skip_none_defaults_optional_arrays() {
+        let options = GenOptions {
+            skip_none: true,
+            ..Default::default()
+        };
+        let code = generate_from_spec(
+            json!({
+                "openapi": "3.0.0",
+                "info": { "title": "Test API", "version": "1.0.0" },
+                "paths": {},
+                "components": {
+                    "schemas": {
+                        "Post": {
+                            "type": "object",
+                            "properties": {
+                                "title": { "type": "string" },
+                                "tags": { "type": "array", "items": { "type": "string" } },
+                                "ids": { "type": "array", "items": { "type": "integer" } }
+                            },
+                            "required": ["ids"]
+                        }
+                    }
+                }
+            }),
+            &options,
+        );
+
+        assert!(code.contains(
+            "    #[serde(skip_serializing_if = \"Option::is_none\")]\n    pub title: Option<String>,"
+        ));
+        assert!(code.contains(
+            "    #[serde(default, skip_serializing_if = \"Vec::is_empty\")]\n    pub tags: Vec<"
+        ));
+        assert!(code.contains("    pub ids: Vec<"));
+        assert!(!code.contains("skip_serializing_if = \"Vec::is_empty\")]\n    pub ids"));
+    }
+
+    #[test]
+    fn test_split_rw_recomputes_required_per_variant() {
+        let options = GenOptions {
+            split_rw: true,
+            ..Default::default()
+        };
+        let code = generate_from_spec(
+            json!({
+                "openapi": "3.0.0",
+                "info": { "title": "Test API", "version": "1.0.0" },
+                "paths": {},
+                "components": {
+                    "schemas": {
+                        "User": {
+                            "type": "object",
+                            "properties": {
+                                "id": { "type": "string", "readOnly": true },
+                                "password": { "type": "string", "writeOnly": true },
+                                "name": { "type": "string" }
+                            },
+                            "required": ["id", "password"]
+                        }
+                    }
+                }
+            }),
+            &options,
+        );
+
+        let read = code
+            .split("pub struct UserRead {")
+            .nth(1)
+            .and_then(|rest| rest.split('}').next())
+            .expect("Expected UserRead struct");
+        let write = code
+            .split("pub struct UserWrite {")
+            .nth(1)
+            .and_then(|rest| rest.split('}').next())
+            .expect("Expected UserWrite struct");
+
+        // Required only for responses: non-optional when reading, absent when writing
+        assert!(read.contains("pub id: String,"));
+        assert!(!write.contains("pub id"));
+        // Required only for requests
+        assert!(write.contains("pub password: String,"));
+        assert!(!read.contains("pub password"));
+        assert!(read.contains("pub name: Option<String>,"));
+        assert!(write.contains("pub name: Option<String>,"));
+        // The combined struct is still generated for existing references
+        assert!(code.contains("pub struct User {"));
+    }
+
+    #[test]
+    fn test_prune_empty_structs_skips_them() {
+        let options = GenOptions {
+            prune_empty_structs: true,
+            ..Default::default()
+        };
+        let code = generate_from_spec(empty_struct_spec(), &options);
+
+        assert!(!code.contains("pub struct Marker"));
+        assert!(code.contains("pub struct Holder {"));
+    }
+
+    #[test]
+    fn test_params_struct_generation() {
+        let spec = json!({
+            "openapi": "3.0.0",
+            "info": { "title": "Test API", "version": "1.0.0" },
+            "paths": {
+                "/pets/{petId}": {
+                    "get": {
+                        "operationId": "getPet",
+                        "parameters": [
+                            {
+                                "name": "petId",
+                                "in": "path",
+                                "required": true,
+                                "schema": { "type": "string" }
+                            },
+                            {
+                                "name": "limit",
+                                "in": "query",
+                                "description": "Max items",
+                                "schema": { "type": "integer" }
+                            },
+                            { "name": "X-Trace", "in": "header", "schema": { "type": "string" } }
+                        ],
+                        "responses": { "200": { "description": "OK" } }
+                    }
+                }
+            }
+        });
+
+        let code = generate_from_spec(spec, &GenOptions::default());
+
+        assert!(code.contains("pub struct GetPetParams {"));
+        assert!(code.contains(
+            "    /// Path parameter\n    #[serde(rename = \"petId\")]\n    pub pet_id: String,\n"
+        ));
+        assert!(code.contains("    /// Max items\n    pub limit: Option<i64>,\n"));
+        assert!(code.contains(
+            "    /// Header parameter\n    #[serde(rename = \"X-Trace\")]\n    pub x_trace: Option<String>,\n"
+        ));
+        // Headers are sent separately, not as part of the URL
+        assert!(!code.contains("x_trace)"));
+    }
+
+    #[test]
+    fn test_params_build_request_url() {
+        let spec = json!({
+            "openapi": "3.0.0",
+            "info": { "title": "Test API", "version": "1.0.0" },
+            "paths": {
+                "/users/{userId}/posts": {
+                    "get": {
+                        "operationId": "listPosts",
+                        "parameters": [
+                            {
+                                "name": "userId",
+                                "in": "path",
+                                "required": true,
+                                "schema": { "type": "string" }
+                            },
+                            {
+                                "name": "limit",
+                                "in": "query",
+                                "required": true,
+                                "schema": { "type": "integer" }
+                            },
+                            { "name": "cursor", "in": "query", "schema": { "type": "string" } }
+                        ],
+                        "responses": { "200": { "description": "OK" } }
+                    }
+                }
+            }
+        });
+
+        let code = generate_from_spec(spec, &GenOptions::default());
+
+        let method = "    pub fn to_request_url(&self, base: &str) -> String {\n        \
+             let mut url = format!(\"{}/users/{}/posts\", base.trim_end_matches('/'), \
+             url_param(&self.user_id));\n        \
+             let mut query: Vec<String> = Vec::new();\n        \
+             query.push(format!(\"limit={}\", url_param(&self.limit)));\n        \
+             if let Some(value) = &self.cursor {\n            \
+             query.push(format!(\"cursor={}\", url_param(value)));\n        }\n";
+        assert!(code.contains(method));
+        assert_eq!(code.matches("fn url_param<").count(), 1);
+
+        // The generated method, as it runs against the generated struct
+        #[derive(serde::Serialize)]
+        struct ListPostsParams {
+            user_id: String,
+            limit: i64,
+            cursor: Option<String>,
+        }
+
+        impl ListPostsParams {
+            fn to_request_url(&self, base: &str) -> String {
+                let mut url = format!(
+                    "{}/users/{}/posts",
+                    base.trim_end_matches('/'),
+                    url_param(&self.user_id)
+                );
+                let mut query: Vec<String> = Vec::new();
+                query.push(format!("limit={}", url_param(&self.limit)));
+                if let Some(value) = &self.cursor {
+                    query.push(format!("cursor={}", url_param(value)));
+                }
+                if !query.is_empty() {
+                    url.push('?');
+                    url.push_str(&query.join("&"));
+                }
+                url
+            }
+        }
+
+        fn url_param<T: serde::Serialize + ?Sized>(value: &T) -> String {
+            match serde_json::to_value(value).unwrap() {
+                serde_json::Value::String(text) => percent_encode(&text),
+                value => percent_encode(&value.to_string()),
+            }
+        }
+
+        let mut params = ListPostsParams {
+            user_id: "ada lovelace".to_string(),
+            limit: 20,
+            cursor: None,
+        };
+        assert_eq!(
+            params.to_request_url("https://api.example.com/v1/"),
+            "https://api.example.com/v1/users/ada%20lovelace/posts?limit=20"
+        );
+        params.cursor = Some("a&b".to_string());
+        assert_eq!(
+            params.to_request_url("https://api.example.com/v1"),
+            "https://api.example.com/v1/users/ada%20lovelace/posts?limit=20&cursor=a%26b"
+        );
+    }
+
+    #[test]
+    fn test_request_url_exposes_secret_params() {
+        let spec = json!({
+            "openapi": "3.0.0",
+            "info": { "title": "Test API", "version": "1.0.0" },
+            "paths": {
+                "/reset/{token}": {
+                    "post": {
+                        "operationId": "resetPassword",
+                        "parameters": [
+                            {
+                                "name": "token",
+                                "in": "path",
+                                "required": true,
+                                "schema": { "type": "string", "format": "password" }
+                            },
+                            {
+                                "name": "pin",
+                                "in": "query",
+                                "schema": { "type": "string", "format": "password" }
+                            }
+                        ],
+                        "responses": { "204": { "description": "Reset" } }
+                    }
+                }
+            }
+        });
+        let options = GenOptions {
+            secrets: true,
+            ..GenOptions::default()
+        };
+
+        let code = generate_from_spec(spec, &options);
+
+        assert!(code.contains("    pub token: secrecy::SecretString,\n"));
+        assert!(code.contains(
+            "let mut url = format!(\"{}/reset/{}\", base.trim_end_matches('/'), \
+             url_param(secrecy::ExposeSecret::expose_secret(&self.token)));"
+        ));
+        assert!(code.contains(
+            "        if let Some(value) = &self.pin {\n            \
+             query.push(format!(\"pin={}\", url_param(secrecy::ExposeSecret::expose_secret(value))));\n"
+        ));
+    }
+
+    #[test]
+    fn test_webhook_params_have_no_request_url() {
+        let spec = json!({
+            "openapi": "3.0.0",
+            "info": { "title": "Test API", "version": "1.0.0" },
+            "paths": {},
+            "x-webhooks": {
+                "petAdded": {
+                    "post": {
+                        "operationId": "petAdded",
+                        "parameters": [
+                            { "name": "X-Signature", "in": "header", "schema": { "type": "string" } }
+                        ],
+                        "responses": { "200": { "description": "OK" } }
+                    }
+                }
+            }
+        });
+
+        let code = generate_from_spec(spec, &GenOptions::default());
+
+        assert!(code.contains("pub struct PetAddedParams {"));
+        assert!(!code.contains("to_request_url"));
+        assert!(!code.contains("fn url_param"));
+    }
+
+    #[test]
+    fn test_cookie_params_are_marked() {
+        let spec = json!({
+            "openapi": "3.0.0",
+            "info": { "title": "Test API", "version": "1.0.0" },
+            "paths": {
+                "/me": {
+                    "get": {
+                        "operationId": "getMe",
+                        "parameters": [
+                            {
+                                "name": "session_id",
+                                "in": "cookie",
+                                "required": true,
+                                "schema": { "type": "string" }
+                            },
+                            { "name": "theme", "in": "cookie", "schema": { "type": "string" } }
+                        ],
+                        "responses": { "200": { "description": "OK" } }
+                    }
+                }
+            }
+        });
+
+        let code = generate_from_spec(spec, &GenOptions::default());
+
+        assert!(code.contains("    /// Cookie parameter\n    pub session_id: String,\n"));
+        assert!(code.contains("    pub theme: Option<String>,\n"));
+        assert!(code.contains(
+            "impl GetMeParams {\n    /// Wire names of the fields sent in the `Cookie` header\n    \
+             pub const COOKIE_PARAMS: &'static [&'static str] = &[\"session_id\", \"theme\"];\n}"
+        ));
+    }
+
+    #[test]
+    fn test_split_rw_retargets_requests_responses_and_nested_refs() {
+        let spec = json!({
+            "openapi": "3.0.0",
+            "info": { "title": "Test API", "version": "1.0.0" },
+            "paths": {
+                "/pets": {
+                    "post": {
+                        "operationId": "createPet",
+                        "requestBody": {
+                            "content": {
+                                "application/json": {
+                                    "schema": { "$ref": "#/components/schemas/Pet" }
+                                }
+                            }
+                        },
+                        "responses": {
+                            "201": {
+                                "description": "Created",
+                                "content": {
+                                    "application/json": {
+                                        "schema": { "$ref": "#/components/schemas/Pet" }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            },
+            "components": {
+                "schemas": {
+                    "Owner": {
+                        "type": "object",
+                        "properties": { "id": { "type": "string", "readOnly": true } }
+                    },
+                    "Pet": {
+                        "type": "object",
+                        "required": ["id"],
+                        "properties": {
+                            "id": { "type": "string", "readOnly": true },
+                            "owner": { "$ref": "#/components/schemas/Owner" }
+                        }
+                    }
+                }
+            }
+        });
+
+        let options = GenOptions {
+            split_rw: true,
+            ..Default::default()
+        };
+        let code = generate_from_spec(spec.clone(), &options);
+
+        assert!(code.contains("pub struct CreatePetRequest {\n    pub body: PetWrite,\n}"));
+        assert!(code.contains("pub struct CreatePet201 {\n    pub body: PetRead,\n}"));
+        assert!(code.contains("pub struct PetWrite {\n    pub owner: Option<OwnerWrite>,\n}"));
+        assert!(code.contains("    pub owner: Option<OwnerRead>,\n"));
+
+        // Without the flag the shared struct is used everywhere
+        let code = generate_from_spec(spec, &GenOptions::default());
+        assert!(code.contains("pub struct CreatePetRequest {\n    pub body: Pet,\n}"));
+        assert!(!code.contains("OwnerWrite"));
+    }
+
+    #[test]
+    fn test_schema_defaults_generate_default_fns_and_impl() {
+        let spec = json!({
+            "openapi": "3.0.0",
+            "info": { "title": "Test API", "version": "1.0.0" },
+            "paths": {},
+            "components": {
+                "schemas": {
+                    "Settings": {
+                        "type": "object",
+                        "properties": {
+                            "retries": { "type": "integer", "default": 3 },
+                            "ratio": { "type": "number", "default": 1 },
+                            "theme": { "type": "string", "default": "dark" },
+                            "tags": {
+                                "type": "array",
+                                "items": { "type": "string" },
+                                "default": ["a"]
+                            },
+                            "note": { "type": "string" }
+                        }
+                    },
+                    "Pet": {
+                        "type": "object",
+                        "required": ["name"],
+                        "properties": {
+                            "name": { "type": "string" },
+                            "status": { "type": "string", "default": "available" }
+                        }
+                    }
+                }
+            }
+        });
+
+        let code = generate_from_spec(spec, &GenOptions::default());
+
+        assert!(code.contains(
+            "    #[serde(default = \"default_settings_retries\")]\n    pub retries: i64,\n"
+        ));
+        assert!(code.contains("fn default_settings_retries() -> i64 {\n    3\n}"));
+        assert!(code.contains("fn default_settings_ratio() -> f64 {\n    1.0\n}"));
+        assert!(
+            code.contains("fn default_settings_theme() -> String {\n    \"dark\".to_string()\n}")
+        );
+        assert!(code.contains(
+            "fn default_settings_tags() -> Vec<String> {\n    \
+             serde_json::from_str(\"[\\\"a\\\"]\").expect(\"invalid default\")\n}"
+        ));
+        assert!(code.contains("impl Default for Settings {"));
+        assert!(code.contains("            note: None,\n"));
+
+        // A required field without a default rules out `impl Default`
+        assert!(code.contains("fn default_pet_status() -> String {"));
+        assert!(!code.contains("impl Default for Pet {"));
+    }
+
+    #[test]
+    fn test_validate_emits_constraint_attributes() {
+        let spec = json!({
+            "openapi": "3.0.0",
+            "info": { "title": "Test", "version": "1.0" },
+            "paths": {},
+            "components": {
+                "schemas": {
+                    "User": {
+                        "type": "object",
+                        "required": ["name"],
+                        "properties": {
+                            "name": {
+                                "type": "string",
+                                "minLength": 1,
+                                "maxLength": 64,
+                                "pattern": "^[a-z]+$"
+                            },
+                            "score": { "type": "number", "minimum": 0, "maximum": 1.5 },
+                            "tags": {
+                                "type": "array",
+                                "items": { "type": "string" },
+                                "maxItems": 5
+                            }
+                        }
+                    }
+                }
+            }
+        });
+
+        let options = GenOptions {
+            validate: true,
+            ..Default::default()
+        };
+        let code = generate_from_spec(spec.clone(), &options);
+
+        assert!(code.contains("use validator::Validate;"));
+        assert!(code.contains("#[derive(Debug, Clone, Serialize, Deserialize, Validate)]"));
+        assert!(code.contains(
+            "    #[validate(length(min = 1, max = 64))]\n    \
+             #[validate(regex(path = *RE_USER_NAME))]\n    pub name: String,\n"
+        ));
+        assert!(code.contains("    #[validate(range(min = 0.0, max = 1.5))]\n"));
+        assert!(code.contains("    #[validate(length(max = 5))]\n    pub tags:"));
+        assert!(code.contains("static RE_USER_NAME: once_cell::sync::Lazy<regex::Regex>"));
+        assert!(code.contains("regex::Regex::new(\"^[a-z]+$\")"));
+
+        // Constraints are ignored unless asked for
+        let code = generate_from_spec(spec, &GenOptions::default());
+        assert!(!code.contains("validate"));
+    }
+
+    #[test]
+    fn test_binary_strings_become_bytes() {
+        let spec = json!({
+            "openapi": "3.0.0",
+            "info": { "title": "Test", "version": "1.0" },
+            "paths": {
+                "/files": {
+                    "post": {
+                        "operationId": "uploadFile",
+                        "requestBody": {
+                            "content": {
+                                "application/octet-stream": {
+                                    "schema": { "type": "string", "format": "binary" }
+                                }
+                            }
+                        },
+                        "responses": {
+                            "200": {
+                                "description": "Stored file",
+                                "content": {
+                                    "application/octet-stream": {
+                                        "schema": { "type": "string", "format": "binary" }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            },
+            "components": {
+                "schemas": {
+                    "Attachment": {
+                        "type": "object",
+                        "required": ["data"],
+                        "properties": {
+                            "data": { "type": "string", "format": "binary" }
+                        }
+                    }
+                }
+            }
+        });
+
+        let code = generate_from_spec(spec.clone(), &GenOptions::default());
+        assert!(code.contains("    pub data: Vec<u8>,\n"));
+        assert!(code.contains("pub struct UploadFileRequest {\n    pub body: Vec<u8>,\n"));
+        assert!(code.contains("pub struct UploadFile200 {\n    pub body: Vec<u8>,\n"));
+
+        let options = GenOptions {
+            bytes: true,
+            ..Default::default()
+        };
+        let code = generate_from_spec(spec, &options);
+        assert!(code.contains("    pub data: bytes::Bytes,\n"));
+        assert!(code.contains("pub struct UploadFileRequest {\n    pub body: bytes::Bytes,\n"));
+        assert!(code.contains("pub struct UploadFile200 {\n    pub body: bytes::Bytes,\n"));
+    }
+
+    #[test]
+    fn test_byte_strings_use_base64_newtype() {
+        let spec = json!({
+            "openapi": "3.0.0",
+            "info": { "title": "Test", "version": "1.0" },
+            "paths": {},
+            "components": {
+                "schemas": {
+                    "Blob": {
+                        "type": "object",
+                        "required": ["data"],
+                        "properties": {
+                            "data": { "type": "string", "format": "byte" },
+                            "chunks": {
+                                "type": "array",
+                                "items": { "type": "string", "format": "byte" }
+                            }
+                        }
+                    }
+                }
+            }
+        });
+
+        let code = generate_from_spec(spec, &GenOptions::default());
+
+        assert!(code.contains("    pub data: Base64Bytes,\n"));
+        assert!(code.contains("    pub chunks: Option<Vec<Base64Bytes>>,\n"));
+        assert_eq!(
+            code.matches("pub struct Base64Bytes(pub Vec<u8>);").count(),
+            1
+        );
+        assert!(code.contains("impl<'de> Deserialize<'de> for Base64Bytes {"));
+
+        let code = generate_from_spec(
+            json!({
+                "openapi": "3.0.0",
+                "info": { "title": "Test", "version": "1.0" },
+                "paths": {}
+            }),
+            &GenOptions::default(),
+        );
+        assert!(!code.contains("Base64Bytes"));
+    }
+
+    #[test]
+    fn test_format_newtypes_for_email_hostname_and_uri() {
+        let spec = json!({
+            "openapi": "3.0.0",
+            "info": { "title": "Test", "version": "1.0" },
+            "paths": {},
+            "components": {
+                "schemas": {
+                    "Contact": {
+                        "type": "object",
+                        "required": ["email"],
+                        "properties": {
+                            "email": { "type": "string", "format": "email", "maxLength": 64 },
+                            "host": { "type": "string", "format": "hostname" },
+                            "site": { "type": "string", "format": "uri" },
+                            "ttl": { "type": "string", "format": "duration" }
+                        }
+                    }
+                }
+            }
+        });
+
+        let code = generate_from_spec(spec.clone(), &GenOptions::default());
+        assert!(code.contains("    pub email: String,\n"));
+        assert!(!code.contains("pub struct Email("));
+
+        let options = GenOptions {
+            format_newtypes: true,
+            validate: true,
+            ..Default::default()
+        };
+        let code = generate_from_spec(spec.clone(), &options);
+        assert!(code.contains("    pub email: Email,\n"));
+        assert!(code.contains("    pub host: Option<Hostname>,\n"));
+        assert!(code.contains("    pub site: Option<url::Url>,\n"));
+        assert!(code.contains(
+            "#[serde(try_from = \"String\", into = \"String\")]\npub struct Email(String);"
+        ));
+        assert!(code.contains("pub struct Hostname(String);"));
+        // Length rules only apply to plain strings
+        assert!(!code.contains("#[validate(length"));
+
+        // Formats without a newtype are left to the other format flags
+        let options = GenOptions {
+            format_newtypes: true,
+            duration: true,
+            ..Default::default()
+        };
+        let code = generate_from_spec(spec, &options);
+        assert!(code.contains("    pub email: Email,\n"));
+        assert!(code.contains("    pub ttl: Option<iso8601_duration::Duration>,\n"));
+    }
+
+    #[test]
+    fn test_ip_as_string_keeps_plain_strings() {
+        let spec = json!({
+            "openapi": "3.0.0",
+            "info": { "title": "Test", "version": "1.0" },
+            "paths": {},
+            "components": {
+                "schemas": {
+                    "Peer": {
+                        "type": "object",
+                        "required": ["address"],
+                        "properties": {
+                            "address": { "type": "string", "format": "ipv4" }
+                        }
+                    }
+                }
+            }
+        });
+
+        let code = generate_from_spec(spec.clone(), &GenOptions::default());
+        assert!(code.contains("    pub address: std::net::Ipv4Addr,\n"));
+
+        let options = GenOptions {
+            ip_as_string: true,
+            ..Default::default()
+        };
+        let code = generate_from_spec(spec, &options);
+        assert!(code.contains("    pub address: String,\n"));
+    }
+
+    #[test]
+    fn test_deprecated_schemas_properties_and_operations() {
+        let spec = json!({
+            "openapi": "3.0.0",
+            "info": { "title": "Test", "version": "1.0" },
+            "paths": {
+                "/legacy": {
+                    "get": {
+                        "operationId": "getLegacy",
+                        "deprecated": true,
+                        "parameters": [
+                            { "name": "q", "in": "query", "schema": { "type": "string" } }
+                        ],
+                        "responses": {
+                            "200": {
+                                "description": "Legacy item",
+                                "content": {
+                                    "application/json": {
+                                        "schema": { "$ref": "#/components/schemas/Legacy" }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            },
+            "components": {
+                "schemas": {
+                    "Legacy": {
+                        "type": "object",
+                        "deprecated": true,
+                        "properties": {
+                            "name": { "type": "string", "deprecated": true },
+                            "id": { "type": "string" }
+                        }
+                    }
+                }
+            }
+        });
+
+        let code = generate_from_spec(spec, &GenOptions::default());
+        let attr = "#[deprecated(note = \"deprecated in the API specification\")]";
+
+        assert!(code.contains("#![allow(deprecated)]"));
+        assert!(code.contains(&format!(
+            "{attr}\n#[derive(Debug, Clone, Serialize, Deserialize)]\npub struct Legacy {{"
+        )));
+        assert!(code.contains(&format!("    {attr}\n    pub name: Option<String>,")));
+        assert!(code.contains("    pub id: Option<String>,"));
+        assert!(!code.contains(&format!("{attr}\n    pub id")));
+        assert!(code.contains(&format!("/// GetLegacyParams\n{attr}\n")));
+        assert!(code.contains(&format!(
+            "{attr}\n#[derive(Debug, Clone, Deserialize)]\npub struct GetLegacy200 {{"
+        )));
+    }
+
+    #[test]
+    fn test_descriptions_become_doc_comments() {
+        let spec = json!({
+            "openapi": "3.0.0",
+            "info": { "title": "Test", "version": "1.0" },
+            "paths": {},
+            "components": {
+                "schemas": {
+                    "Base": {
+                        "type": "object",
+                        "properties": { "id": { "type": "string" } }
+                    },
+                    "Pet": {
+                        "description": "A pet.\n\nExample:\n```\n{\"id\": \"1\"}\n```",
+                        "allOf": [
+                            { "$ref": "#/components/schemas/Base" },
+                            {
+                                "type": "object",
+                                "properties": {
+                                    "name": {
+                                        "type": "string",
+                                        "description": "Display name\n  shown in lists"
+                                    }
+                                }
+                            }
+                        ]
+                    },
+                    "Shape": {
+                        "description": "Any shape",
+                        "oneOf": [
+                            { "$ref": "#/components/schemas/Base" },
+                            { "type": "string" }
+                        ]
+                    }
+                }
+            }
+        });
+
+        let code = generate_from_spec(spec, &GenOptions::default());
+
+        assert!(code.contains(
+            "/// A pet.\n///\n/// Example:\n/// ```text\n/// {\"id\": \"1\"}\n/// ```\n#[derive("
+        ));
+        assert!(code.contains(
+            "    /// Display name\n    /// shown in lists\n    pub name: Option<String>,"
+        ));
+        assert!(code.contains("/// Any shape\n#[derive("));
+        assert!(code.contains("/// Base\n#[derive("));
+    }
+
+    #[test]
+    fn test_x_rust_use_adds_imports() {
+        let spec = json!({
+            "openapi": "3.0.0",
+            "info": { "title": "Test", "version": "1.0" },
+            "paths": {},
+            "components": {
+                "schemas": {
+                    "Money": {
+                        "type": "string",
+                        "x-rust-type": "Decimal",
+                        "x-rust-use": "rust_decimal::Decimal"
+                    },
+                    "Order": {
+                        "type": "object",
+                        "required": ["id", "total"],
+                        "properties": {
+                            "id": {
+                                "type": "string",
+                                "x-rust-type": "Uuid",
+                                "x-rust-use": ["uuid::Uuid"]
+                            },
+                            "total": {
+                                "type": "string",
+                                "x-rust-type": "Decimal",
+                                "x-rust-use": "rust_decimal::Decimal"
+                            },
+                            "placed": { "type": "string", "format": "uuid" }
+                        }
+                    }
+                }
+            }
+        });
+
+        let code = generate_from_spec(spec, &GenOptions::default());
+
+        assert_eq!(code.matches("use rust_decimal::Decimal;\n").count(), 1);
+        // Already imported for `format: uuid`
+        assert_eq!(code.matches("use uuid::Uuid;\n").count(), 1);
+        assert!(code.contains("pub type Money = Decimal;"));
+        assert!(code.contains("    pub total: Decimal,\n"));
+    }
+
+    #[test]
+    fn test_integer_enum_serializes_as_number() {
+        let spec = json!({
+            "openapi": "3.0.0",
+            "info": { "title": "Test", "version": "1.0" },
+            "paths": {},
+            "components": {
+                "schemas": {
+                    "Priority": {
+                        "type": "integer",
+                        "enum": [1, 2, -1]
+                    }
+                }
+            }
+        });
+
+        let code = generate_from_spec(spec, &GenOptions::default());
+
+        assert!(code.contains("#[serde(into = \"i64\", try_from = \"i64\")]\n#[repr(i64)]"));
+        assert!(code.contains("    Value2 = 2,\n    ValueMinus1 = -1,\n"));
+        assert!(code.contains("            -1 => Ok(Self::ValueMinus1),\n"));
+        assert!(code.contains("pub enum ParseError"));
+    }
+
+    #[test]
+    fn test_mixed_enum_matches_each_literal() {
+        let spec = json!({
+            "openapi": "3.0.0",
+            "info": { "title": "Test", "version": "1.0" },
+            "paths": {},
+            "components": {
+                "schemas": {
+                    "Limit": {
+                        "enum": ["auto", 10, true, 0.5]
+                    }
+                }
+            }
+        });
+
+        let code = generate_from_spec(spec, &GenOptions::default());
+
+        assert!(code.contains(
+            "pub enum Limit {\n    Auto,\n    Value10,\n    True,\n    Value0Point5,\n}"
+        ));
+        assert!(code.contains("Self::Value10 => serializer.serialize_i64(10),"));
+        assert!(code.contains("serde_json::Value::String(s) if s == \"auto\" => Ok(Self::Auto),"));
+        assert!(code.contains("serde_json::Value::Bool(true) => Ok(Self::True),"));
+        assert!(code.contains("n.as_f64() == Some(0.5) => Ok(Self::Value0Point5),"));
+    }
+
+    #[test]
+    fn test_security_schemes_generate_typed_configs() {
+        let openapi: OpenAPI = serde_json::from_value(json!({
+            "openapi": "3.0.0",
+            "info": { "title": "Test", "version": "1.0" },
+            "paths": {},
+            "components": {
+                "securitySchemes": {
+                    "api_key": { "type": "apiKey", "name": "X-API-Key", "in": "header" },
+                    "bearerAuth": { "type": "http", "scheme": "bearer", "bearerFormat": "JWT" }
+                }
+            }
+        }))
+        .expect("Failed to deserialize OpenAPI spec");
+
+        let schemes = crate::parser::parse_security_schemes(&openapi);
+        let code = generate_security(&schemes).expect("Failed to generate security");
+
+        assert!(code.contains(
+            "pub const API_KEY: ApiKey = ApiKey {\n    name: \"X-API-Key\",\n    location: ApiKeyLocation::Header,\n};"
+        ));
+        assert!(code.contains("bearer_format: Some(\"JWT\"),"));
+        assert!(code.contains("pub enum SecurityScheme {\n    ApiKey,\n    BearerAuth,\n}"));
+        assert!(code.contains(
+            "SecurityScheme::BearerAuth => SecuritySchemeConfig::HttpBearer(BEARER_AUTH),"
+        ));
+    }
+
+    #[test]
+    fn test_servers_generate_url_constants_and_variables() {
+        let openapi: OpenAPI = serde_json::from_value(json!({
+            "openapi": "3.0.0",
+            "info": { "title": "Test", "version": "1.0" },
+            "servers": [
+                { "url": "https://api.example.com", "description": "Production" },
+                {
+                    "url": "https://{region}.example.com",
+                    "variables": { "region": { "default": "eu", "enum": ["eu", "us"] } }
+                }
+            ],
+            "paths": {}
+        }))
+        .expect("Failed to deserialize OpenAPI spec");
+
+        let servers = crate::parser::parse_servers(&openapi);
+        let code = generate_servers(&servers).expect("Failed to generate servers");
+
+        assert!(code.contains("pub const PRODUCTION: &str = \"https://api.example.com\";"));
+        assert!(code.contains("pub struct Server2Variables {\n    /// Allowed values: `eu`, `us`\n    pub region: String,\n}"));
+        assert!(code.contains("            region: \"eu\".to_string(),\n"));
+        assert!(
+            code.contains("        SERVER2\n            .replace(\"{region}\", &self.region)\n")
+        );
+        assert!(code.contains("pub enum Server {\n    Production,\n    Server2,\n}"));
+        assert!(code.contains("Server::Server2 => Server2Variables::default().url(),"));
+    }
+
+    #[test]
+    fn test_group_by_tag_shares_cross_tag_models() {
+        let op = |tag: &str, id: &str, schema: &str| {
+            json!({
+                "tags": [tag],
+                "operationId": id,
+                "responses": {
+                    "200": {
+                        "description": "ok",
+                        "content": {
+                            "application/json": {
+                                "schema": { "$ref": format!("#/components/schemas/{schema}") }
+                            }
+                        }
+                    }
+                }
+            })
+        };
+        let openapi: OpenAPI = serde_json::from_value(json!({
+            "openapi": "3.0.0",
+            "info": { "title": "Test", "version": "1.0" },
+            "paths": {
+                "/pets": { "get": op("pets", "listPets", "PetPage") },
+                "/orders": { "get": op("store", "getOrder", "Order") }
+            },
+            "components": {
+                "schemas": {
+                    "Pet": { "type": "object", "properties": { "name": { "type": "string" } } },
+                    "PetPage": {
+                        "type": "object",
+                        "properties": {
+                            "items": { "type": "array", "items": { "$ref": "#/components/schemas/Pet" } }
+                        }
+                    },
+                    "Order": {
+                        "type": "object",
+                        "properties": { "pet": { "$ref": "#/components/schemas/Pet" } }
+                    }
+                }
+            }
+        }))
+        .expect("Failed to deserialize OpenAPI spec");
+
+        let (models, requests, responses) =
+            parse_openapi(&openapi).expect("Failed to parse OpenAPI spec");
+        let tags = crate::parser::operation_tags(&openapi).expect("Failed to collect tags");
+        let modules = generate_tag_modules(
+            &models,
+            &requests,
+            &responses,
+            &tags,
+            &GenOptions::default(),
+        )
+        .expect("Failed to generate modules");
+
+        let names: Vec<&str> = modules.iter().map(|(name, _)| name.as_str()).collect();
+        assert_eq!(names, vec!["common", "pets", "store"]);
+        assert!(modules[0].1.contains("pub struct Pet {"));
+        assert!(modules[1].1.contains("pub struct PetPage {"));
+        assert!(modules[1].1.contains("use super::common::*;"));
+        assert!(modules[2].1.contains("pub struct Order {"));
+        assert!(!modules[2].1.contains("pub struct Pet {"));
+    }
+
+    #[test]
+    fn test_tag_modules_avoid_generated_module_names() {
+        assert_eq!(tag_module_name("Pet Store"), "pet_store");
+        assert_eq!(tag_module_name("Security"), "security_tag");
+        assert_eq!(tag_module_name("common"), "common_tag");
+        assert_eq!(tag_module_name("Mod"), "mod_tag");
+        assert_eq!(tag_module_name("type"), "type_");
+        assert_eq!(tag_module_name("2024"), "tag_2024");
+        assert_eq!(tag_module_name("!!!"), "tag");
+
+        let openapi: OpenAPI = serde_json::from_value(json!({
+            "openapi": "3.0.0",
+            "info": { "title": "Test", "version": "1.0" },
+            "paths": {
+                "/login": {
+                    "post": {
+                        "tags": ["Security"],
+                        "operationId": "login",
+                        "responses": {
+                            "200": {
+                                "description": "ok",
+                                "content": {
+                                    "application/json": {
+                                        "schema": { "$ref": "#/components/schemas/Token" }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            },
+            "components": {
+                "schemas": {
+                    "Token": { "type": "object", "properties": { "value": { "type": "string" } } }
+                }
+            }
+        }))
+        .expect("Failed to deserialize OpenAPI spec");
+
+        let (models, requests, responses) =
+            parse_openapi(&openapi).expect("Failed to parse OpenAPI spec");
+        let tags = crate::parser::operation_tags(&openapi).expect("Failed to collect tags");
+        let modules = generate_tag_modules(
+            &models,
+            &requests,
+            &responses,
+            &tags,
+            &GenOptions::default(),
+        )
+        .expect("Failed to generate modules");
+
+        let names: Vec<&str> = modules.iter().map(|(name, _)| name.as_str()).collect();
+        assert_eq!(names, vec!["security_tag"]);
+        assert!(modules[0].1.contains("pub struct Token {"));
+
+        let lib = generate_lib_with(&["security_tag", "security", "security"]).unwrap();
+        assert!(lib.ends_with("pub mod security_tag;\npub mod security;\n"));
+    }
+
+    #[test]
+    fn test_multipart_body_uses_file_parts() {
+        let spec = json!({
+            "openapi": "3.0.0",
+            "info": { "title": "Test", "version": "1.0" },
+            "paths": {
+                "/upload": {
+                    "post": {
+                        "operationId": "upload",
+                        "requestBody": {
+                            "content": {
+                                "multipart/form-data": {
+                                    "schema": {
+                                        "type": "object",
+                                        "required": ["file"],
+                                        "properties": {
+                                            "file": { "type": "string", "format": "binary" },
+                                            "title": { "type": "string" }
+                                        }
+                                    }
+                                }
+                            }
+                        },
+                        "responses": { "200": { "description": "ok" } }
+                    }
+                }
+            }
+        });
+
+        let code = generate_from_spec(spec, &GenOptions::default());
+
+        assert!(code.contains("pub struct UploadMultipartBody {\n    pub file: FilePart,\n    pub title: Option<String>,\n}"));
+        assert!(code.contains("pub body: UploadMultipartBody,"));
+        assert!(code.contains("pub struct FilePart {\n    pub bytes: Vec<u8>,"));
+    }
+
+    #[test]
+    fn test_form_body_struct_and_content_type_marker() {
+        let spec = json!({
+            "openapi": "3.0.0",
+            "info": { "title": "Test", "version": "1.0" },
+            "paths": {
+                "/token": {
+                    "post": {
+                        "operationId": "getToken",
+                        "requestBody": {
+                            "content": {
+                                "application/x-www-form-urlencoded": {
+                                    "schema": { "$ref": "#/components/schemas/TokenForm" }
+                                }
+                            }
+                        },
+                        "responses": { "200": { "description": "ok" } }
+                    }
+                }
+            },
+            "components": {
+                "schemas": {
+                    "TokenForm": {
+                        "type": "object",
+                        "properties": { "scope": { "type": "string" } }
+                    }
+                }
+            }
+        });
+
+        let code = generate_from_spec(spec, &GenOptions::default());
+
+        assert!(code.contains("pub struct GetTokenFormBody {\n    pub scope: Option<String>,\n}"));
+        assert!(code.contains("    pub body: GetTokenFormBody,\n"));
+        assert!(code.contains(
+            "impl GetTokenRequest {\n    pub const CONTENT_TYPE: &'static str = \"application/x-www-form-urlencoded\";\n}"
+        ));
+    }
+
+    #[test]
+    fn test_fixtures_embed_examples_with_deserialize_tests() {
+        let openapi: OpenAPI = serde_json::from_value(json!({
+            "openapi": "3.0.0",
+            "info": { "title": "Test", "version": "1.0" },
+            "paths": {
+                "/pets": {
+                    "get": {
+                        "operationId": "listPets",
+                        "responses": {
+                            "200": {
+                                "description": "ok",
+                                "content": {
+                                    "application/json": {
+                                        "schema": {
+                                            "type": "array",
+                                            "items": { "$ref": "#/components/schemas/Pet" }
+                                        },
+                                        "examples": { "empty": { "value": [] } }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            },
+            "components": {
+                "schemas": {
+                    "Pet": {
+                        "type": "object",
+                        "properties": { "name": { "type": "string" } },
+                        "example": { "name": "Rex \"#1\"" }
+                    }
+                }
+            }
+        }))
+        .expect("Failed to deserialize OpenAPI spec");
+
+        let (models, requests, responses) =
+            crate::parser::parse_openapi(&openapi).expect("Failed to parse OpenAPI spec");
+        let examples = crate::parser::parse_examples(&openapi, &models, &requests, &responses);
+        let code = generate_fixtures(&examples, &["models"]).expect("Failed to generate fixtures");
+
+        assert!(code.contains("use super::models::*;\n"));
+        assert!(code.contains(
+            "pub const PET_EXAMPLE_JSON: &str = r##\"{\n  \"name\": \"Rex \\\"#1\\\"\"\n}\"##;"
+        ));
+        assert!(code.contains("pub const LIST_PETS200_EMPTY_EXAMPLE_JSON: &str = r#\"[]\"#;"));
+        assert!(code.contains("serde_json::from_str::<Vec<Pet>>(LIST_PETS200_EMPTY_EXAMPLE_JSON)"));
+        assert!(code.contains("    fn pet_example_deserializes() {\n"));
+    }
+
+    #[test]
+    fn test_unique_items_arrays_of_scalars_become_sets() {
+        let spec = json!({
+            "openapi": "3.0.0",
+            "info": { "title": "Test", "version": "1.0" },
+            "paths": {},
+            "components": {
+                "schemas": {
+                    "Tagged": {
+                        "type": "object",
+                        "required": ["tags", "scores"],
+                        "properties": {
+                            "tags": {
+                                "type": "array",
+                                "uniqueItems": true,
+                                "items": { "type": "string" }
+                            },
+                            "scores": {
+                                "type": "array",
+                                "uniqueItems": true,
+                                "items": { "type": "number" }
+                            }
+                        }
+                    }
+                }
+            }
+        });
+
+        let code = generate_from_spec(spec.clone(), &GenOptions::default());
+        assert!(code.contains("    pub tags: Vec<String>,\n"));
+
+        let options = GenOptions {
+            unique_items: Some(SetType::BtreeSet),
+            ..GenOptions::default()
+        };
+        let code = generate_from_spec(spec.clone(), &options);
+        assert!(code.contains("    pub tags: std::collections::BTreeSet<String>,\n"));
+        // Floats are neither Ord nor Hash
+        assert!(code.contains("    pub scores: Vec<f64>,\n"));
+
+        let options = GenOptions {
+            unique_items: Some(SetType::IndexSet),
+            ..GenOptions::default()
+        };
+        let code = generate_from_spec(spec.clone(), &options);
+        assert!(code.contains("    pub tags: indexmap::IndexSet<String>,\n"));
+
+        // validator cannot measure an IndexSet, so its item bounds are left out
+        let mut spec = spec;
+        spec["components"]["schemas"]["Tagged"]["properties"]["tags"]["maxItems"] = json!(5);
+        let options = GenOptions {
+            unique_items: Some(SetType::IndexSet),
+            validate: true,
+            ..GenOptions::default()
+        };
+        let code = generate_from_spec(spec.clone(), &options);
+        assert!(code.contains("    pub tags: indexmap::IndexSet<String>,\n"));
+        assert!(!code.contains("length(max = 5)"));
+
+        let options = GenOptions {
+            unique_items: Some(SetType::BtreeSet),
+            validate: true,
+            ..GenOptions::default()
+        };
+        let code = generate_from_spec(spec, &options);
+        assert!(code.contains(
+            "    #[validate(length(max = 5))]\n    pub tags: std::collections::BTreeSet<String>,\n"
+        ));
+    }
+
+    #[test]
+    fn test_fixed_arrays_for_pinned_item_counts() {
+        let spec = json!({
+            "openapi": "3.0.0",
+            "info": { "title": "Test", "version": "1.0" },
+            "paths": {},
+            "components": {
+                "schemas": {
+                    "Pixel": {
+                        "type": "object",
+                        "required": ["rgb", "history"],
+                        "properties": {
+                            "rgb": {
+                                "type": "array",
+                                "minItems": 3,
+                                "maxItems": 3,
+                                "items": { "type": "integer", "format": "int32" }
+                            },
+                            "history": {
+                                "type": "array",
+                                "minItems": 100,
+                                "maxItems": 100,
+                                "items": { "type": "integer", "format": "int32" }
+                            }
+                        }
+                    }
+                }
+            }
+        });
+
+        let code = generate_from_spec(spec.clone(), &GenOptions::default());
+        assert!(code.contains("    pub rgb: Vec<i32>,\n"));
+
+        let options = GenOptions {
+            fixed_arrays: true,
+            validate: true,
+            ..GenOptions::default()
+        };
+        let code = generate_from_spec(spec, &options);
+        assert!(code.contains("    pub rgb: [i32; 3],\n"));
+        assert!(!code.contains("length(min = 3, max = 3)"));
+        // serde only implements arrays up to 32 elements
+        assert!(code.contains(
+            "    #[validate(length(min = 100, max = 100))]\n    pub history: Vec<i32>,\n"
+        ));
+    }
+
+    #[test]
+    fn test_decimal_format_maps_to_rust_decimal() {
+        let spec = json!({
+            "openapi": "3.0.0",
+            "info": { "title": "Test", "version": "1.0" },
+            "paths": {},
+            "components": {
+                "schemas": {
+                    "Price": {
+                        "type": "object",
+                        "required": ["amount"],
+                        "properties": {
+                            "amount": { "type": "string", "format": "decimal" },
+                            "tax": { "type": "number", "format": "decimal" }
+                        }
+                    }
+                }
+            }
+        });
+
+        let code = generate_from_spec(spec.clone(), &GenOptions::default());
+        assert!(code.contains("    pub amount: String,\n"));
+        assert!(!code.contains("rust_decimal"));
+
+        let options = GenOptions {
+            decimal: true,
+            ..GenOptions::default()
+        };
+        let code = generate_from_spec(spec.clone(), &options);
+        assert!(code.contains("use rust_decimal::Decimal;\n"));
+        assert!(code.contains(
+            "    #[serde(with = \"rust_decimal::serde::str\")]\n    pub amount: Decimal,\n"
+        ));
+        assert!(code.contains(
+            "    #[serde(default, with = \"rust_decimal::serde::str_option\")]\n    pub tax: Option<Decimal>,\n"
+        ));
+
+        let options = GenOptions {
+            decimal: true,
+            format_newtypes: true,
+            ..GenOptions::default()
+        };
+        let code = generate_from_spec(spec, &options);
+        assert!(code.contains(
+            "    #[serde(with = \"rust_decimal::serde::str\")]\n    pub amount: Decimal,\n"
+        ));
+    }
+
+    #[test]
+    fn test_duration_format_maps_to_iso8601_duration() {
+        let spec = json!({
+            "openapi": "3.0.0",
+            "info": { "title": "Test", "version": "1.0" },
+            "paths": {},
+            "components": {
+                "schemas": {
+                    "Job": {
+                        "type": "object",
+                        "required": ["timeout"],
+                        "properties": {
+                            "timeout": { "type": "string", "format": "duration", "maxLength": 20 }
+                        }
+                    }
+                }
+            }
+        });
+
+        let code = generate_from_spec(spec.clone(), &GenOptions::default());
+        assert!(code.contains("    pub timeout: String,\n"));
+
+        let options = GenOptions {
+            duration: true,
+            validate: true,
+            ..GenOptions::default()
+        };
+        let code = generate_from_spec(spec.clone(), &options);
+        assert!(code.contains("    pub timeout: iso8601_duration::Duration,\n"));
+        assert!(!code.contains("length(max = 20)"));
+
+        let options = GenOptions {
+            duration: true,
+            format_newtypes: true,
+            ..GenOptions::default()
+        };
+        let code = generate_from_spec(spec, &options);
+        assert!(code.contains("    pub timeout: iso8601_duration::Duration,\n"));
+    }
+
+    #[test]
+    fn test_chrono_types_are_imported() {
+        let spec = json!({
+            "openapi": "3.0.0",
+            "info": { "title": "Test", "version": "1.0" },
+            "paths": {},
+            "components": {
+                "schemas": {
+                    "Opening": {
+                        "type": "object",
+                        "required": ["opens"],
+                        "properties": {
+                            "opens": { "type": "string", "format": "time" },
+                            "day": { "type": "string", "format": "date" }
+                        }
+                    },
+                    "Timestamps": {
+                        "type": "array",
+                        "items": { "type": "string", "format": "date-time" }
+                    }
+                }
+            }
+        });
+
+        let code = generate_from_spec(spec, &GenOptions::default());
+
+        assert!(code.contains("use chrono::{DateTime, NaiveDate, NaiveTime, Utc};\n"));
+        assert!(code.contains("    pub opens: NaiveTime,\n"));
+        assert!(code.contains("    pub day: Option<NaiveDate>,\n"));
+    }
+
+    #[test]
+    fn test_secrets_use_secret_string() {
+        let spec = json!({
+            "openapi": "3.0.0",
+            "info": { "title": "Test", "version": "1.0" },
+            "paths": {},
+            "components": {
+                "schemas": {
+                    "Login": {
+                        "type": "object",
+                        "required": ["password"],
+                        "properties": {
+                            "password": { "type": "string", "format": "password" },
+                            "token": { "type": "string", "x-secret": true }
+                        }
+                    }
+                }
+            }
+        });
+
+        let code = generate_from_spec(spec.clone(), &GenOptions::default());
+        assert!(code.contains("    pub password: String,\n"));
+        assert!(!code.contains("secrecy"));
+
+        let options = GenOptions {
+            secrets: true,
+            ..GenOptions::default()
+        };
+        let code = generate_from_spec(spec.clone(), &options);
+        assert!(code.contains(
+            "    #[serde(serialize_with = \"serialize_secret\")]\n    pub password: secrecy::SecretString,\n"
+        ));
+        assert!(code.contains(
+            "    #[serde(serialize_with = \"serialize_optional_secret\")]\n    pub token: Option<secrecy::SecretString>,\n"
+        ));
+        assert!(code.contains("fn serialize_optional_secret<S: serde::Serializer>("));
+
+        let options = GenOptions {
+            secrets: true,
+            format_newtypes: true,
+            ..GenOptions::default()
+        };
+        let code = generate_from_spec(spec, &options);
+        assert!(code.contains("    pub password: secrecy::SecretString,\n"));
+        assert!(code.contains("fn serialize_optional_secret<S: serde::Serializer>("));
+    }
+
+    #[test]
+    fn test_flatten_all_of_embeds_referenced_members() {
+        let spec = json!({
+            "openapi": "3.0.0",
+            "info": { "title": "Test", "version": "1.0" },
+            "paths": {},
+            "components": {
+                "schemas": {
+                    "Base": {
+                        "type": "object",
+                        "required": ["id"],
+                        "properties": { "id": { "type": "string" } }
+                    },
+                    "Pet": {
+                        "allOf": [
+                            { "$ref": "#/components/schemas/Base" },
+                            {
+                                "type": "object",
+                                "required": ["name"],
+                                "properties": { "name": { "type": "string" } }
+                            }
+                        ]
+                    }
+                }
+            }
+        });
+
+        let code = generate_from_spec(spec.clone(), &GenOptions::default());
+        assert!(code.contains("pub struct Pet {\n    pub id: String,\n    pub name: String,\n}"));
+
+        let options = GenOptions {
+            flatten_all_of: true,
+            ..GenOptions::default()
+        };
+        let code = generate_from_spec(spec, &options);
+        assert!(code.contains(
+            "pub struct Pet {\n    #[serde(flatten)]\n    pub base: Base,\n    pub name: String,\n}"
+        ));
+        assert!(code.contains("pub struct Base {\n    pub id: String,\n}"));
+    }
+
+    #[test]
+    fn test_all_of_traits_for_extended_schemas() {
+        let spec = json!({
+            "openapi": "3.0.0",
+            "info": { "title": "Test", "version": "1.0" },
+            "paths": {},
+            "components": {
+                "schemas": {
+                    "Base": {
+                        "type": "object",
+                        "required": ["id"],
+                        "properties": {
+                            "id": { "type": "string" },
+                            "note": { "type": "string" }
+                        }
+                    },
+                    "Pet": {
+                        "allOf": [
+                            { "$ref": "#/components/schemas/Base" },
+                            {
+                                "type": "object",
+                                "required": ["note"],
+                                "properties": { "name": { "type": "string" } }
+                            }
+                        ]
+                    }
+                }
+            }
+        });
+
+        let code = generate_from_spec(spec.clone(), &GenOptions::default());
+        assert!(!code.contains("trait"));
+
+        let options = GenOptions {
+            all_of_traits: true,
+            ..GenOptions::default()
+        };
+        let code = generate_from_spec(spec.clone(), &options);
+        assert!(code.contains(
+            "pub trait BaseFields {\n    fn id(&self) -> &String;\n    fn note(&self) -> Option<&String>;\n}"
+        ));
+        assert!(code.contains("impl BaseFields for Base {\n    fn id(&self) -> &String {\n        &self.id\n    }\n    fn note(&self) -> Option<&String> {\n        self.note.as_ref()\n    }\n}"));
+        // Pet requires `note`, which the getter still returns as an Option
+        assert!(code.contains("impl BaseFields for Pet {\n    fn id(&self) -> &String {\n        &self.id\n    }\n    fn note(&self) -> Option<&String> {\n        Some(&self.note)\n    }\n}"));
+
+        let options = GenOptions {
+            all_of_traits: true,
+            flatten_all_of: true,
+            ..GenOptions::default()
+        };
+        let code = generate_from_spec(spec, &options);
+        assert!(code.contains("        BaseFields::id(&self.base)\n"));
+    }
+
+    #[test]
+    fn test_response_with_several_content_types_is_an_enum() {
+        let spec = json!({
+            "openapi": "3.0.0",
+            "info": { "title": "Test", "version": "1.0" },
+            "paths": {
+                "/report": {
+                    "get": {
+                        "operationId": "getReport",
+                        "responses": {
+                            "200": {
+                                "description": "The report",
+                                "content": {
+                                    "application/json": {
+                                        "schema": { "$ref": "#/components/schemas/Report" }
+                                    },
+                                    "text/csv": { "schema": { "type": "string" } }
+                                }
+                            }
+                        }
+                    }
+                }
+            },
+            "components": {
+                "schemas": {
+                    "Report": { "type": "object", "properties": { "rows": { "type": "integer" } } }
+                }
+            }
+        });
+
+        let code = generate_from_spec(spec, &GenOptions::default());
+        assert!(code.contains(
+            "pub enum GetReport200 {\n    /// `application/json`\n    Json(Report),\n    /// `text/csv`\n    Csv(String),\n}"
+        ));
+        assert!(code.contains(
+            "pub const CONTENT_TYPES: &'static [&'static str] = &[\"application/json\", \"text/csv\"];"
+        ));
+        assert!(!code.contains("pub struct GetReport200"));
+    }
+
+    #[test]
+    fn test_xml_names_from_xml_objects() {
+        let spec = json!({
+            "openapi": "3.0.0",
+            "info": { "title": "Test", "version": "1.0" },
+            "paths": {},
+            "components": {
+                "schemas": {
+                    "pet": {
+                        "type": "object",
+                        "required": ["id", "name"],
+                        "properties": {
+                            "id": { "type": "integer", "x-xml": { "attribute": true } },
+                            "name": {
+                                "type": "string",
+                                "x-xml": { "name": "PetName", "prefix": "ns" }
+                            },
+                            "tags": {
+                                "type": "array",
+                                "items": { "type": "string", "x-xml": { "name": "tag" } }
+                            }
+                        }
+                    }
+                }
+            }
+        });
+
+        let code = generate_from_spec(spec.clone(), &GenOptions::default());
+        assert!(!code.contains("@id"));
+        assert!(!code.contains("#[serde(rename = \"pet\")]"));
+
+        let options = GenOptions {
+            xml: true,
+            ..GenOptions::default()
+        };
+        let code = generate_from_spec(spec, &options);
+        assert!(code.contains("#[serde(rename = \"pet\")]\npub struct Pet {"));
+        assert!(code.contains("    #[serde(rename = \"@id\")]\n    pub id: i64,\n"));
+        assert!(code.contains(
+            "    #[serde(rename(serialize = \"ns:PetName\", deserialize = \"PetName\"))]\n    pub name: String,\n"
+        ));
+        assert!(
+            code.contains("    #[serde(rename = \"tag\")]\n    pub tags: Option<Vec<String>>,\n")
+        );
+    }
+
+    #[test]
+    fn test_streamed_responses_get_item_and_stream_types() {
+        let spec = json!({
+            "openapi": "3.0.0",
+            "info": { "title": "Test", "version": "1.0" },
+            "paths": {
+                "/logs": {
+                    "get": {
+                        "operationId": "exportLogs",
+                        "responses": {
+                            "200": {
+                                "description": "Log lines",
+                                "content": {
+                                    "application/x-ndjson": {
+                                        "schema": {
+                                            "type": "array",
+                                            "items": { "$ref": "#/components/schemas/Log" }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                },
+                "/events": {
+                    "get": {
+                        "operationId": "streamEvents",
+                        "responses": {
+                            "200": {
+                                "description": "Events",
+                                "content": {
+                                    "text/event-stream": {
+                                        "schema": { "$ref": "#/components/schemas/Log" }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            },
+            "components": {
+                "schemas": {
+                    "Log": { "type": "object", "properties": { "line": { "type": "string" } } }
+                }
+            }
+        });
+
+        let code = generate_from_spec(spec, &GenOptions::default());
+        assert!(code.contains("pub type ExportLogs200Item = Log;\n"));
+        assert!(code.contains("pub type ExportLogs200Stream = Vec<ExportLogs200Item>;\n"));
+        assert!(code.contains(
+            "/// One item of the `text/event-stream` stream\npub type StreamEvents200Item = Log;\n"
+        ));
+        assert!(!code.contains("pub struct ExportLogs200"));
+    }
+
+    #[test]
+    fn test_double_option_for_optional_nullable_fields() {
+        let options = GenOptions {
+            double_option: true,
+            ..Default::default()
+        };
+        let code = generate_from_spec(
+            json!({
+                "openapi": "3.0.0",
+                "info": { "title": "Test API", "version": "1.0.0" },
+                "paths": {},
+                "components": {
+                    "schemas": {
+                        "UserPatch": {
+                            "type": "object",
+                            "properties": {
+                                "nickname": { "type": "string", "nullable": true },
+                                "age": { "type": "integer" },
+                                "email": { "type": "string", "nullable": true }
+                            },
+                            "required": ["email"]
+                        }
+                    }
+                }
+            }),
+            &options,
+        );
+
+        assert!(code.contains(
+            "    #[serde(default, skip_serializing_if = \"Option::is_none\", deserialize_with = \"deserialize_double_option\")]\n    pub nickname: Option<Option<String>>,"
+        ));
+        assert!(code.contains("    pub age: Option<i64>,"));
+        assert!(code.contains("    pub email: Option<String>,"));
+        assert!(code.contains("fn deserialize_double_option<'de, D, T>("));
+    }
+
+    #[test]
+    fn test_merge_patch_bodies_get_patch_structs() {
+        let code = generate_from_spec(
+            json!({
+                "openapi": "3.0.0",
+                "info": { "title": "Test API", "version": "1.0.0" },
+                "paths": {
+                    "/users/{id}": {
+                        "patch": {
+                            "operationId": "updateUser",
+                            "requestBody": {
+                                "content": {
+                                    "application/merge-patch+json": {
+                                        "schema": { "$ref": "#/components/schemas/User" }
+                                    }
+                                }
+                            },
+                            "responses": { "204": { "description": "Updated" } }
+                        }
+                    }
+                },
+                "components": {
+                    "schemas": {
+                        "User": {
+                            "type": "object",
+                            "properties": {
+                                "id": { "type": "string", "readOnly": true },
+                                "name": { "type": "string" }
+                            },
+                            "required": ["id", "name"]
+                        }
+                    }
+                }
+            }),
+            &GenOptions::default(),
+        );
+
+        assert!(code.contains(
+            "pub struct UserPatch {\n    #[serde(default, skip_serializing_if = \"Option::is_none\", deserialize_with = \"deserialize_double_option\")]\n    pub name: Option<Option<String>>,\n}"
+        ));
+        assert!(code.contains("pub struct User {\n    pub id: String,\n    pub name: String,\n}"));
+        assert!(code.contains("    pub body: UserPatch,\n"));
+    }
+
+    #[test]
+    fn test_split_rw_skips_patch_structs() {
+        let options = GenOptions {
+            split_rw: true,
+            ..Default::default()
+        };
+        let code = generate_from_spec(
+            json!({
+                "openapi": "3.0.0",
+                "info": { "title": "Test API", "version": "1.0.0" },
+                "paths": {
+                    "/users/{id}": {
+                        "patch": {
+                            "operationId": "updateUser",
+                            "requestBody": {
+                                "content": {
+                                    "application/merge-patch+json": {
+                                        "schema": { "$ref": "#/components/schemas/User" }
+                                    }
+                                }
+                            },
+                            "responses": { "204": { "description": "Updated" } }
+                        }
+                    }
+                },
+                "components": {
+                    "schemas": {
+                        "User": {
+                            "type": "object",
+                            "properties": {
+                                "id": { "type": "string", "readOnly": true },
+                                "password": { "type": "string", "writeOnly": true }
+                            }
+                        }
+                    }
+                }
+            }),
+            &options,
+        );
+
+        assert!(code.contains("pub struct UserRead {"));
+        assert!(code.contains("pub struct UserWrite {"));
+        assert!(code.contains("pub struct UserPatch {"));
+        assert!(!code.contains("UserPatchRead"));
+        assert!(!code.contains("UserPatchWrite"));
+    }
+
+    #[test]
+    fn test_deny_unknown_fields_for_closed_schemas() {
+        let options = GenOptions {
+            deny_unknown_fields: true,
+            ..Default::default()
+        };
+        let spec = json!({
+            "openapi": "3.0.0",
+            "info": { "title": "Test API", "version": "1.0.0" },
+            "paths": {},
+            "components": {
+                "schemas": {
+                    "Closed": {
+                        "type": "object",
+                        "properties": { "id": { "type": "string" } },
+                        "additionalProperties": false
+                    },
+                    "Open": {
+                        "type": "object",
+                        "properties": { "id": { "type": "string" } }
+                    }
+                }
+            }
+        });
+
+        let code = generate_from_spec(spec.clone(), &options);
+        assert!(code.contains("#[serde(deny_unknown_fields)]\npub struct Closed {"));
+        assert_eq!(code.matches("deny_unknown_fields").count(), 1);
+
+        let code = generate_from_spec(spec, &GenOptions::default());
+        assert!(!code.contains("deny_unknown_fields"));
+    }
+
+    #[test]
+    fn test_unsigned_integers() {
+        let spec = json!({
+            "openapi": "3.0.0",
+            "info": { "title": "Test API", "version": "1.0.0" },
+            "paths": {},
+            "components": {
+                "schemas": {
+                    "Stats": {
+                        "type": "object",
+                        "properties": {
+                            "count": { "type": "integer", "minimum": 0 },
+                            "small": { "type": "integer", "format": "int32", "minimum": 1 },
+                            "delta": { "type": "integer", "minimum": -5 },
+                            "flags": { "type": "integer", "format": "uint8" },
+                            "sizes": {
+                                "type": "array",
+                                "items": { "type": "integer", "format": "uint32" }
+                            }
+                        }
+                    }
+                }
+            }
+        });
+
+        let options = GenOptions {
+            unsigned: true,
+            ..Default::default()
+        };
+        let code = generate_from_spec(spec.clone(), &options);
+        assert!(code.contains("    pub count: Option<u64>,\n"));
+        assert!(code.contains("    pub small: Option<u32>,\n"));
+        assert!(code.contains("    pub delta: Option<i64>,\n"));
+        assert!(code.contains("    pub flags: Option<u8>,\n"));
+        assert!(code.contains("    pub sizes: Option<Vec<u32>>,\n"));
+
+        let code = generate_from_spec(spec, &GenOptions::default());
+        assert!(code.contains("    pub count: Option<i64>,\n"));
+        assert!(code.contains("    pub flags: Option<i64>,\n"));
+    }
+
+    #[test]
+    fn test_big_integers_are_serialized_as_strings() {
+        let spec = json!({
+            "openapi": "3.0.0",
+            "info": { "title": "Test API", "version": "1.0.0" },
+            "paths": {},
+            "components": {
+                "schemas": {
+                    "Ledger": {
+                        "type": "object",
+                        "properties": {
+                            "total": { "type": "integer", "format": "bigint" },
+                            "credit": { "type": "integer", "x-precision": 30 },
+                            "count": { "type": "integer", "x-precision": 10 }
+                        },
+                        "required": ["total"]
+                    }
+                }
+            }
+        });
+
+        let options = GenOptions {
+            big_integers: Some(BigIntType::I128),
+            ..Default::default()
+        };
+        let code = generate_from_spec(spec.clone(), &options);
+        assert!(
+            code.contains("    #[serde(with = \"big_integer_string\")]\n    pub total: i128,\n")
+        );
+        assert!(code.contains(
+            "    #[serde(default, with = \"big_integer_string::option\")]\n    pub credit: Option<i128>,\n"
+        ));
+        assert!(code.contains("    pub count: Option<i64>,\n"));
+        assert!(code.contains("mod big_integer_string {"));
+
+        let options = GenOptions {
+            big_integers: Some(BigIntType::Bigint),
+            ..Default::default()
+        };
+        let code = generate_from_spec(spec.clone(), &options);
+        assert!(code.contains("    pub total: num_bigint::BigInt,\n"));
+
+        let code = generate_from_spec(spec, &GenOptions::default());
+        assert!(code.contains("    pub total: i64,\n"));
+        assert!(!code.contains("big_integer_string"));
+    }
+
+    #[test]
+    fn test_validate_keeps_exclusive_bounds_exclusive() {
+        let spec = json!({
+            "openapi": "3.0.0",
+            "info": { "title": "Test", "version": "1.0" },
+            "paths": {},
+            "components": {
+                "schemas": {
+                    "Range": {
+                        "type": "object",
+                        "required": ["ratio", "id"],
+                        "properties": {
+                            "ratio": {
+                                "type": "number",
+                                "minimum": 0,
+                                "exclusiveMinimum": true,
+                                "maximum": 1
+                            },
+                            "id": {
+                                "type": "integer",
+                                "minimum": 0,
+                                "maximum": 100,
+                                "exclusiveMaximum": true
+                            }
+                        }
+                    }
+                }
+            }
+        });
+
+        let options = GenOptions {
+            validate: true,
+            ..Default::default()
+        };
+        let code = generate_from_spec(spec, &options);
+
+        assert!(code.contains(
+            "    #[validate(range(exclusive_min = 0.0, max = 1.0))]\n    pub ratio: f64,\n"
+        ));
+        assert!(code
+            .contains("    #[validate(range(min = 0, exclusive_max = 100))]\n    pub id: i64,\n"));
+    }
+
+    #[test]
+    fn test_enum_values_become_valid_variant_identifiers() {
+        let code = generate_from_spec(
+            json!({
+                "openapi": "3.0.0",
+                "info": { "title": "Test API", "version": "1.0.0" },
+                "paths": {},
+                "components": {
+                    "schemas": {
+                        "State": {
+                            "type": "string",
+                            "enum": ["in-progress", "in_progress", "2xx", "N/A", "", "self"]
+                        }
+                    }
+                }
+            }),
+            &GenOptions::default(),
+        );
+
+        assert!(code.contains("    #[serde(rename = \"in-progress\")]\n    InProgress,\n"));
+        assert!(code.contains("    #[serde(rename = \"in_progress\")]\n    InProgress2,\n"));
+        assert!(code.contains("    #[serde(rename = \"2xx\")]\n    Value2xx,\n"));
+        assert!(code.contains("    #[serde(rename = \"N/A\")]\n    NA,\n"));
+        assert!(code.contains("    #[serde(rename = \"\")]\n    Empty,\n"));
+        assert!(code.contains("    #[serde(rename = \"self\")]\n    SelfValue\n"));
+    }
+
+    #[test]
+    fn test_field_names_are_snake_cased() {
+        let code = generate_from_spec(
+            json!({
+                "openapi": "3.0.0",
+                "info": { "title": "Test API", "version": "1.0.0" },
+                "paths": {},
+                "components": {
+                    "schemas": {
+                        "Event": {
+                            "type": "object",
+                            "properties": {
+                                "createdAt": { "type": "string" },
+                                "userID": { "type": "string" },
+                                "HTTPStatus": { "type": "integer" },
+                                "address2Line": { "type": "string" },
+                                "retry-after": { "type": "integer" },
+                                "plain_name": { "type": "string" }
+                            }
+                        }
+                    }
+                }
+            }),
+            &GenOptions::default(),
+        );
+
+        assert!(code.contains("    #[serde(rename = \"createdAt\")]\n    pub created_at:"));
+        assert!(code.contains("    #[serde(rename = \"userID\")]\n    pub user_id:"));
+        assert!(code.contains("    #[serde(rename = \"HTTPStatus\")]\n    pub http_status:"));
+        assert!(code.contains("    #[serde(rename = \"address2Line\")]\n    pub address2_line:"));
+        assert!(code.contains("    #[serde(rename = \"retry-after\")]\n    pub retry_after:"));
+        assert!(code.contains("    pub plain_name: Option<String>,"));
+        assert!(!code.contains("rename = \"plain_name\""));
+    }
+
+    #[test]
+    fn test_uniform_struct_fields_use_container_rename_all() {
+        let code = generate_from_spec(
+            json!({
+                "openapi": "3.0.0",
+                "info": { "title": "Test API", "version": "1.0.0" },
+                "paths": {},
+                "components": {
+                    "schemas": {
+                        "Order": {
+                            "type": "object",
+                            "properties": {
+                                "orderId": { "type": "string" },
+                                "createdAt": { "type": "string" },
+                                "totalAmount": { "type": "number" },
+                                "id": { "type": "string" },
+                                "legacy_code": { "type": "string" }
+                            }
+                        },
+                        "Headers": {
+                            "type": "object",
+                            "properties": {
+                                "content-type": { "type": "string" },
+                                "retry-after": { "type": "integer" }
+                            }
+                        },
+                        "Point": {
+                            "type": "object",
+                            "properties": {
+                                "x": { "type": "number" },
+                                "yValue": { "type": "number" }
+                            }
+                        }
+                    }
+                }
+            }),
+            &GenOptions::default(),
+        );
+
+        assert!(code.contains("#[serde(rename_all = \"camelCase\")]\npub struct Order {"));
+        assert!(code.contains("    pub order_id: Option<String>,"));
+        assert!(code.contains("    pub id: Option<String>,"));
+        assert!(code.contains("    #[serde(rename = \"legacy_code\")]\n    pub legacy_code:"));
+        assert!(!code.contains("rename = \"orderId\""));
+        assert!(code.contains("#[serde(rename_all = \"kebab-case\")]\npub struct Headers {"));
+        // A single rename is kept on the field
+        assert!(code.contains("pub struct Point {\n"));
+        assert!(code.contains("    #[serde(rename = \"yValue\")]\n    pub y_value:"));
+    }
+
+    #[test]
+    fn test_configured_derives_apply_to_structs_and_enums() {
+        let options = GenOptions {
+            derives: vec!["Clone".to_string(), "PartialEq".to_string()],
+            ..Default::default()
+        };
+        let code = generate_from_spec(
+            json!({
+                "openapi": "3.0.0",
+                "info": { "title": "Test API", "version": "1.0.0" },
+                "paths": {},
+                "components": {
+                    "schemas": {
+                        "User": {
+                            "type": "object",
+                            "properties": { "name": { "type": "string" } }
+                        },
+                        "Status": { "type": "string", "enum": ["active", "inactive"] },
+                        "Key": {
+                            "type": "object",
+                            "x-rust-derives": ["PartialEq", "Eq", "Hash"],
+                            "properties": { "id": { "type": "string" } }
+                        }
+                    }
+                }
+            }),
+            &options,
+        );
+
+        assert!(code.contains(
+            "#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]\npub struct User {"
+        ));
+        assert!(code.contains("#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]\n#[serde(rename_all = \"lowercase\")]\npub enum Status {"));
+        assert!(code.contains(
+            "#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]\npub struct Key {"
+        ));
+    }
+
+    #[test]
+    fn test_configured_default_derive_replaces_generated_impl() {
+        let options = GenOptions {
+            derives: vec!["Default".to_string()],
+            ..Default::default()
+        };
+        let code = generate_from_spec(
+            json!({
+                "openapi": "3.0.0",
+                "info": { "title": "Test API", "version": "1.0.0" },
+                "paths": {},
+                "components": {
+                    "schemas": {
+                        "Settings": {
+                            "type": "object",
+                            "required": ["status"],
+                            "properties": {
+                                "status": { "$ref": "#/components/schemas/Status" },
+                                "retries": { "type": "integer", "default": 3 }
+                            }
+                        },
+                        "Status": { "type": "string", "enum": ["active", "inactive"] },
+                        "Pet": {
+                            "oneOf": [{ "type": "string" }, { "type": "integer" }]
+                        }
+                    }
+                }
+            }),
+            &options,
+        );
+
+        assert!(code.contains(
+            "#[derive(Debug, Clone, Serialize, Deserialize, Default)]\npub struct Settings {\n    \
+             #[serde(default = \"default_settings_retries\")]\n    pub retries: i64,\n    \
+             pub status: Status,\n}"
+        ));
+        assert!(code.contains("fn default_settings_retries() -> i64 {\n    3\n}"));
+        assert!(!code.contains("impl Default for Settings"));
+        // Deriving Default on an enum needs a #[default] variant
+        assert!(code.contains(
+            "#[derive(Debug, Clone, Serialize, Deserialize, Default)]\n\
+             #[serde(rename_all = \"lowercase\")]\npub enum Status {\n    #[default]\n    Active,\n"
+        ));
+        assert!(code.contains(
+            "#[derive(Debug, Clone, Serialize, Deserialize)]\n#[serde(untagged)]\npub enum Pet {"
+        ));
+
+        // The generated types, as they compile with the derive
+        #[derive(Debug, Clone, serde::Serialize, serde::Deserialize, Default, PartialEq)]
+        #[serde(rename_all = "lowercase")]
+        enum Status {
+            #[default]
+            Active,
+            Inactive,
+        }
+
+        #[derive(Debug, Clone, serde::Serialize, serde::Deserialize, Default)]
+        struct Settings {
+            #[serde(default = "default_settings_retries")]
+            retries: i64,
+            status: Status,
+        }
+
+        fn default_settings_retries() -> i64 {
+            3
+        }
+
+        assert_eq!(Settings::default().status, Status::Active);
+        let settings: Settings = serde_json::from_value(json!({ "status": "inactive" })).unwrap();
+        assert_eq!(settings.status, Status::Inactive);
+        assert_eq!(settings.retries, 3);
+    }
+
+    #[test]
+    fn test_comparison_derives_are_rejected_on_secret_structs() {
+        let spec = json!({
+            "openapi": "3.0.0",
+            "info": { "title": "Test API", "version": "1.0.0" },
+            "paths": {},
+            "components": {
+                "schemas": {
+                    "Login": {
+                        "type": "object",
+                        "properties": {
+                            "password": { "type": "string", "format": "password" }
+                        }
+                    }
+                }
+            }
+        });
+        let openapi: OpenAPI = serde_json::from_value(spec).expect("Failed to deserialize spec");
+        let (models, requests, responses) = parse_openapi(&openapi).expect("Failed to parse");
+        let options = GenOptions {
+            secrets: true,
+            derives: vec!["PartialEq".to_string()],
+            ..Default::default()
+        };
+
+        let error = generate_models_with(&models, &requests, &responses, &options)
+            .expect_err("PartialEq cannot be derived over a SecretString");
+        assert!(error
+            .to_string()
+            .contains("Cannot derive `PartialEq` for `Login`: its field `password`"));
+
+        // Without --secrets the password is a plain String
+        let options = GenOptions {
+            secrets: false,
+            ..options
+        };
+        assert!(generate_models_with(&models, &requests, &responses, &options).is_ok());
+    }
+
+    #[test]
+    fn test_non_exhaustive_enums() {
+        let spec = json!({
+            "openapi": "3.0.0",
+            "info": { "title": "Test API", "version": "1.0.0" },
+            "paths": {},
+            "components": {
+                "schemas": {
+                    "Status": { "type": "string", "enum": ["active", "inactive"] },
+                    "Priority": { "type": "integer", "enum": [1, 2, 3] }
+                }
+            }
+        });
+
+        let code = generate_from_spec(spec.clone(), &GenOptions::default());
+        assert!(!code.contains("#[non_exhaustive]"));
+
+        let options = GenOptions {
+            non_exhaustive: true,
+            ..Default::default()
+        };
+        let code = generate_from_spec(spec, &options);
+        assert!(code.contains("#[non_exhaustive]\npub enum Status {"));
+        assert!(code.contains("#[repr(i64)]\n#[non_exhaustive]\npub enum Priority {"));
+    }
+
+    #[test]
+    fn test_open_enums_get_a_catch_all_variant() {
+        let spec = json!({
+            "openapi": "3.0.0",
+            "info": { "title": "Test API", "version": "1.0.0" },
+            "paths": {},
+            "components": {
+                "schemas": {
+                    "Status": { "type": "string", "enum": ["active", "inactive"] },
+                    "Color": {
+                        "type": "string",
+                        "enum": ["red", "other"],
+                        "x-extensible-enum": true
+                    }
+                }
+            }
+        });
+
+        let code = generate_from_spec(spec.clone(), &GenOptions::default());
+        assert!(code.contains("    Active,\n    Inactive\n}"));
+        assert!(code.contains(
+            "    Red,\n    Other,\n    /// A value the specification does not list\n    \
+             #[serde(untagged)]\n    Unknown(String)\n}"
+        ));
+
+        let options = GenOptions {
+            open_enums: true,
+            enum_helpers: true,
+            ..Default::default()
+        };
+        let code = generate_from_spec(spec, &options);
+        assert!(code.contains(
+            "    Inactive,\n    /// A value the specification does not list\n    \
+             #[serde(untagged)]\n    Other(String)\n}"
+        ));
+        assert!(code.contains("            _ => Ok(Self::Other(s.to_string())),\n"));
+    }
+
+    #[test]
+    fn test_optional_fields_default_when_absent() {
+        let code = generate_from_spec(
+            json!({
+                "openapi": "3.0.0",
+                "info": { "title": "Test API", "version": "1.0.0" },
+                "paths": {},
+                "components": {
+                    "schemas": {
+                        "Settings": {
+                            "type": "object",
+                            "properties": {
+                                "theme": { "type": "string", "nullable": true, "default": "dark" },
+                                "balance": { "type": "string", "x-serde-with": "my_crate::bignum" },
+                                "note": { "type": "string" }
+                            }
+                        }
+                    }
+                }
+            }),
+            &GenOptions::default(),
+        );
+
+        assert!(code.contains(
+            "    #[serde(default = \"default_settings_theme\")]\n    pub theme: Option<String>,\n"
+        ));
+        assert!(code.contains(
+            "fn default_settings_theme() -> Option<String> {\n    Some(\"dark\".to_string())\n}"
+        ));
+        assert!(code.contains("            theme: default_settings_theme(),\n"));
+        assert!(code.contains(
+            "    #[serde(default, with = \"my_crate::bignum\")]\n    pub balance: Option<String>,\n"
+        ));
+        assert!(code.contains("    pub note: Option<String>,\n"));
+    }
+
+    #[test]
+    fn test_builders_for_structs_over_the_threshold() {
+        let options = GenOptions {
+            builder_threshold: Some(3),
+            ..Default::default()
+        };
+        let code = generate_from_spec(
+            json!({
+                "openapi": "3.0.0",
+                "info": { "title": "Test API", "version": "1.0.0" },
+                "paths": {},
+                "components": {
+                    "schemas": {
+                        "Point": {
+                            "type": "object",
+                            "properties": { "x": { "type": "number" }, "y": { "type": "number" } }
+                        },
+                        "User": {
+                            "type": "object",
+                            "required": ["name"],
+                            "properties": {
+                                "name": { "type": "string" },
+                                "retries": { "type": "integer", "default": 3 },
+                                "email": { "type": "string" }
+                            }
+                        }
+                    }
+                }
+            }),
+            &options,
+        );
+
+        assert!(
+            code.contains("#[derive(Debug, Clone, Serialize, Deserialize)]\npub struct Point {")
+        );
+        assert!(!code.contains("pub struct Point {\n    #[builder"));
+        assert!(code.contains(
+            "#[derive(Debug, Clone, Serialize, Deserialize, typed_builder::TypedBuilder)]\n\
+             pub struct User {"
+        ));
+        assert!(code.contains("    #[builder(setter(into))]\n    pub name: String,\n"));
+        assert!(code.contains(
+            "    #[builder(default = default_user_retries(), setter(into))]\n    pub retries: i64,\n"
+        ));
+        assert!(code.contains(
+            "    #[builder(default, setter(strip_option, into))]\n    pub email: Option<String>,\n"
+        ));
+    }
+
+    #[test]
+    fn test_constructors_take_required_fields() {
+        let options = GenOptions {
+            constructors: true,
+            ..Default::default()
+        };
+        let code = generate_from_spec(
+            json!({
+                "openapi": "3.0.0",
+                "info": { "title": "Test API", "version": "1.0.0" },
+                "paths": {},
+                "components": {
+                    "schemas": {
+                        "User": {
+                            "type": "object",
+                            "required": ["name", "type"],
+                            "properties": {
+                                "name": { "type": "string" },
+                                "type": { "type": "string" },
+                                "retries": { "type": "integer", "default": 3 },
+                                "email": { "type": "string" }
+                            }
+                        },
+                        "Filter": {
+                            "type": "object",
+                            "properties": { "query": { "type": "string" } }
+                        }
+                    }
+                }
+            }),
+            &options,
+        );
+
+        assert!(code.contains(
+            "impl User {\n    pub fn new(name: String, r#type: String) -> Self {\n        Self {\n            \
+             email: None,\n            name,\n            retries: default_user_retries(),\n            \
+             r#type,\n        }\n    }\n}\n"
+        ));
+        assert!(!code.contains("impl Filter {"));
+    }
+
+    #[test]
+    fn test_enum_helpers_emit_display_for_every_enum() {
+        let options = GenOptions {
+            enum_helpers: true,
+            ..Default::default()
+        };
+        let code = generate_from_spec(
+            json!({
+                "openapi": "3.0.0",
+                "info": { "title": "Test API", "version": "1.0.0" },
+                "paths": {},
+                "components": {
+                    "schemas": {
+                        "Status": { "type": "string", "enum": ["active", "on-hold"] },
+                        "Priority": { "type": "integer", "enum": [1, 2] },
+                        "Flag": { "enum": ["auto", true] }
+                    }
+                }
+            }),
+            &options,
+        );
+
+        assert!(code.contains(
+            "    pub fn as_str(&self) -> &'static str {\n        match self {\n            \
+             Self::Active => \"active\",\n            Self::OnHold => \"on-hold\",\n"
+        ));
+        assert!(code.contains("impl std::fmt::Display for Status {"));
+        assert!(code.contains("        f.write_str(self.as_str())\n"));
+        assert!(code.contains("impl std::fmt::Display for Priority {"));
+        assert!(code.contains("            Self::Value1 => \"1\",\n"));
+        assert!(code.contains("impl std::str::FromStr for Priority {"));
+        assert!(code.contains("        s.parse::<i64>()\n"));
+        assert!(code.contains("impl std::fmt::Display for Flag {"));
+        assert!(code.contains("impl std::str::FromStr for Flag {"));
+        assert!(code.contains("            \"true\" => Ok(Self::True),\n"));
+    }
+
+    #[test]
+    fn test_x_newtype_fields_get_a_transparent_newtype() {
+        let code = generate_from_spec(
+            json!({
+                "openapi": "3.0.0",
+                "info": { "title": "Test", "version": "1.0.0" },
+                "paths": {},
+                "components": {
+                    "schemas": {
+                        "Order": {
+                            "type": "object",
+                            "required": ["userId"],
+                            "properties": {
+                                "userId": { "type": "string", "format": "uuid", "x-newtype": true },
+                                "sku": { "type": "string", "x-newtype": "Sku" }
+                            }
+                        }
+                    }
+                }
+            }),
+            &GenOptions::default(),
+        );
+        assert!(code.contains("pub user_id: UserId,"));
+        assert!(code.contains("pub sku: Option<Sku>,"));
+        assert!(code.contains(
+            "#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]\n#[serde(transparent)]\npub struct UserId(pub Uuid);"
+        ));
+        assert!(code.contains("impl From<String> for Sku {"));
+        assert!(code.contains("impl std::fmt::Display for Sku {"));
+        assert!(code.contains("use uuid::Uuid;"));
+    }
+
+    #[test]
+    fn test_primitive_component_schemas_become_type_aliases() {
+        let code = generate_from_spec(
+            json!({
+                "openapi": "3.0.0",
+                "info": { "title": "Test", "version": "1.0.0" },
+                "paths": {},
+                "components": {
+                    "schemas": {
+                        "CorrelationId": {
+                            "type": "string",
+                            "format": "uuid",
+                            "description": "Request correlation"
+                        },
+                        "Count": { "type": "integer", "format": "int32" },
+                        "Flag": { "type": "boolean" },
+                        "Event": {
+                            "type": "object",
+                            "properties": {
+                                "correlation": { "$ref": "#/components/schemas/CorrelationId" }
+                            }
+                        }
+                    }
+                }
+            }),
+            &GenOptions::default(),
+        );
+        assert!(code.contains("/// Request correlation\npub type CorrelationId = Uuid;\n"));
+        assert!(code.contains("pub type Count = i32;\n"));
+        assert!(code.contains("pub type Flag = bool;\n"));
+        assert!(code.contains("pub correlation: Option<CorrelationId>,"));
+        assert!(code.contains("use uuid::Uuid;"));
+    }
 }
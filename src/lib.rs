@@ -3,9 +3,10 @@ pub mod error;
 pub mod generator;
 pub mod models;
 pub mod parser;
+mod swagger2;
 
 pub use error::Error;
-pub use generator::generate_models;
+pub use generator::{generate_models, generate_models_with, GenOptions};
 pub use parser::parse_openapi;
 
 pub type Result<T> = std::result::Result<T, Error>;
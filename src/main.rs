@@ -1,6 +1,9 @@
 use clap::Parser;
-use openapi_model_generator::{cli::Args, generator, parser, Error, Result};
-use openapiv3::OpenAPI;
+use openapi_model_generator::{
+    cli::{Args, GroupBy},
+    generator::{self, GenOptions},
+    parser, Error, Result,
+};
 use std::fs;
 use std::io;
 use std::path::PathBuf;
@@ -53,6 +56,15 @@ pub fn create_output_dir(path: &PathBuf) -> Result<()> {
 fn main() -> Result<()> {
     let args = Args::parse();
 
+    tracing_subscriber::fmt()
+        .with_max_level(if args.verbose {
+            tracing::Level::INFO
+        } else {
+            tracing::Level::WARN
+        })
+        .with_writer(io::stderr)
+        .init();
+
     if let Err(e) = validate_input_file(&args.input) {
         eprintln!("Failed to validate input file: {e}");
         std::process::exit(1);
@@ -65,19 +77,95 @@ fn main() -> Result<()> {
 
     let content = fs::read_to_string(&args.input)?;
 
-    let openapi: OpenAPI = if args.input.extension().is_some_and(|ext| ext == "yaml") {
-        serde_yaml::from_str(&content)?
+    let is_yaml = args
+        .input
+        .extension()
+        .is_some_and(|ext| ext == "yaml" || ext == "yml");
+    let openapi = parser::load_openapi(&content, is_yaml)?;
+
+    let base_dir = args
+        .input
+        .parent()
+        .unwrap_or_else(|| std::path::Path::new("."));
+    let openapi = parser::resolve_external_refs(&openapi, base_dir)?;
+    // Taken before --only narrows the spec, and covering the external files it references
+    let fingerprint = generator::spec_fingerprint(&openapi)?;
+
+    if args.strict {
+        for warning in parser::collect_warnings(&openapi) {
+            eprintln!("Warning: {warning}");
+        }
+    }
+
+    let openapi = if args.only.is_empty() {
+        openapi
     } else {
-        serde_json::from_str(&content)?
+        parser::select_schemas(&openapi, &args.only)?
+    };
+
+    let (mut models, requests, responses) = parser::parse_openapi(&openapi)?;
+    if args.id_newtypes {
+        parser::wrap_identifiers(&mut models, true);
+    }
+
+    let options = GenOptions {
+        spec_fingerprint: Some(fingerprint),
+        ..GenOptions::from(&args)
     };
 
-    let (models, requests, responses) = parser::parse_openapi(&openapi)?;
+    let mut modules = Vec::new();
+    let output_models_path = match args.group_by {
+        Some(GroupBy::Tag) => {
+            let operation_tags = parser::operation_tags(&openapi)?;
+            let tag_modules = generator::generate_tag_modules(
+                &models,
+                &requests,
+                &responses,
+                &operation_tags,
+                &options,
+            )?;
+            for (module, code) in tag_modules {
+                fs::write(args.output.join(format!("{module}.rs")), code.trim())?;
+                modules.push(module);
+            }
+            args.output.clone()
+        }
+        None => {
+            let rust_code =
+                generator::generate_models_with(&models, &requests, &responses, &options)?;
+            let output_models_path = args.output.join("models.rs");
+            fs::write(&output_models_path, rust_code.trim())?;
+            modules.push("models".to_string());
+            output_models_path
+        }
+    };
 
-    let rust_code = generator::generate_models(&models, &requests, &responses)?;
-    let output_models_path = args.output.join("models.rs");
-    fs::write(&output_models_path, rust_code.trim())?;
+    let model_modules = modules.clone();
+
+    let security_schemes = parser::parse_security_schemes(&openapi);
+    if !security_schemes.is_empty() {
+        let security_code = generator::generate_security(&security_schemes)?;
+        fs::write(args.output.join("security.rs"), security_code.trim())?;
+        modules.push("security".to_string());
+    }
+
+    let servers = parser::parse_servers(&openapi);
+    if !servers.is_empty() {
+        let servers_code = generator::generate_servers(&servers)?;
+        fs::write(args.output.join("servers.rs"), servers_code.trim())?;
+        modules.push("servers".to_string());
+    }
+
+    if args.fixtures {
+        let examples = parser::parse_examples(&openapi, &models, &requests, &responses);
+        let model_modules: Vec<&str> = model_modules.iter().map(String::as_str).collect();
+        let fixtures_code = generator::generate_fixtures(&examples, &model_modules)?;
+        fs::write(args.output.join("fixtures.rs"), fixtures_code.trim())?;
+        modules.push("fixtures".to_string());
+    }
 
-    let rust_lib = generator::generate_lib()?;
+    let modules: Vec<&str> = modules.iter().map(String::as_str).collect();
+    let rust_lib = generator::generate_lib_with(&modules)?;
     let output_lib_path = args.output.join("mod.rs");
     fs::write(&output_lib_path, rust_lib.trim())?;
 
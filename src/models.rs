@@ -7,6 +7,8 @@ pub enum ModelType {
     Composition(CompositionModel), // allOf
     Enum(EnumModel),               // enum values -> enum
     TypeAlias(TypeAliasModel),     // x-rust-type -> type alias
+    Params(ParamsModel),           // operation parameters -> struct
+    Newtype(NewtypeModel),         // identifier fields -> tuple struct
 }
 
 impl ModelType {
@@ -17,6 +19,8 @@ impl ModelType {
             ModelType::Union(u) => &u.name,
             ModelType::Composition(c) => &c.name,
             ModelType::TypeAlias(t) => &t.name,
+            ModelType::Params(p) => &p.name,
+            ModelType::Newtype(n) => &n.name,
         }
     }
 }
@@ -26,7 +30,13 @@ pub struct Model {
     pub name: String,
     pub fields: Vec<Field>,
     pub custom_attrs: Option<Vec<String>>,
+    /// Derives from the x-rust-derives extension, replacing the configured extra derives
+    pub derives: Option<Vec<String>>,
     pub description: Option<String>,
+    /// XML root element name: the schema's `xml.name`, or the component name
+    pub xml_name: Option<String>,
+    /// The schema sets `additionalProperties: false`
+    pub deny_unknown_fields: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -38,12 +48,76 @@ pub struct Field {
     pub is_nullable: bool,
     pub is_array_ref: bool,
     pub description: Option<String>,
+    /// Custom serde module from the x-serde-with extension
+    pub serde_with: Option<String>,
+    pub read_only: bool,
+    pub write_only: bool,
+    /// Schema `default` value
+    pub default: Option<serde_json::Value>,
+    pub constraints: Constraints,
+    /// Marked `deprecated: true` in the spec
+    pub deprecated: bool,
+    /// Paths from the x-rust-use extension, emitted as `use` lines
+    pub rust_uses: Vec<String>,
+    /// Embeds the field's type with `#[serde(flatten)]`, e.g. a union taking part in an allOf
+    pub flatten: bool,
+    /// The property's `xml` object, if it has one or its array items are named
+    pub xml: Option<XmlOptions>,
+    /// Generated as `Option<Option<T>>` whatever the options, as in merge-patch structs
+    pub double_option: bool,
+    /// Newtype named by the x-newtype extension to wrap the field's type in
+    pub newtype: Option<String>,
+    /// Key on the wire when `name` was changed to keep the Rust field unique, as for a
+    /// header parameter sharing a query parameter's name
+    pub rename: Option<String>,
+}
+
+/// The `xml` object of a property schema
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct XmlOptions {
+    pub name: Option<String>,
+    pub prefix: Option<String>,
+    pub attribute: bool,
+    pub wrapped: bool,
+    /// `xml.name` of the array items, which names the repeated elements
+    #[serde(skip)]
+    pub items_name: Option<String>,
+}
+
+/// Validation keywords captured from a property schema
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Constraints {
+    pub min_length: Option<usize>,
+    pub max_length: Option<usize>,
+    pub minimum: Option<serde_json::Number>,
+    pub maximum: Option<serde_json::Number>,
+    /// `exclusiveMinimum: true`: the value must be greater than `minimum`
+    pub exclusive_minimum: bool,
+    /// `exclusiveMaximum: true`: the value must be less than `maximum`
+    pub exclusive_maximum: bool,
+    pub pattern: Option<String>,
+    pub min_items: Option<usize>,
+    pub max_items: Option<usize>,
+    pub unique_items: bool,
+}
+
+impl Constraints {
+    pub fn is_empty(&self) -> bool {
+        *self == Constraints::default()
+    }
 }
 
 impl Field {
-    /// Returns true if this field should be flattened (for additionalProperties)
+    /// Name of the field on the wire
+    pub fn wire_name(&self) -> &str {
+        self.rename.as_deref().unwrap_or(&self.name)
+    }
+
+    /// Returns true if this field should be flattened (for additionalProperties
+    /// and embedded types)
     pub fn should_flatten(&self) -> bool {
-        self.name == "additional_properties"
+        self.flatten || self.name == "additional_properties"
     }
 }
 
@@ -53,6 +127,9 @@ pub struct UnionModel {
     pub variants: Vec<UnionVariant>,
     pub union_type: UnionType,
     pub custom_attrs: Option<Vec<String>>,
+    /// Derives from the x-rust-derives extension, replacing the configured extra derives
+    pub derives: Option<Vec<String>>,
+    pub description: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -72,24 +149,155 @@ pub struct UnionVariant {
 pub struct CompositionModel {
     pub name: String,
     pub all_fields: Vec<Field>,
+    /// Fields keeping the members' structure: referenced members as `#[serde(flatten)]`
+    /// fields, inline members as their own properties
+    pub embedded_fields: Vec<Field>,
+    /// Component schemas referenced as allOf members, including from inline allOf members
+    pub bases: Vec<String>,
+    /// XML root element name: the schema's `xml.name`, or the component name
+    pub xml_name: Option<String>,
     pub custom_attrs: Option<Vec<String>>,
+    /// Derives from the x-rust-derives extension, replacing the configured extra derives
+    pub derives: Option<Vec<String>>,
+    pub description: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RequestModel {
     pub name: String,
+    /// HTTP method of the operation, in upper case
+    pub method: String,
     pub content_type: String,
     pub schema: String,
     pub is_required: bool,
+    /// The operation is marked `deprecated: true`
+    pub deprecated: bool,
+    /// Example payloads of the media type, keyed by name (`example` for the single `example`)
+    pub examples: Vec<(String, serde_json::Value)>,
+}
+
+/// Path, query, header and cookie parameters of a single operation
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ParamsModel {
+    pub name: String,
+    pub fields: Vec<ParamField>,
+    /// The operation is marked `deprecated: true`
+    pub deprecated: bool,
+    /// Path template of the operation, such as `/users/{userId}`; `None` for webhooks
+    /// and callbacks
+    pub path: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ParamField {
+    pub field: Field,
+    pub location: ParameterLocation,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ParameterLocation {
+    Path,
+    Query,
+    Header,
+    Cookie,
+}
+
+impl ParameterLocation {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ParameterLocation::Path => "path",
+            ParameterLocation::Query => "query",
+            ParameterLocation::Header => "header",
+            ParameterLocation::Cookie => "cookie",
+        }
+    }
+}
+
+/// An entry of the top-level `servers` array
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ServerModel {
+    /// PascalCase name derived from the description, or `Server{n}` without one
+    pub name: String,
+    pub url: String,
+    pub description: Option<String>,
+    pub variables: Vec<ServerVariableModel>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ServerVariableModel {
+    pub name: String,
+    pub default: String,
+    pub enumeration: Vec<String>,
+    pub description: Option<String>,
+}
+
+/// A scheme from `components/securitySchemes`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SecuritySchemeModel {
+    pub name: String,
+    pub description: Option<String>,
+    pub kind: SecuritySchemeKind,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum SecuritySchemeKind {
+    ApiKey {
+        parameter_name: String,
+        location: ParameterLocation,
+    },
+    Http {
+        scheme: String,
+        bearer_format: Option<String>,
+    },
+    OAuth2 {
+        flows: Vec<OAuth2FlowModel>,
+    },
+    OpenIdConnect {
+        url: String,
+    },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OAuth2FlowModel {
+    pub kind: OAuth2FlowKind,
+    pub authorization_url: Option<String>,
+    pub token_url: Option<String>,
+    pub refresh_url: Option<String>,
+    /// Scope names with their descriptions
+    pub scopes: Vec<(String, String)>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum OAuth2FlowKind {
+    Implicit,
+    Password,
+    ClientCredentials,
+    AuthorizationCode,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ResponseModel {
     pub name: String,
+    /// HTTP method of the operation, in upper case
+    pub method: String,
     pub status_code: String,
+    /// Leading digit of a range status such as `2XX`, which matches any code in `200..=299`
+    pub status_range: Option<u16>,
     pub content_type: String,
     pub schema: String,
     pub description: Option<String>,
+    /// The operation is marked `deprecated: true`
+    pub deprecated: bool,
+    /// Example payloads of the media type, keyed by name (`example` for the single `example`)
+    pub examples: Vec<(String, serde_json::Value)>,
+}
+
+/// A JSON example from the spec and the generated type it should deserialize into
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExampleModel {
+    pub name: String,
+    pub type_name: String,
+    pub value: serde_json::Value,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -98,6 +306,22 @@ pub struct EnumModel {
     pub variants: Vec<String>,
     pub description: Option<String>,
     pub custom_attrs: Option<Vec<String>>,
+    /// Derives from the x-rust-derives extension, replacing the configured extra derives
+    pub derives: Option<Vec<String>>,
+    /// The schema sets `x-extensible-enum`: values outside `variants` are accepted
+    pub extensible: bool,
+    /// Wire values of an integer-coded enum, parallel to `variants`; empty for string enums
+    pub integer_values: Vec<i64>,
+    /// Wire values of an enum mixing strings, numbers, booleans or null, parallel to `variants`
+    pub mixed_values: Vec<serde_json::Value>,
+}
+
+/// A distinct identifier type (`UserId(Uuid)`) wrapping a field's primitive type
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NewtypeModel {
+    pub name: String,
+    pub inner_type: String,
+    pub description: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -106,4 +330,6 @@ pub struct TypeAliasModel {
     pub target_type: String,
     pub description: Option<String>,
     pub custom_attrs: Option<Vec<String>>,
+    /// Paths from the x-rust-use extension, emitted as `use` lines
+    pub rust_uses: Vec<String>,
 }
@@ -1,19 +1,53 @@
 use crate::{
+    error::Error,
     models::{
-        CompositionModel, EnumModel, Field, Model, ModelType, RequestModel, ResponseModel,
-        TypeAliasModel, UnionModel, UnionType, UnionVariant,
+        CompositionModel, Constraints, EnumModel, ExampleModel, Field, Model, ModelType,
+        NewtypeModel, OAuth2FlowKind, OAuth2FlowModel, ParamField, ParameterLocation, ParamsModel,
+        RequestModel, ResponseModel, SecuritySchemeKind, SecuritySchemeModel, ServerModel,
+        ServerVariableModel, TypeAliasModel, UnionModel, UnionType, UnionVariant, XmlOptions,
     },
-    Result,
+    swagger2, Result,
 };
 use indexmap::IndexMap;
 use openapiv3::{
-    AdditionalProperties, OpenAPI, ReferenceOr, Schema, SchemaKind, StringFormat, Type,
-    VariantOrUnknownOrEmpty,
+    AdditionalProperties, Components, IntegerFormat, IntegerType, NumberFormat, NumberType,
+    OpenAPI, Parameter, ParameterSchemaOrContent, PathItem, ReferenceOr, Schema, SchemaKind,
+    StatusCode, StringFormat, Type, VariantOrUnknownOrEmpty,
+};
+use std::{
+    collections::{BTreeSet, HashMap, HashSet, VecDeque},
+    fs,
+    path::{Path, PathBuf},
 };
-use std::collections::HashSet;
 
 const X_RUST_TYPE: &str = "x-rust-type";
+const X_RUST_USE: &str = "x-rust-use";
+/// Attribute emitted for schemas, properties and operations marked `deprecated: true`
+pub const DEPRECATED_ATTR: &str = "#[deprecated(note = \"deprecated in the API specification\")]";
 const X_RUST_ATTRS: &str = "x-rust-attrs";
+const X_RUST_DERIVES: &str = "x-rust-derives";
+/// Marks an enum as open to values added later; a list of values stands in for `enum`
+const X_EXTENSIBLE_ENUM: &str = "x-extensible-enum";
+const X_SERDE_WITH: &str = "x-serde-with";
+/// Wraps an identifier property in a newtype: `true` derives its name, a string names it
+const X_NEWTYPE: &str = "x-newtype";
+/// Marks a string property as a credential, like `format: password`
+const X_SECRET: &str = "x-secret";
+/// Set on load to the key patterns of a `patternProperties` folded into `additionalProperties`
+const X_PATTERN_PROPERTIES: &str = "x-pattern-properties";
+/// Set on load to a schema's `xml` object, which openapiv3 does not keep
+const X_XML: &str = "x-xml";
+/// Number of decimal digits an integer can have; above 18 it is read as `format: bigint`
+const X_PRECISION: &str = "x-precision";
+/// Set on load to the branches (`then`, `else`) of an `if` folded into a schema's properties
+const X_CONDITIONAL: &str = "x-conditional";
+/// Set on load to a schema's `prefixItems`, the element schemas of a positional tuple
+const X_PREFIX_ITEMS: &str = "x-prefix-items";
+/// Set on load to the 3.1 `webhooks` map, which has no field in the 3.0 document model
+const X_WEBHOOKS: &str = "x-webhooks";
+/// Set on load to the original key of a component schema renamed because its Rust name
+/// collided with another schema's, paired with that other schema's key
+const X_RENAMED_FROM: &str = "x-renamed-from";
 
 /// Information about a field extracted from OpenAPI schema
 #[derive(Debug)]
@@ -23,6 +57,15 @@ struct FieldInfo {
     is_nullable: bool,
     is_array_ref: bool,
     description: Option<String>,
+    serde_with: Option<String>,
+    read_only: bool,
+    write_only: bool,
+    default: Option<serde_json::Value>,
+    constraints: Constraints,
+    deprecated: bool,
+    rust_uses: Vec<String>,
+    xml: Option<XmlOptions>,
+    newtype: Option<String>,
 }
 
 /// Converts camelCase to PascalCase
@@ -41,31 +84,97 @@ pub(crate) fn to_pascal_case(input: &str) -> String {
         .collect::<String>()
 }
 
+/// Returns true when progress should be reported: roughly every 10% of `total`
+/// and once more on completion
+fn should_report_progress(done: usize, total: usize) -> bool {
+    let step = (total / 10).max(1);
+    done == total || done % step == 0
+}
+
+/// Emits a throttled `info` progress event such as "Parsing schemas: 40/120"
+pub(crate) fn report_progress(stage: &str, done: usize, total: usize) {
+    if should_report_progress(done, total) {
+        tracing::info!("{}: {}/{}", stage, done, total);
+    }
+}
+
 /// Extracts custom Rust attributes from x-rust-attrs extension
 fn extract_custom_attrs(schema: &Schema) -> Option<Vec<String>> {
-    schema
-        .schema_data
-        .extensions
-        .get(X_RUST_ATTRS)
-        .and_then(|value| {
+    let mut attrs: Vec<String> = match schema.schema_data.extensions.get(X_RUST_ATTRS) {
+        Some(value) => {
             if let Some(arr) = value.as_array() {
-                let attrs: Vec<String> = arr
-                    .iter()
+                arr.iter()
                     .filter_map(|v| v.as_str().map(|s| s.to_string()))
-                    .collect();
-                if attrs.is_empty() {
-                    None
-                } else {
-                    Some(attrs)
-                }
+                    .collect()
             } else {
                 tracing::warn!(
                     "x-rust-attrs should be an array of strings, got: {:?}",
                     value
                 );
-                None
+                Vec::new()
             }
-        })
+        }
+        None => Vec::new(),
+    };
+
+    if schema.schema_data.deprecated {
+        attrs.push(DEPRECATED_ATTR.to_string());
+    }
+
+    if attrs.is_empty() {
+        None
+    } else {
+        Some(attrs)
+    }
+}
+
+/// Reads the `x-rust-derives` extension: the derives to add to this type instead of the
+/// ones configured with `--derives`
+fn extract_derives(schema: &Schema) -> Option<Vec<String>> {
+    match schema.schema_data.extensions.get(X_RUST_DERIVES)? {
+        serde_json::Value::Array(derives) => Some(
+            derives
+                .iter()
+                .filter_map(|v| v.as_str().map(|s| s.trim().to_string()))
+                .collect(),
+        ),
+        value => {
+            tracing::warn!(
+                "x-rust-derives should be an array of strings, got: {:?}",
+                value
+            );
+            None
+        }
+    }
+}
+
+/// Whether a schema marks its `enum` as open with `x-extensible-enum`, either as `true`
+/// or as the list of currently known values
+fn is_extensible_enum(schema: &Schema) -> bool {
+    match schema.schema_data.extensions.get(X_EXTENSIBLE_ENUM) {
+        Some(serde_json::Value::Bool(extensible)) => *extensible,
+        Some(serde_json::Value::Array(_)) => true,
+        _ => false,
+    }
+}
+
+/// Reads the `x-rust-use` extension: a path or list of paths to import for `x-rust-type`
+fn extract_rust_uses(schema: &Schema) -> Vec<String> {
+    match schema.schema_data.extensions.get(X_RUST_USE) {
+        Some(serde_json::Value::String(path)) => vec![path.clone()],
+        Some(serde_json::Value::Array(paths)) => paths
+            .iter()
+            .filter_map(|v| v.as_str().map(|s| s.to_string()))
+            .collect(),
+        Some(value) => {
+            tracing::warn!(
+                "x-rust-use should be a string or an array of strings, got: {:?}",
+                value
+            );
+            Vec::new()
+        }
+        None => Vec::new(),
+    }
 }
 
 pub fn parse_openapi(
@@ -77,25 +186,57 @@ pub fn parse_openapi(
 
     let mut added_models = HashSet::new();
 
-    let empty_schemas = IndexMap::new();
-    let empty_request_bodies = IndexMap::new();
-
-    let (schemas, request_bodies) = if let Some(components) = &openapi.components {
-        (&components.schemas, &components.request_bodies)
-    } else {
-        (&empty_schemas, &empty_request_bodies)
-    };
+    let empty_components = Components::default();
+    let all_components = openapi.components.as_ref().unwrap_or(&empty_components);
 
     // Parse components/schemas
     if let Some(components) = &openapi.components {
-        for (name, schema) in &components.schemas {
-            let model_types = parse_schema_to_model_type(name, schema, &components.schemas)?;
+        let total = components.schemas.len();
+        for (index, (name, schema)) in components.schemas.iter().enumerate() {
+            report_progress("Parsing schemas", index + 1, total);
+            let mut model_types = parse_schema_to_model_type(name, schema, &components.schemas)?;
+            if model_types.is_empty() {
+                model_types.extend(primitive_alias(name, schema, &components.schemas)?);
+            }
             for model_type in model_types {
-                if added_models.insert(model_type.name().to_string()) {
-                    models.push(model_type);
+                add_model(&mut models, &mut added_models, model_type);
+            }
+        }
+
+        // Schemas that are only a `$ref` alias their target, once the target has a model.
+        // Aliases of aliases resolve over several passes
+        let mut pending_aliases: Vec<(String, String)> = components
+            .schemas
+            .iter()
+            .filter(|(_, schema)| matches!(schema, ReferenceOr::Reference { .. }))
+            .map(|(name, schema)| {
+                let target = extract_type_and_format(schema, &components.schemas)?.0;
+                Ok((to_pascal_case(name), target))
+            })
+            .collect::<Result<_>>()?;
+        loop {
+            let (ready, rest): (Vec<_>, Vec<_>) = pending_aliases
+                .into_iter()
+                .partition(|(_, target)| added_models.contains(target));
+            pending_aliases = rest;
+            if ready.is_empty() {
+                break;
+            }
+            for (name, target) in ready {
+                if added_models.insert(name.clone()) {
+                    models.push(ModelType::TypeAlias(TypeAliasModel {
+                        name,
+                        target_type: target,
+                        description: None,
+                        custom_attrs: None,
+                        rust_uses: Vec::new(),
+                    }));
                 }
             }
         }
+        for (name, target) in pending_aliases {
+            tracing::warn!("Schema '{name}' references '{target}', which has no model; skipping");
+        }
 
         // Parse components/requestBodies - extract schemas and create models
         for (name, request_body_ref) in &components.request_bodies {
@@ -105,9 +246,7 @@ pub fn parse_openapi(
                         let model_types =
                             parse_schema_to_model_type(name, schema, &components.schemas)?;
                         for model_type in model_types {
-                            if added_models.insert(model_type.name().to_string()) {
-                                models.push(model_type);
-                            }
+                            add_model(&mut models, &mut added_models, model_type);
                         }
                     }
                 }
@@ -115,1522 +254,6645 @@ pub fn parse_openapi(
         }
     }
 
-    // Parse paths
-    for (path, path_item) in openapi.paths.iter() {
+    // Parse paths, then webhooks, which describe operations the same way
+    for_each_operation(openapi, |method, op, parameters, path, backup_name| {
+        let mut inline_models = process_operation(
+            op,
+            method,
+            parameters,
+            &mut requests,
+            &mut responses,
+            all_components,
+            backup_name,
+        )?;
+        if let Some(path) = path {
+            let params_name = format!("{}Params", operation_name(op, backup_name));
+            for model_type in &mut inline_models {
+                if let ModelType::Params(params) = model_type {
+                    if params.name == params_name {
+                        params.path = Some(path.to_string());
+                    }
+                }
+            }
+        }
+        for model_type in inline_models {
+            add_model(&mut models, &mut added_models, model_type);
+        }
+        Ok(())
+    })?;
+
+    let component_names: HashSet<String> = all_components
+        .schemas
+        .keys()
+        .map(|name| to_pascal_case(name))
+        .collect();
+    let renames = share_identical_enums(&mut models, &component_names);
+    for request in &mut requests {
+        rename_type_idents(&mut request.schema, &renames);
+    }
+    for response in &mut responses {
+        rename_type_idents(&mut response.schema, &renames);
+    }
+
+    box_recursive_references(&mut models);
+    wrap_identifiers(&mut models, false);
+
+    Ok((models, requests, responses))
+}
+
+/// Calls `f` for every path and webhook operation with its HTTP method, merged parameters,
+/// path template (`None` for webhooks) and the name used when it has no operationId
+fn for_each_operation(
+    openapi: &OpenAPI,
+    mut f: impl FnMut(&str, &openapiv3::Operation, &[&Parameter], Option<&str>, &str) -> Result<()>,
+) -> Result<()> {
+    let empty_components = Components::default();
+    let parameters = &openapi
+        .components
+        .as_ref()
+        .unwrap_or(&empty_components)
+        .parameters;
+    let webhooks: IndexMap<String, ReferenceOr<PathItem>> = match openapi.extensions.get(X_WEBHOOKS)
+    {
+        Some(value) => serde_json::from_value(value.clone())?,
+        None => IndexMap::new(),
+    };
+
+    let paths = openapi.paths.iter().map(|(path, item)| (path, item, true));
+    let webhooks = webhooks.iter().map(|(name, item)| (name, item, false));
+    for (path, path_item, is_path) in paths.chain(webhooks) {
         let path_item = match path_item {
             ReferenceOr::Item(item) => item,
             ReferenceOr::Reference { .. } => continue,
         };
 
-        let operations = [
-            ("GET", &path_item.get),
-            ("POST", &path_item.post),
-            ("PUT", &path_item.put),
-            ("DELETE", &path_item.delete),
-            ("PATCH", &path_item.patch),
-        ];
-
-        for (method, op) in operations
-            .iter()
-            .filter_map(|(m, o)| o.as_ref().map(|operation| (*m, operation)))
-        {
+        for (method, op) in path_item_operations(path_item) {
             let backup_name = format!(
                 "{}{}",
                 method,
                 to_pascal_case(&path.replace(['/', '{'], "-").replace('}', ""))
             );
-            let inline_models = process_operation(
-                op,
-                &mut requests,
-                &mut responses,
-                schemas,
-                request_bodies,
-                &backup_name,
-            )?;
-            for model_type in inline_models {
-                if added_models.insert(model_type.name().to_string()) {
-                    models.push(model_type);
-                }
-            }
+            let operation_parameters = merge_parameters(
+                resolve_parameters(&path_item.parameters, parameters),
+                resolve_parameters(&op.parameters, parameters),
+            );
+            let template = is_path.then_some(path.as_str());
+            f(method, op, &operation_parameters, template, &backup_name)?;
         }
     }
+    Ok(())
+}
 
-    Ok((models, requests, responses))
+/// Maps the name of every model, request and response generated for a tagged operation
+/// to the operation's tags. Schemas those reference are not followed here.
+pub fn operation_tags(openapi: &OpenAPI) -> Result<HashMap<String, BTreeSet<String>>> {
+    let empty_components = Components::default();
+    let all_components = openapi.components.as_ref().unwrap_or(&empty_components);
+    let mut tags: HashMap<String, BTreeSet<String>> = HashMap::new();
+
+    for_each_operation(openapi, |method, op, parameters, _path, backup_name| {
+        if op.tags.is_empty() {
+            return Ok(());
+        }
+        let mut requests = Vec::new();
+        let mut responses = Vec::new();
+        let inline_models = process_operation(
+            op,
+            method,
+            parameters,
+            &mut requests,
+            &mut responses,
+            all_components,
+            backup_name,
+        )?;
+
+        let names = inline_models
+            .iter()
+            .map(|model| model.name().to_string())
+            .chain(requests.into_iter().map(|request| request.name))
+            .chain(responses.into_iter().map(|response| response.name));
+        for name in names {
+            tags.entry(name)
+                .or_default()
+                .extend(op.tags.iter().cloned());
+        }
+        Ok(())
+    })?;
+
+    Ok(tags)
 }
 
-fn process_operation(
-    operation: &openapiv3::Operation,
-    requests: &mut Vec<RequestModel>,
-    responses: &mut Vec<ResponseModel>,
-    all_schemas: &IndexMap<String, ReferenceOr<Schema>>,
-    request_bodies: &IndexMap<String, ReferenceOr<openapiv3::RequestBody>>,
-    backup_name: &str,
-) -> Result<Vec<ModelType>> {
-    let mut inline_models = Vec::new();
-    let operation_name = to_pascal_case(operation.operation_id.as_deref().unwrap_or(backup_name));
+/// Parses the top-level `servers` array, naming each server after its description
+pub fn parse_servers(openapi: &OpenAPI) -> Vec<ServerModel> {
+    let mut used_names = HashSet::new();
+
+    openapi
+        .servers
+        .iter()
+        .enumerate()
+        .map(|(index, server)| {
+            let described = server
+                .description
+                .as_deref()
+                .map(|description| {
+                    description
+                        .split(|c: char| !c.is_ascii_alphanumeric())
+                        .map(|word| to_pascal_case(&word.to_ascii_lowercase()))
+                        .collect::<String>()
+                })
+                .filter(|name| name.starts_with(|c: char| c.is_ascii_alphabetic()));
+            let name = match described {
+                Some(name) if !used_names.contains(&name) => name,
+                _ => format!("Server{}", index + 1),
+            };
+            used_names.insert(name.clone());
+
+            ServerModel {
+                name,
+                url: server.url.clone(),
+                description: server.description.clone(),
+                variables: server
+                    .variables
+                    .iter()
+                    .flatten()
+                    .map(|(name, variable)| ServerVariableModel {
+                        name: name.clone(),
+                        default: variable.default.clone(),
+                        enumeration: variable.enumeration.clone(),
+                        description: variable.description.clone(),
+                    })
+                    .collect(),
+            }
+        })
+        .collect()
+}
 
-    // Parse request body
-    if let Some(request_body_ref) = &operation.request_body {
-        let (request_body_data, is_inline) = match request_body_ref {
-            ReferenceOr::Item(request_body) => (Some((request_body, request_body.required)), true),
+/// Parses `components/securitySchemes` in declaration order. References to other
+/// schemes are skipped.
+pub fn parse_security_schemes(openapi: &OpenAPI) -> Vec<SecuritySchemeModel> {
+    let Some(components) = &openapi.components else {
+        return Vec::new();
+    };
+
+    components
+        .security_schemes
+        .iter()
+        .filter_map(|(name, scheme)| match scheme {
+            ReferenceOr::Item(scheme) => Some((name, scheme)),
             ReferenceOr::Reference { reference } => {
-                if let Some(rb_name) = reference.strip_prefix("#/components/requestBodies/") {
-                    (
-                        request_bodies.get(rb_name).and_then(|rb_ref| match rb_ref {
-                            ReferenceOr::Item(rb) => Some((rb, false)),
-                            ReferenceOr::Reference { .. } => None,
-                        }),
-                        false,
-                    )
-                } else {
-                    (None, false)
-                }
+                tracing::warn!("Skipping security scheme reference {}", reference);
+                None
             }
-        };
+        })
+        .map(|(name, scheme)| {
+            let (description, kind) = match scheme {
+                openapiv3::SecurityScheme::APIKey {
+                    location,
+                    name,
+                    description,
+                    ..
+                } => (
+                    description,
+                    SecuritySchemeKind::ApiKey {
+                        parameter_name: name.clone(),
+                        location: match location {
+                            openapiv3::APIKeyLocation::Query => ParameterLocation::Query,
+                            openapiv3::APIKeyLocation::Header => ParameterLocation::Header,
+                            openapiv3::APIKeyLocation::Cookie => ParameterLocation::Cookie,
+                        },
+                    },
+                ),
+                openapiv3::SecurityScheme::HTTP {
+                    scheme,
+                    bearer_format,
+                    description,
+                    ..
+                } => (
+                    description,
+                    SecuritySchemeKind::Http {
+                        scheme: scheme.to_lowercase(),
+                        bearer_format: bearer_format.clone(),
+                    },
+                ),
+                openapiv3::SecurityScheme::OAuth2 {
+                    flows, description, ..
+                } => (
+                    description,
+                    SecuritySchemeKind::OAuth2 {
+                        flows: oauth2_flows(flows),
+                    },
+                ),
+                openapiv3::SecurityScheme::OpenIDConnect {
+                    open_id_connect_url,
+                    description,
+                    ..
+                } => (
+                    description,
+                    SecuritySchemeKind::OpenIdConnect {
+                        url: open_id_connect_url.clone(),
+                    },
+                ),
+            };
+            SecuritySchemeModel {
+                name: name.clone(),
+                description: description.clone(),
+                kind,
+            }
+        })
+        .collect()
+}
 
-        if let Some((request_body, is_required)) = request_body_data {
-            for (content_type, media_type) in &request_body.content {
-                if let Some(schema) = &media_type.schema {
-                    let schema_type = if is_inline {
-                        if let ReferenceOr::Item(schema_item) = schema {
-                            if matches!(schema_item.schema_kind, SchemaKind::Type(Type::Object(_)))
-                            {
-                                let model_name = format!("{operation_name}RequestBody");
-                                let model_types =
-                                    parse_schema_to_model_type(&model_name, schema, all_schemas)?;
-                                inline_models.extend(model_types);
-                                model_name
-                            } else {
-                                extract_type_and_format(schema, all_schemas)?.0
-                            }
-                        } else {
-                            extract_type_and_format(schema, all_schemas)?.0
-                        }
-                    } else {
-                        extract_type_and_format(schema, all_schemas)?.0
-                    };
+/// Flattens the OAuth2 flows object in the order the specification lists the flows
+fn oauth2_flows(flows: &openapiv3::OAuth2Flows) -> Vec<OAuth2FlowModel> {
+    fn scopes(scopes: &IndexMap<String, String>) -> Vec<(String, String)> {
+        scopes
+            .iter()
+            .map(|(name, description)| (name.clone(), description.clone()))
+            .collect()
+    }
 
-                    let request = RequestModel {
-                        name: format!("{operation_name}Request"),
-                        content_type: content_type.clone(),
-                        schema: schema_type,
-                        is_required,
-                    };
-                    requests.push(request);
-                }
-            }
+    let mut models = Vec::new();
+    if let Some(flow) = &flows.implicit {
+        models.push(OAuth2FlowModel {
+            kind: OAuth2FlowKind::Implicit,
+            authorization_url: Some(flow.authorization_url.clone()),
+            token_url: None,
+            refresh_url: flow.refresh_url.clone(),
+            scopes: scopes(&flow.scopes),
+        });
+    }
+    if let Some(flow) = &flows.password {
+        models.push(OAuth2FlowModel {
+            kind: OAuth2FlowKind::Password,
+            authorization_url: None,
+            token_url: Some(flow.token_url.clone()),
+            refresh_url: flow.refresh_url.clone(),
+            scopes: scopes(&flow.scopes),
+        });
+    }
+    if let Some(flow) = &flows.client_credentials {
+        models.push(OAuth2FlowModel {
+            kind: OAuth2FlowKind::ClientCredentials,
+            authorization_url: None,
+            token_url: Some(flow.token_url.clone()),
+            refresh_url: flow.refresh_url.clone(),
+            scopes: scopes(&flow.scopes),
+        });
+    }
+    if let Some(flow) = &flows.authorization_code {
+        models.push(OAuth2FlowModel {
+            kind: OAuth2FlowKind::AuthorizationCode,
+            authorization_url: Some(flow.authorization_url.clone()),
+            token_url: Some(flow.token_url.clone()),
+            refresh_url: flow.refresh_url.clone(),
+            scopes: scopes(&flow.scopes),
+        });
+    }
+    models
+}
+
+/// A by-value reference from one model to another
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ValueEdge {
+    Field(usize),
+    Variant(usize),
+    Alias,
+}
+
+/// By-value references of a model. Arrays and maps already add indirection, so
+/// only plain named types count.
+fn value_edges(model: &ModelType) -> Vec<(ValueEdge, &str)> {
+    fn is_type_name(ty: &str) -> bool {
+        ty.chars().all(|c| c.is_alphanumeric() || c == '_')
+    }
+    fn field_edges(fields: &[Field]) -> Vec<(ValueEdge, &str)> {
+        fields
+            .iter()
+            .enumerate()
+            .filter(|(_, field)| !field.is_array_ref && is_type_name(&field.field_type))
+            .map(|(i, field)| (ValueEdge::Field(i), field.field_type.as_str()))
+            .collect()
+    }
+
+    match model {
+        ModelType::Struct(model) => field_edges(&model.fields),
+        ModelType::Composition(comp) => field_edges(&comp.all_fields),
+        ModelType::Union(union) => union
+            .variants
+            .iter()
+            .enumerate()
+            .filter(|(_, variant)| variant.primitive_type.is_none())
+            .map(|(i, variant)| (ValueEdge::Variant(i), variant.name.as_str()))
+            .collect(),
+        ModelType::TypeAlias(alias) if is_type_name(&alias.target_type) => {
+            vec![(ValueEdge::Alias, alias.target_type.as_str())]
         }
+        _ => Vec::new(),
     }
+}
 
-    // Parse responses
-    for (status, response_ref) in operation.responses.responses.iter() {
-        if let ReferenceOr::Item(response) = response_ref {
-            for (content_type, media_type) in &response.content {
-                if let Some(schema) = &media_type.schema {
-                    let mut is_array = false;
-                    let schema_type = if let ReferenceOr::Item(schema_item) = schema {
-                        if matches!(schema_item.schema_kind, SchemaKind::Type(Type::Object(_))) {
-                            let model_name = format!("{operation_name}Response{status}");
-                            let model_types =
-                                parse_schema_to_model_type(&model_name, schema, all_schemas)?;
-                            inline_models.extend(model_types);
-                            model_name
-                        } else if matches!(
-                            schema_item.schema_kind,
-                            SchemaKind::Type(Type::Array(_))
-                        ) {
-                            is_array = true;
-                            let model_name = format!("{operation_name}ResponseArrayObject{status}");
-                            let model_types =
-                                parse_schema_to_model_type(&model_name, schema, all_schemas)?;
+/// Wraps references that close a cycle of by-value references (`next: Node`) in
+/// `Box<T>`, which would otherwise make the generated types infinitely sized.
+/// Models are walked in order, so the same spec always boxes the same edges.
+fn box_recursive_references(models: &mut [ModelType]) {
+    let index: HashMap<&str, usize> = models
+        .iter()
+        .enumerate()
+        .map(|(i, model)| (model.name(), i))
+        .collect();
+    let edges: Vec<Vec<(ValueEdge, usize)>> = models
+        .iter()
+        .map(|model| {
+            value_edges(model)
+                .into_iter()
+                .filter_map(|(edge, target)| index.get(target).map(|&t| (edge, t)))
+                .collect()
+        })
+        .collect();
 
-                            inline_models.extend(model_types);
-                            model_name
-                        } else {
-                            extract_type_and_format(schema, all_schemas)?.0
-                        }
-                    } else {
-                        extract_type_and_format(schema, all_schemas)?.0
-                    };
-                    let schema = if is_array {
-                        format!("Vec<{}>", schema_type)
-                    } else {
-                        schema_type
-                    };
-                    let response = ResponseModel {
-                        name: operation_name.clone(),
-                        status_code: format!("{}", status),
-                        content_type: content_type.clone(),
-                        schema,
-                        description: Some(response.description.clone()),
-                    };
-                    responses.push(response);
+    #[derive(Clone, Copy, PartialEq)]
+    enum Mark {
+        New,
+        InProgress,
+        Done,
+    }
+    let mut marks = vec![Mark::New; models.len()];
+    let mut boxed = Vec::new();
+
+    for root in 0..models.len() {
+        if marks[root] != Mark::New {
+            continue;
+        }
+        marks[root] = Mark::InProgress;
+        let mut stack = vec![(root, 0)];
+        while let Some((node, next)) = stack.last_mut() {
+            let node = *node;
+            let Some(&(edge, target)) = edges[node].get(*next) else {
+                marks[node] = Mark::Done;
+                stack.pop();
+                continue;
+            };
+            *next += 1;
+            match marks[target] {
+                Mark::InProgress => boxed.push((node, edge)),
+                Mark::New => {
+                    marks[target] = Mark::InProgress;
+                    stack.push((target, 0));
                 }
+                Mark::Done => {}
             }
         }
     }
-    Ok(inline_models)
+
+    for (node, edge) in boxed {
+        match (&mut models[node], edge) {
+            (ModelType::Struct(model), ValueEdge::Field(i)) => {
+                box_type(&mut model.fields[i].field_type);
+            }
+            (ModelType::Composition(comp), ValueEdge::Field(i)) => {
+                box_type(&mut comp.all_fields[i].field_type);
+            }
+            (ModelType::Union(union), ValueEdge::Variant(i)) => {
+                let variant = &mut union.variants[i];
+                variant.primitive_type = Some(format!("Box<{}>", variant.name));
+            }
+            (ModelType::TypeAlias(alias), ValueEdge::Alias) => box_type(&mut alias.target_type),
+            _ => {}
+        }
+    }
 }
 
-fn parse_schema_to_model_type(
-    name: &str,
-    schema: &ReferenceOr<Schema>,
-    all_schemas: &IndexMap<String, ReferenceOr<Schema>>,
-) -> Result<Vec<ModelType>> {
-    match schema {
-        ReferenceOr::Reference { .. } => Ok(Vec::new()),
-        ReferenceOr::Item(schema) => {
-            if let Some(rust_type) = schema.schema_data.extensions.get(X_RUST_TYPE) {
-                if let Some(type_str) = rust_type.as_str() {
-                    return Ok(vec![ModelType::TypeAlias(TypeAliasModel {
-                        name: to_pascal_case(name),
-                        target_type: type_str.to_string(),
-                        description: schema.schema_data.description.clone(),
-                        custom_attrs: extract_custom_attrs(schema),
-                    })]);
+/// Adds a model unless one of the same name was added before. The same schema is often
+/// parsed several times (a shared inline body, a component reached from several places);
+/// a different model under a taken name is a collision, reported and dropped.
+fn add_model(models: &mut Vec<ModelType>, added_models: &mut HashSet<String>, model: ModelType) {
+    if added_models.insert(model.name().to_string()) {
+        models.push(model);
+        return;
+    }
+    let Some(existing) = models.iter().find(|m| m.name() == model.name()) else {
+        return;
+    };
+    if serde_json::to_value(existing).ok() != serde_json::to_value(&model).ok() {
+        tracing::warn!(
+            "Two different models are named '{}'; keeping the first one",
+            model.name()
+        );
+    }
+}
+
+/// Fields of a model that name a type, including union variant and parameter fields
+fn model_fields_mut(model: &mut ModelType) -> Vec<&mut Field> {
+    match model {
+        ModelType::Struct(model) => model.fields.iter_mut().collect(),
+        ModelType::Composition(comp) => comp
+            .all_fields
+            .iter_mut()
+            .chain(comp.embedded_fields.iter_mut())
+            .collect(),
+        ModelType::Params(params) => params.fields.iter_mut().map(|p| &mut p.field).collect(),
+        ModelType::Union(union) => union
+            .variants
+            .iter_mut()
+            .flat_map(|variant| variant.fields.iter_mut())
+            .collect(),
+        ModelType::Enum(_) | ModelType::TypeAlias(_) | ModelType::Newtype(_) => Vec::new(),
+    }
+}
+
+/// Field types an identifier newtype can wrap
+const IDENTIFIER_TYPES: &[&str] = &["String", "Uuid", "i32", "i64"];
+
+/// Newtype name for an identifier field: `id` of `User` → `UserId`, `orderID` → `OrderId`,
+/// `tag_ids` (an array) → `TagId`. Returns None for fields not named like identifiers.
+fn identifier_newtype_name(owner: &str, field_name: &str, is_array: bool) -> Option<String> {
+    let mut name = to_pascal_case(field_name);
+    if is_array {
+        name = name
+            .strip_suffix("IDs")
+            .or_else(|| name.strip_suffix("Ids"))
+            .map(|base| format!("{base}Id"))?;
+    } else if let Some(base) = name.strip_suffix("ID") {
+        name = format!("{base}Id");
+    }
+    match name.as_str() {
+        "Id" => Some(format!("{owner}Id")),
+        _ if name.ends_with("Id") => Some(name),
+        _ => None,
+    }
+}
+
+/// Wraps identifier fields in distinct newtypes (`user_id: UserId` over `Uuid`) so one kind
+/// of ID cannot be passed where another is expected. Fields with an `x-newtype` are always
+/// wrapped; `all_ids` also wraps every `id`, `*Id` and `*Ids` field of a wrappable type.
+/// Fields naming the same newtype share it; a name already taken by a model, or by a newtype
+/// over another type, leaves the field as it is.
+pub fn wrap_identifiers(models: &mut Vec<ModelType>, all_ids: bool) {
+    let taken: HashSet<String> = models
+        .iter()
+        .filter(|model| !matches!(model, ModelType::Newtype(_)))
+        .map(|model| model.name().to_string())
+        .collect();
+    let mut newtypes: IndexMap<String, NewtypeModel> = models
+        .iter()
+        .filter_map(|model| match model {
+            ModelType::Newtype(newtype) => Some((newtype.name.clone(), newtype.clone())),
+            _ => None,
+        })
+        .collect();
+    let existing = newtypes.len();
+
+    for model in models.iter_mut() {
+        let owner = model.name().to_string();
+        let is_params = matches!(model, ModelType::Params(_));
+        for field in model_fields_mut(model) {
+            if !IDENTIFIER_TYPES.contains(&field.field_type.as_str())
+                || field.flatten
+                || field.serde_with.is_some()
+            {
+                continue;
+            }
+            let name = match &field.newtype {
+                Some(name) => name.clone(),
+                // A bare `id` parameter has no model to name its newtype after
+                None if all_ids && !(is_params && field.name == "id") => {
+                    match identifier_newtype_name(&owner, &field.name, field.is_array_ref) {
+                        Some(name) => name,
+                        None => continue,
+                    }
+                }
+                None => continue,
+            };
+            match newtypes.get(&name) {
+                Some(newtype) if newtype.inner_type != field.field_type => {
+                    tracing::warn!(
+                        "Newtype {name} wraps {} but {owner}.{} is {}; leaving the field unwrapped",
+                        newtype.inner_type,
+                        field.name,
+                        field.field_type
+                    );
+                    continue;
+                }
+                Some(_) => {}
+                None if taken.contains(&name) => {
+                    tracing::warn!(
+                        "Newtype {name} for {owner}.{} is already a model name; leaving the field unwrapped",
+                        field.name
+                    );
+                    continue;
+                }
+                None => {
+                    newtypes.insert(
+                        name.clone(),
+                        NewtypeModel {
+                            name: name.clone(),
+                            inner_type: field.field_type.clone(),
+                            description: None,
+                        },
+                    );
                 }
             }
+            field.field_type = name;
+            // Range and length checks do not apply to the wrapper
+            field.constraints = Constraints {
+                min_items: field.constraints.min_items,
+                max_items: field.constraints.max_items,
+                unique_items: field.constraints.unique_items,
+                ..Constraints::default()
+            };
+        }
+    }
 
-            match &schema.schema_kind {
-                // regular objects
-                SchemaKind::Type(Type::Object(obj)) => {
-                    // Special case: object with only additionalProperties (no regular properties)
-                    if obj.properties.is_empty() && obj.additional_properties.is_some() {
-                        let hashmap_type = match &obj.additional_properties {
-                            Some(additional_props) => match additional_props {
-                                openapiv3::AdditionalProperties::Any(_) => {
-                                    "std::collections::HashMap<String, serde_json::Value>"
-                                        .to_string()
-                                }
-                                openapiv3::AdditionalProperties::Schema(schema_ref) => {
-                                    let (inner_type, _) =
-                                        extract_type_and_format(schema_ref, all_schemas)?;
-                                    format!("std::collections::HashMap<String, {inner_type}>")
-                                }
-                            },
-                            None => {
-                                "std::collections::HashMap<String, serde_json::Value>".to_string()
-                            }
-                        };
-                        return Ok(vec![ModelType::TypeAlias(TypeAliasModel {
-                            name: to_pascal_case(name),
-                            target_type: hashmap_type,
-                            description: schema.schema_data.description.clone(),
-                            custom_attrs: extract_custom_attrs(schema),
-                        })]);
-                    }
+    models.extend(
+        newtypes
+            .into_values()
+            .skip(existing)
+            .map(ModelType::Newtype),
+    );
+}
 
-                    let mut fields = Vec::new();
-                    let mut inline_models = Vec::new();
+/// Replaces whole identifiers of a type expression (`Vec<UserStatus>`) by their new names
+fn rename_type_idents(type_expr: &mut String, renames: &HashMap<String, String>) {
+    if renames.is_empty() {
+        return;
+    }
+    let mut renamed = String::with_capacity(type_expr.len());
+    let mut ident = String::new();
+    for c in type_expr.chars().chain(std::iter::once('\0')) {
+        if c.is_alphanumeric() || c == '_' {
+            ident.push(c);
+            continue;
+        }
+        renamed.push_str(renames.get(&ident).unwrap_or(&ident));
+        ident.clear();
+        if c != '\0' {
+            renamed.push(c);
+        }
+    }
+    *type_expr = renamed;
+}
 
-                    // Process regular properties
-                    for (field_name, field_schema) in &obj.properties {
-                        let mut field_to_field_type: IndexMap<String, String> = IndexMap::new();
-                        if let ReferenceOr::Item(boxed_schema) = field_schema {
-                            if matches!(boxed_schema.schema_kind, SchemaKind::Type(Type::Object(_)))
-                            {
-                                let struct_name = to_pascal_case(field_name);
-                                let wrapped_schema = ReferenceOr::Item((**boxed_schema).clone());
-                                let nested_models = parse_schema_to_model_type(
-                                    &struct_name,
-                                    &wrapped_schema,
-                                    all_schemas,
-                                )?;
-                                inline_models.extend(nested_models);
-                            } else if matches!(
-                                &boxed_schema.schema_kind,
-                                SchemaKind::Type(Type::Array(_))
-                            ) {
-                                let struct_name = format!("{}Item", to_pascal_case(field_name));
-                                field_to_field_type
-                                    .insert(field_name.to_string(), struct_name.to_string());
-                                let wrapped_schema = ReferenceOr::Item((**boxed_schema).clone());
-                                let nested_models = parse_schema_to_model_type(
-                                    &struct_name,
-                                    &wrapped_schema,
-                                    all_schemas,
-                                )?;
-                                inline_models.extend(nested_models);
-                            }
-                        }
+/// Merges property enums with the same values into one shared enum and returns the
+/// renames applied. An enum equal to a component enum becomes that component; otherwise
+/// the group is named after the property when every use has the same property name
+/// (ten `status` fields → `Status`), or after its first member. Enums that are union
+/// variants keep their names, which are also the variant identifiers.
+fn share_identical_enums(
+    models: &mut Vec<ModelType>,
+    component_names: &HashSet<String>,
+) -> HashMap<String, String> {
+    let union_variants: HashSet<String> = models
+        .iter()
+        .filter_map(|model| match model {
+            ModelType::Union(union) => Some(union.variants.iter().map(|v| v.name.clone())),
+            _ => None,
+        })
+        .flatten()
+        .collect();
 
-                        let (field_info, inline_model) = match field_schema {
-                            ReferenceOr::Item(boxed_schema) => extract_field_info(
-                                field_name,
-                                &ReferenceOr::Item((**boxed_schema).clone()),
-                                all_schemas,
-                            )?,
-                            ReferenceOr::Reference { reference } => extract_field_info(
-                                field_name,
-                                &ReferenceOr::Reference {
-                                    reference: reference.clone(),
-                                },
-                                all_schemas,
-                            )?,
-                        };
-                        if let Some(inline_model) = inline_model {
-                            inline_models.push(inline_model);
-                        }
-                        let is_required = obj.required.contains(field_name);
-                        fields.push(Field {
-                            name: field_name.clone(),
-                            field_type: field_to_field_type
-                                .get(field_name)
-                                .unwrap_or(&field_info.field_type)
-                                .to_string(),
-                            format: field_info.format,
-                            is_required,
-                            is_array_ref: field_info.is_array_ref,
-                            is_nullable: field_info.is_nullable,
-                            description: field_info.description,
-                        });
-                    }
+    // Enums grouped by their values, in model order
+    let mut groups: IndexMap<String, Vec<String>> = IndexMap::new();
+    for model in models.iter() {
+        let ModelType::Enum(enum_model) = model else {
+            continue;
+        };
+        if union_variants.contains(&enum_model.name) {
+            continue;
+        }
+        let key = serde_json::to_string(&(
+            &enum_model.variants,
+            &enum_model.integer_values,
+            &enum_model.mixed_values,
+            &enum_model.custom_attrs,
+            enum_model.extensible,
+        ))
+        .unwrap_or_default();
+        groups.entry(key).or_default().push(enum_model.name.clone());
+    }
 
-                    let mut models = inline_models;
-                    if obj.properties.is_empty() && obj.additional_properties.is_none() {
-                        models.push(ModelType::Struct(Model {
-                            name: to_pascal_case(name),
-                            fields: vec![],
-                            custom_attrs: extract_custom_attrs(schema),
-                            description: schema.schema_data.description.clone(),
-                        }));
-                    } else if !fields.is_empty() {
-                        models.push(ModelType::Struct(Model {
-                            name: to_pascal_case(name),
-                            fields,
-                            custom_attrs: extract_custom_attrs(schema),
-                            description: schema.schema_data.description.clone(),
-                        }));
-                    }
-                    Ok(models)
+    let mut field_names: HashMap<String, BTreeSet<String>> = HashMap::new();
+    for model in models.iter_mut() {
+        for field in model_fields_mut(model) {
+            field_names
+                .entry(field.field_type.clone())
+                .or_default()
+                .insert(field.name.clone());
+        }
+    }
+    let taken: HashSet<String> = models.iter().map(|m| m.name().to_string()).collect();
+
+    let mut renames = HashMap::new();
+    for members in groups.values() {
+        let inline: Vec<&String> = members
+            .iter()
+            .filter(|name| !component_names.contains(*name))
+            .collect();
+        if members.len() < 2 || inline.is_empty() {
+            continue;
+        }
+        let shared = if let Some(component) = members.iter().find(|n| component_names.contains(*n))
+        {
+            component.clone()
+        } else {
+            let properties: BTreeSet<&String> = members
+                .iter()
+                .flat_map(|name| field_names.get(name).into_iter().flatten())
+                .collect();
+            match properties.into_iter().collect::<Vec<_>>().as_slice() {
+                [property]
+                    if !taken.contains(&to_pascal_case(property))
+                        || members.contains(&to_pascal_case(property)) =>
+                {
+                    to_pascal_case(property)
                 }
+                _ => members[0].clone(),
+            }
+        };
+        for name in inline {
+            if *name != shared {
+                renames.insert(name.clone(), shared.clone());
+            }
+        }
+    }
+    if renames.is_empty() {
+        return renames;
+    }
 
-                // allOf
-                SchemaKind::AllOf { all_of } => {
-                    let (all_fields, inline_models) =
-                        resolve_all_of_fields(name, all_of, all_schemas)?;
-                    let mut models = inline_models;
+    // The first member of a group named after its property takes the new name
+    let mut kept = HashSet::new();
+    models.retain_mut(|model| {
+        if let ModelType::Enum(enum_model) = model {
+            if let Some(shared) = renames.get(&enum_model.name) {
+                if taken.contains(shared) || !kept.insert(shared.clone()) {
+                    return false;
+                }
+                enum_model.name = shared.clone();
+            }
+        }
+        true
+    });
+    for model in models.iter_mut() {
+        if let ModelType::TypeAlias(alias) = model {
+            rename_type_idents(&mut alias.target_type, &renames);
+        }
+        if let ModelType::Union(union) = model {
+            for variant in &mut union.variants {
+                if let Some(primitive_type) = &mut variant.primitive_type {
+                    rename_type_idents(primitive_type, &renames);
+                }
+            }
+        }
+        for field in model_fields_mut(model) {
+            rename_type_idents(&mut field.field_type, &renames);
+        }
+    }
+    renames
+}
 
-                    if !all_fields.is_empty() {
-                        models.push(ModelType::Composition(CompositionModel {
-                            name: to_pascal_case(name),
-                            all_fields,
-                            custom_attrs: extract_custom_attrs(schema),
-                        }));
-                    }
+fn box_type(ty: &mut String) {
+    *ty = format!("Box<{ty}>");
+}
 
-                    Ok(models)
-                }
+/// Collects warnings about schema constructs that cannot be fully represented
+/// in the generated code. Reported by the CLI in `--strict` mode.
+pub fn collect_warnings(openapi: &OpenAPI) -> Vec<String> {
+    let mut warnings = Vec::new();
 
-                // oneOf
-                SchemaKind::OneOf { one_of } => {
-                    let (variants, inline_models) =
-                        resolve_union_variants(name, one_of, all_schemas)?;
-                    let mut models = inline_models;
+    let Some(components) = &openapi.components else {
+        return warnings;
+    };
 
-                    models.push(ModelType::Union(UnionModel {
-                        name: to_pascal_case(name),
-                        variants,
-                        union_type: UnionType::OneOf,
-                        custom_attrs: extract_custom_attrs(schema),
-                    }));
+    for cycle in all_of_cycles(&components.schemas) {
+        warnings.push(format!(
+            "allOf cycle {}; the repeated member is skipped",
+            cycle.join(" -> ")
+        ));
+    }
 
-                    Ok(models)
-                }
+    for usage in not_usages(&components.schemas) {
+        warnings.push(format!(
+            "'{usage}' uses `not`; the negated schema is not encoded in the generated type"
+        ));
+    }
 
-                // anyOf
-                SchemaKind::AnyOf { any_of } => {
-                    let (variants, inline_models) =
-                        resolve_union_variants(name, any_of, all_schemas)?;
-                    let mut models = inline_models;
+    for (name, schema) in &components.schemas {
+        let ReferenceOr::Item(schema) = schema else {
+            continue;
+        };
 
-                    models.push(ModelType::Union(UnionModel {
-                        name: to_pascal_case(name),
-                        variants,
-                        union_type: UnionType::AnyOf,
-                        custom_attrs: extract_custom_attrs(schema),
-                    }));
+        if let Some(serde_json::Value::Array(patterns)) =
+            schema.schema_data.extensions.get(X_PATTERN_PROPERTIES)
+        {
+            let patterns: Vec<&str> = patterns.iter().filter_map(|p| p.as_str()).collect();
+            warnings.push(format!(
+                "Schema '{name}' uses patternProperties ({}); generating a map and \
+                 ignoring the key patterns",
+                patterns.join(", ")
+            ));
+        }
 
-                    Ok(models)
-                }
+        if let Some(serde_json::Value::Array(keys)) =
+            schema.schema_data.extensions.get(X_RENAMED_FROM)
+        {
+            if let [Some(key), Some(other)] = [keys.first(), keys.get(1)].map(|k| k?.as_str()) {
+                warnings.push(format!(
+                    "Schemas '{other}' and '{key}' are both named {}; generating '{key}' as {}",
+                    to_pascal_case(key),
+                    to_pascal_case(name)
+                ));
+            }
+        }
 
-                // enum strings
-                SchemaKind::Type(Type::String(string_type)) => {
-                    if !string_type.enumeration.is_empty() {
-                        let variants: Vec<String> = string_type
-                            .enumeration
-                            .iter()
-                            .filter_map(|value| value.clone())
-                            .collect();
+        if let Some(serde_json::Value::Array(branches)) =
+            schema.schema_data.extensions.get(X_CONDITIONAL)
+        {
+            let branches: Vec<&str> = branches.iter().filter_map(|b| b.as_str()).collect();
+            warnings.push(format!(
+                "Schema '{name}' uses if/then/else; generating one struct with the properties \
+                 of the {} branch{} as optional fields",
+                branches.join(" and "),
+                if branches.len() > 1 { "es" } else { "" }
+            ));
+        }
 
-                        if !variants.is_empty() {
-                            let models = vec![ModelType::Enum(EnumModel {
-                                name: to_pascal_case(name),
-                                variants,
-                                description: schema.schema_data.description.clone(),
-                                custom_attrs: extract_custom_attrs(schema),
-                            })];
+        if let SchemaKind::Any(any) = &schema.schema_kind {
+            let composition = if !any.one_of.is_empty() {
+                Some("oneOf")
+            } else if !any.any_of.is_empty() {
+                Some("anyOf")
+            } else {
+                None
+            };
+            if let (Some(composition), Some(_)) = (composition, &any.additional_properties) {
+                warnings.push(format!(
+                    "Schema '{name}' combines {composition} with additionalProperties; \
+                     generating the union and ignoring additionalProperties"
+                ));
+            }
+        }
+    }
 
-                            return Ok(models);
-                        }
-                    }
-                    Ok(Vec::new())
-                }
+    warnings
+}
 
-                SchemaKind::Type(Type::Array(array)) => {
-                    let mut models = Vec::new();
-                    let array_name = to_pascal_case(name);
+/// The schema without its `not` keyword, when `not` is combined with other keywords.
+/// openapiv3 only types such schemas as `SchemaKind::Any`; re-reading them without
+/// the negation recovers the base type.
+fn without_not(schema: &Schema) -> Option<Schema> {
+    let SchemaKind::Any(any) = &schema.schema_kind else {
+        return None;
+    };
+    any.not.as_ref()?;
 
-                    let items = match &array.items {
-                        Some(items) => items,
-                        None => return Ok(Vec::new()),
-                    };
+    let mut value = serde_json::to_value(schema).ok()?;
+    value.as_object_mut()?.remove("not");
+    serde_json::from_value(value).ok()
+}
 
-                    match items {
-                        ReferenceOr::Item(item_schema) => match &item_schema.schema_kind {
-                            SchemaKind::Type(Type::Object(_obj)) => {
-                                let array_model_schema = ReferenceOr::Item((**item_schema).clone());
-                                let array_object_models = parse_schema_to_model_type(
-                                    name,
-                                    &array_model_schema,
-                                    all_schemas,
-                                )?;
-                                models.extend(array_object_models);
-                            }
+/// Component schemas and properties using `not`, which is not encoded in the
+/// generated types
+fn not_usages(all_schemas: &IndexMap<String, ReferenceOr<Schema>>) -> Vec<String> {
+    fn uses_not(schema: &Schema) -> bool {
+        match &schema.schema_kind {
+            SchemaKind::Not { .. } => true,
+            SchemaKind::Any(any) => any.not.is_some(),
+            _ => false,
+        }
+    }
 
-                            SchemaKind::OneOf { one_of } => {
-                                let item_type_name = format!("{array_name}Item");
+    let mut usages = Vec::new();
+    for (name, schema) in all_schemas {
+        let ReferenceOr::Item(schema) = schema else {
+            continue;
+        };
+        if uses_not(schema) {
+            usages.push(name.clone());
+        }
+        let properties = match &schema.schema_kind {
+            SchemaKind::Type(Type::Object(obj)) => &obj.properties,
+            SchemaKind::Any(any) => &any.properties,
+            _ => continue,
+        };
+        for (property, property_schema) in properties {
+            if matches!(property_schema, ReferenceOr::Item(s) if uses_not(s)) {
+                usages.push(format!("{name}.{property}"));
+            }
+        }
+    }
+    usages
+}
 
-                                let (variants, inline_models) =
-                                    resolve_union_variants(&item_type_name, one_of, all_schemas)?;
+/// Finds cycles formed by `allOf` references between component schemas, each
+/// reported once as the path that closes it (`A -> B -> A`), in schema order
+fn all_of_cycles(all_schemas: &IndexMap<String, ReferenceOr<Schema>>) -> Vec<Vec<String>> {
+    fn all_of_refs(schema: Option<&ReferenceOr<Schema>>) -> Vec<&str> {
+        match schema {
+            Some(ReferenceOr::Item(Schema {
+                schema_kind: SchemaKind::AllOf { all_of },
+                ..
+            })) => all_of
+                .iter()
+                .filter_map(|member| match member {
+                    ReferenceOr::Reference { reference } => {
+                        reference.strip_prefix("#/components/schemas/")
+                    }
+                    ReferenceOr::Item(_) => None,
+                })
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
 
-                                models.extend(inline_models);
+    fn visit<'a>(
+        name: &'a str,
+        all_schemas: &'a IndexMap<String, ReferenceOr<Schema>>,
+        path: &mut Vec<&'a str>,
+        done: &mut HashSet<&'a str>,
+        cycles: &mut Vec<Vec<String>>,
+    ) {
+        path.push(name);
+        for target in all_of_refs(all_schemas.get(name)) {
+            if let Some(start) = path.iter().position(|&visited| visited == target) {
+                let mut cycle: Vec<String> = path[start..].iter().map(|s| s.to_string()).collect();
+                cycle.push(target.to_string());
+                cycles.push(cycle);
+            } else if !done.contains(target) {
+                visit(target, all_schemas, path, done, cycles);
+            }
+        }
+        path.pop();
+        done.insert(name);
+    }
 
-                                models.push(ModelType::Union(UnionModel {
-                                    name: item_type_name.clone(),
-                                    variants,
-                                    union_type: UnionType::OneOf,
-                                    custom_attrs: extract_custom_attrs(item_schema),
-                                }));
+    let mut cycles = Vec::new();
+    let mut done = HashSet::new();
+    for name in all_schemas.keys() {
+        if !done.contains(name.as_str()) {
+            visit(name, all_schemas, &mut Vec::new(), &mut done, &mut cycles);
+        }
+    }
+    cycles
+}
 
-                                models.push(ModelType::TypeAlias(TypeAliasModel {
-                                    name: array_name,
-                                    target_type: format!("Vec<{item_type_name}>"),
-                                    description: schema.schema_data.description.clone(),
-                                    custom_attrs: extract_custom_attrs(schema),
-                                }));
-                            }
+/// Deserializes a specification from YAML or JSON. Swagger 2.0 and OpenAPI 3.1
+/// documents are converted to 3.0 first, since that is what the rest of the
+/// pipeline understands.
+pub fn load_openapi(content: &str, is_yaml: bool) -> Result<OpenAPI> {
+    let mut value: serde_json::Value = if is_yaml {
+        serde_yaml::from_str(content)?
+    } else {
+        serde_json::from_str(content)?
+    };
 
-                            SchemaKind::Type(Type::String(s)) if !s.enumeration.is_empty() => {
-                                let item_type_name = format!("{array_name}Item");
+    if let Some(version) = value.get("swagger").and_then(|v| v.as_str()) {
+        tracing::info!("Converting Swagger {version} document to OpenAPI 3.0");
+        value = swagger2::convert_swagger_2(&value);
+    }
 
-                                let variants: Vec<String> =
-                                    s.enumeration.iter().filter_map(|v| v.clone()).collect();
+    let version = value
+        .get("openapi")
+        .and_then(|v| v.as_str())
+        .unwrap_or_default()
+        .to_string();
+    if version.starts_with("3.1") {
+        tracing::info!("Downgrading OpenAPI {version} document to 3.0");
+        downgrade_openapi_31(&mut value);
+    } else {
+        normalize_schemas_30(&mut value);
+    }
+    rename_colliding_schemas(&mut value);
 
-                                models.push(ModelType::Enum(EnumModel {
-                                    name: item_type_name.clone(),
-                                    variants,
-                                    description: item_schema.schema_data.description.clone(),
-                                    custom_attrs: extract_custom_attrs(item_schema),
-                                }));
+    Ok(serde_json::from_value(value)?)
+}
 
-                                models.push(ModelType::TypeAlias(TypeAliasModel {
-                                    name: array_name,
-                                    target_type: format!("Vec<{item_type_name}>"),
-                                    description: schema.schema_data.description.clone(),
-                                    custom_attrs: extract_custom_attrs(schema),
-                                }));
-                            }
+/// Rewrites an OpenAPI 3.1 document in place into the 3.0 shape accepted by `openapiv3`
+fn downgrade_openapi_31(document: &mut serde_json::Value) {
+    let Some(root) = document.as_object_mut() else {
+        return;
+    };
+    root.insert("openapi".to_string(), serde_json::json!("3.0.3"));
+    // `paths` is optional in 3.1 (e.g. webhook-only documents)
+    root.entry("paths").or_insert_with(|| serde_json::json!({}));
+    if let Some(webhooks) = root.remove("webhooks") {
+        root.insert(X_WEBHOOKS.to_string(), webhooks);
+    }
 
-                            SchemaKind::Type(Type::Integer(n)) if !n.enumeration.is_empty() => {
-                                let item_type_name = format!("{array_name}Item");
+    downgrade_schemas_31(document);
+}
 
-                                let variants: Vec<String> = n
-                                    .enumeration
-                                    .iter()
-                                    .filter_map(|v| v.map(|num| format!("Value{num}")))
-                                    .collect();
+/// Recursively applies the JSON Schema 2020-12 → OpenAPI 3.0 schema rewrites
+fn downgrade_schemas_31(value: &mut serde_json::Value) {
+    match value {
+        serde_json::Value::Object(map) => {
+            // The parent goes first so it still sees `type: "null"` members as written
+            downgrade_schema_31(map);
+            for inner in map.values_mut() {
+                downgrade_schemas_31(inner);
+            }
+        }
+        serde_json::Value::Array(items) => {
+            for inner in items {
+                downgrade_schemas_31(inner);
+            }
+        }
+        _ => {}
+    }
+}
 
-                                models.push(ModelType::Enum(EnumModel {
-                                    name: item_type_name.clone(),
-                                    variants,
-                                    description: item_schema.schema_data.description.clone(),
-                                    custom_attrs: extract_custom_attrs(item_schema),
-                                }));
+/// Downgrades a single schema object. Non-schema objects pass through unchanged,
+/// because every rewrite is keyed on a shape that is only valid for 3.1 schemas.
+fn downgrade_schema_31(schema: &mut serde_json::Map<String, serde_json::Value>) {
+    null_types_to_nullable(schema);
+    xml_to_extension(schema);
+    ref_siblings_to_all_of(schema);
+
+    // Numeric `exclusiveMinimum`/`exclusiveMaximum` → bound plus boolean flag
+    for (exclusive, bound) in [
+        ("exclusiveMinimum", "minimum"),
+        ("exclusiveMaximum", "maximum"),
+    ] {
+        if let Some(limit) = schema.get(exclusive).filter(|v| v.is_number()).cloned() {
+            schema.insert(bound.to_string(), limit);
+            schema.insert(exclusive.to_string(), serde_json::json!(true));
+        }
+    }
 
-                                models.push(ModelType::TypeAlias(TypeAliasModel {
-                                    name: array_name,
-                                    target_type: format!("Vec<{item_type_name}>"),
-                                    description: schema.schema_data.description.clone(),
-                                    custom_attrs: extract_custom_attrs(schema),
-                                }));
-                            }
+    const_to_enum(schema);
+    extensible_enum_to_enum(schema);
+    pattern_properties_to_map(schema);
+    prefix_items_to_extension(schema);
+    conditionals_to_properties(schema);
+
+    // Schema `examples` array → 3.0 `example`. Media types and parameters use an
+    // `examples` map in both versions, so only arrays are touched.
+    if let Some(serde_json::Value::Array(examples)) = schema.get("examples") {
+        if let Some(first) = examples.first().cloned() {
+            schema.entry("example").or_insert(first);
+        }
+        schema.remove("examples");
+    }
 
-                            _ => {
-                                let normalized_items = match items {
-                                    ReferenceOr::Item(boxed_schema) => {
-                                        ReferenceOr::Item((**boxed_schema).clone())
-                                    }
-                                    ReferenceOr::Reference { reference } => {
-                                        ReferenceOr::Reference {
-                                            reference: reference.clone(),
-                                        }
-                                    }
-                                };
+    // contentEncoding/contentMediaType replace the 3.0 byte/binary formats
+    if !schema.contains_key("format") {
+        let format = if schema.get("contentEncoding").is_some_and(|e| e == "base64") {
+            Some("byte")
+        } else if schema
+            .get("contentMediaType")
+            .is_some_and(|m| m == "application/octet-stream")
+        {
+            Some("binary")
+        } else {
+            None
+        };
+        if let Some(format) = format {
+            schema.insert("format".to_string(), serde_json::json!(format));
+        }
+    }
+}
 
-                                let (inner_type, _) =
-                                    extract_type_and_format(&normalized_items, all_schemas)?;
+/// Turns `null` in a `type` array, or a `{type: "null"}` member of `oneOf`/`anyOf`,
+/// into `nullable: true`. Several remaining types become an `anyOf` of single-typed
+/// schemas; a single remaining union member is kept as `allOf: [member]`.
+fn null_types_to_nullable(schema: &mut serde_json::Map<String, serde_json::Value>) {
+    // `type: [string, "null"]` → `type: string, nullable: true`; several
+    // non-null types become an anyOf of single-typed schemas
+    if let Some(serde_json::Value::Array(types)) = schema.get("type").cloned() {
+        let mut types: Vec<serde_json::Value> = types;
+        let before = types.len();
+        types.retain(|t| t != "null");
+        if types.len() != before {
+            schema.insert("nullable".to_string(), serde_json::json!(true));
+        }
+        match types.len() {
+            0 => {
+                schema.remove("type");
+            }
+            1 => {
+                schema.insert("type".to_string(), types.remove(0));
+            }
+            _ => {
+                schema.remove("type");
+                let variants = types
+                    .into_iter()
+                    .map(|t| serde_json::json!({ "type": t }))
+                    .collect();
+                schema.insert("anyOf".to_string(), serde_json::Value::Array(variants));
+            }
+        }
+    } else if schema.get("type").is_some_and(|t| t == "null") {
+        schema.remove("type");
+        schema.insert("nullable".to_string(), serde_json::json!(true));
+    }
 
-                                models.push(ModelType::TypeAlias(TypeAliasModel {
-                                    name: array_name,
-                                    target_type: format!("Vec<{inner_type}>"),
-                                    description: schema.schema_data.description.clone(),
-                                    custom_attrs: extract_custom_attrs(schema),
-                                }));
-                            }
-                        },
+    // `oneOf`/`anyOf` with a `{type: "null"}` member → nullable
+    for keyword in ["oneOf", "anyOf"] {
+        if let Some(serde_json::Value::Array(members)) = schema.get_mut(keyword) {
+            let before = members.len();
+            members.retain(|member| !member.get("type").is_some_and(|t| t == "null"));
+            if members.len() != before {
+                if members.len() == 1 && !schema.contains_key("type") {
+                    let only = schema.remove(keyword).and_then(|m| match m {
+                        serde_json::Value::Array(mut m) => m.pop(),
+                        _ => None,
+                    });
+                    if let Some(only) = only {
+                        schema.insert("allOf".to_string(), serde_json::json!([only]));
+                    }
+                }
+                schema.insert("nullable".to_string(), serde_json::json!(true));
+            }
+        }
+    }
+}
 
-                        ReferenceOr::Reference { .. } => {
-                            let normalized_items = match items {
-                                ReferenceOr::Item(boxed_schema) => {
-                                    ReferenceOr::Item((**boxed_schema).clone())
-                                }
-                                ReferenceOr::Reference { reference } => ReferenceOr::Reference {
-                                    reference: reference.clone(),
-                                },
-                            };
+/// Rewrites `const` into the single-value `enum` that OpenAPI 3.0 understands,
+/// inferring `type` from the value when it is missing. An object under `const` is a
+/// property named "const" in a `properties` map, not the keyword, and is left alone.
+fn const_to_enum(schema: &mut serde_json::Map<String, serde_json::Value>) {
+    if !matches!(schema.get("const"), Some(constant) if !constant.is_object()) {
+        return;
+    }
+    let Some(constant) = schema.remove("const") else {
+        return;
+    };
 
-                            let (inner_type, _) =
-                                extract_type_and_format(&normalized_items, all_schemas)?;
+    if !schema.contains_key("type") {
+        let inferred = match &constant {
+            serde_json::Value::String(_) => Some("string"),
+            serde_json::Value::Bool(_) => Some("boolean"),
+            serde_json::Value::Number(n) if n.is_f64() => Some("number"),
+            serde_json::Value::Number(_) => Some("integer"),
+            _ => None,
+        };
+        if let Some(inferred) = inferred {
+            schema.insert("type".to_string(), serde_json::json!(inferred));
+        }
+    }
+    schema
+        .entry("enum")
+        .or_insert_with(|| serde_json::json!([constant]));
+}
 
-                            models.push(ModelType::TypeAlias(TypeAliasModel {
-                                name: array_name,
-                                target_type: format!("Vec<{inner_type}>"),
-                                description: schema.schema_data.description.clone(),
-                                custom_attrs: extract_custom_attrs(schema),
-                            }));
-                        }
-                    }
+/// Copies the values of an `x-extensible-enum` list into `enum` when the schema has none,
+/// so the known values are generated as variants like those of a closed enum
+fn extensible_enum_to_enum(schema: &mut serde_json::Map<String, serde_json::Value>) {
+    if let Some(values @ serde_json::Value::Array(_)) = schema.get(X_EXTENSIBLE_ENUM) {
+        let values = values.clone();
+        schema.entry("enum").or_insert(values);
+    }
+}
 
-                    Ok(models)
-                }
+/// Wraps a schema `$ref` that has sibling keywords such as `description` or `nullable`
+/// in a single-member `allOf`, which keeps the siblings: openapiv3 reads a `$ref` as a
+/// bare reference and drops everything next to it. References to anything other than
+/// component schemas are left alone, since parameters and responses have no `allOf`.
+fn ref_siblings_to_all_of(schema: &mut serde_json::Map<String, serde_json::Value>) {
+    let is_schema_ref = schema
+        .get("$ref")
+        .and_then(|reference| reference.as_str())
+        .is_some_and(|reference| reference.starts_with("#/components/schemas/"));
+    if !is_schema_ref || schema.len() == 1 {
+        return;
+    }
+    if let Some(reference) = schema.remove("$ref") {
+        schema.insert(
+            "allOf".to_string(),
+            serde_json::json!([{ "$ref": reference }]),
+        );
+    }
+}
 
-                _ => Ok(Vec::new()),
+/// Moves the `xml` keyword of a schema to `x-xml`, where openapiv3 keeps it. A property
+/// named "xml" maps to a schema rather than to an object of XML settings, and is left alone.
+fn xml_to_extension(schema: &mut serde_json::Map<String, serde_json::Value>) {
+    const XML_KEYS: &[&str] = &["name", "namespace", "prefix", "attribute", "wrapped"];
+    let is_keyword = matches!(
+        schema.get("xml"),
+        Some(serde_json::Value::Object(xml))
+            if xml.iter().all(|(key, value)| XML_KEYS.contains(&key.as_str()) && !value.is_object())
+    );
+    if !is_keyword {
+        return;
+    }
+    if let Some(xml) = schema.remove("xml") {
+        schema.insert(X_XML.to_string(), xml);
+    }
+}
+
+/// Folds `patternProperties` into `additionalProperties`, since a Rust map cannot
+/// constrain its keys: one value schema is used as is, several become an `anyOf`.
+/// The key patterns are kept under `x-pattern-properties` for the `--strict` warning.
+fn pattern_properties_to_map(schema: &mut serde_json::Map<String, serde_json::Value>) {
+    // A property that happens to be named "patternProperties" maps to one schema,
+    // not to an object of schemas
+    let is_keyword = matches!(
+        schema.get("patternProperties"),
+        Some(serde_json::Value::Object(patterns)) if patterns.values().all(|v| v.is_object())
+    );
+    if !is_keyword {
+        return;
+    }
+    let Some(serde_json::Value::Object(patterns)) = schema.remove("patternProperties") else {
+        return;
+    };
+
+    let mut value_schemas: Vec<serde_json::Value> = Vec::new();
+    if let Some(additional @ serde_json::Value::Object(_)) = schema.get("additionalProperties") {
+        value_schemas.push(additional.clone());
+    }
+    for value_schema in patterns.values() {
+        if !value_schemas.contains(value_schema) {
+            value_schemas.push(value_schema.clone());
+        }
+    }
+
+    // `additionalProperties: true` already admits every value
+    if !schema
+        .get("additionalProperties")
+        .is_some_and(|a| a == true)
+    {
+        let value_schema = if value_schemas.len() == 1 {
+            value_schemas.remove(0)
+        } else {
+            serde_json::json!({ "anyOf": value_schemas })
+        };
+        schema.insert("additionalProperties".to_string(), value_schema);
+    }
+    schema
+        .entry("type")
+        .or_insert_with(|| serde_json::json!("object"));
+    schema.insert(
+        X_PATTERN_PROPERTIES.to_string(),
+        serde_json::Value::Array(
+            patterns
+                .keys()
+                .map(|p| serde_json::Value::String(p.clone()))
+                .collect(),
+        ),
+    );
+}
+
+/// Moves `prefixItems` to `x-prefix-items`, which openapiv3 keeps, and drops the
+/// `items: false` closing the tuple, which it cannot read. The element schemas are still
+/// normalized, as the extension is walked like any other key.
+fn prefix_items_to_extension(schema: &mut serde_json::Map<String, serde_json::Value>) {
+    // A property named "prefixItems" maps to one schema, not to an array of them
+    if !matches!(schema.get("prefixItems"), Some(serde_json::Value::Array(_))) {
+        return;
+    }
+    if let Some(prefix_items) = schema.remove("prefixItems") {
+        schema.insert(X_PREFIX_ITEMS.to_string(), prefix_items);
+    }
+    if schema.get("items").is_some_and(|items| items == false) {
+        schema.remove("items");
+    }
+}
+
+/// Folds `if`/`then`/`else` into the schema, which openapiv3 would drop: the properties
+/// of both branches are added to the schema's own, optional unless the schema already
+/// requires them. The result accepts every payload of either branch. The branches are
+/// listed under `x-conditional` for the `--strict` warning.
+fn conditionals_to_properties(schema: &mut serde_json::Map<String, serde_json::Value>) {
+    // A properties map may hold a property named "then"; a conditional needs its `if`
+    let is_keyword = ["if", "then", "else"]
+        .iter()
+        .filter_map(|key| schema.get(*key))
+        .all(serde_json::Value::is_object)
+        && schema.contains_key("if")
+        && (schema.contains_key("then") || schema.contains_key("else"));
+    if !is_keyword {
+        return;
+    }
+    schema.remove("if");
+
+    let mut branches = Vec::new();
+    for key in ["then", "else"] {
+        let Some(serde_json::Value::Object(branch)) = schema.remove(key) else {
+            continue;
+        };
+        branches.push(serde_json::json!(key));
+        let Some(serde_json::Value::Object(branch_properties)) = branch.get("properties") else {
+            continue;
+        };
+        let properties = schema
+            .entry("properties")
+            .or_insert_with(|| serde_json::json!({}));
+        if let serde_json::Value::Object(properties) = properties {
+            for (name, property) in branch_properties {
+                properties
+                    .entry(name.clone())
+                    .or_insert_with(|| property.clone());
             }
         }
     }
+    schema.insert(
+        X_CONDITIONAL.to_string(),
+        serde_json::Value::Array(branches),
+    );
 }
 
-fn extract_type_and_format(
-    schema: &ReferenceOr<Schema>,
-    all_schemas: &IndexMap<String, ReferenceOr<Schema>>,
-) -> Result<(String, String)> {
-    match schema {
-        ReferenceOr::Reference { reference } => {
-            let type_name = reference.split('/').next_back().unwrap_or("Unknown");
+/// Applies [`null_types_to_nullable`], [`const_to_enum`], [`pattern_properties_to_map`],
+/// [`prefix_items_to_extension`] and [`conditionals_to_properties`] throughout a 3.0
+/// document. None of these keywords are OpenAPI 3.0, but 3.0 specs
+/// commonly borrow them from JSON Schema. [`extensible_enum_to_enum`] fills in `enum`
+/// for schemas that only list their values in `x-extensible-enum`. [`xml_to_extension`] and
+/// [`ref_siblings_to_all_of`] keep the `xml` objects and `$ref` siblings openapiv3 would
+/// drop. Parents go first, as in the 3.1 downgrade.
+fn normalize_schemas_30(value: &mut serde_json::Value) {
+    match value {
+        serde_json::Value::Object(map) => {
+            null_types_to_nullable(map);
+            const_to_enum(map);
+            extensible_enum_to_enum(map);
+            pattern_properties_to_map(map);
+            prefix_items_to_extension(map);
+            conditionals_to_properties(map);
+            xml_to_extension(map);
+            ref_siblings_to_all_of(map);
+            for inner in map.values_mut() {
+                normalize_schemas_30(inner);
+            }
+        }
+        serde_json::Value::Array(items) => {
+            for inner in items {
+                normalize_schemas_30(inner);
+            }
+        }
+        _ => {}
+    }
+}
 
-            if let Some(ReferenceOr::Item(schema)) = all_schemas.get(type_name) {
-                if matches!(schema.schema_kind, SchemaKind::OneOf { .. }) {
-                    return Ok((to_pascal_case(type_name), "oneOf".to_string()));
-                }
+/// Renames component schemas whose keys map to the same Rust type name, such as
+/// `user_profile` and `UserProfile`, which would otherwise shadow each other. The later
+/// key gets the first free numeric suffix (`user_profile2` -> `UserProfile2`), and every
+/// `$ref` and discriminator mapping pointing at it is rewritten. The original keys are
+/// kept in `x-renamed-from` so the renames can be reported.
+fn rename_colliding_schemas(document: &mut serde_json::Value) {
+    let Some(schemas) = document
+        .pointer_mut("/components/schemas")
+        .and_then(|schemas| schemas.as_object_mut())
+    else {
+        return;
+    };
+
+    let mut names: HashMap<String, String> = HashMap::new();
+    let mut renames = Vec::new();
+    for key in schemas.keys() {
+        let name = to_pascal_case(key);
+        let Some(other) = names.get(&name).cloned() else {
+            names.insert(name, key.clone());
+            continue;
+        };
+        let new_key = (2..)
+            .map(|n| format!("{key}{n}"))
+            .find(|candidate| {
+                !schemas.contains_key(candidate) && !names.contains_key(&to_pascal_case(candidate))
+            })
+            .expect("an unused suffix exists");
+        let new_name = to_pascal_case(&new_key);
+        tracing::warn!(
+            "Schemas '{other}' and '{key}' are both named {name}; generating '{key}' as {new_name}"
+        );
+        names.insert(new_name, new_key.clone());
+        renames.push((key.clone(), new_key, other));
+    }
+    if renames.is_empty() {
+        return;
+    }
+
+    for (key, new_key, other) in &renames {
+        if let Some(mut schema) = schemas.remove(key) {
+            if let Some(object) = schema.as_object_mut() {
+                object.insert(X_RENAMED_FROM.to_string(), serde_json::json!([key, other]));
             }
-            Ok((to_pascal_case(type_name), "reference".to_string()))
+            schemas.insert(new_key.clone(), schema);
         }
+    }
+    let renames: HashMap<&str, &str> = renames
+        .iter()
+        .map(|(key, new_key, _)| (key.as_str(), new_key.as_str()))
+        .collect();
+    rewrite_schema_refs(document, &renames);
+}
 
-        ReferenceOr::Item(schema) => match &schema.schema_kind {
-            SchemaKind::Type(Type::String(string_type)) => match &string_type.format {
-                VariantOrUnknownOrEmpty::Item(fmt) => match fmt {
-                    StringFormat::DateTime => {
-                        Ok(("DateTime<Utc>".to_string(), "date-time".to_string()))
-                    }
-                    StringFormat::Date => Ok(("NaiveDate".to_string(), "date".to_string())),
-                    _ => Ok(("String".to_string(), format!("{fmt:?}"))),
-                },
-                VariantOrUnknownOrEmpty::Unknown(unknown_format) => {
-                    if unknown_format.to_lowercase() == "uuid" {
-                        Ok(("Uuid".to_string(), "uuid".to_string()))
-                    } else {
-                        Ok(("String".to_string(), unknown_format.clone()))
-                    }
-                }
-                _ => Ok(("String".to_string(), "string".to_string())),
-            },
-            SchemaKind::Type(Type::Integer(_)) => Ok(("i64".to_string(), "integer".to_string())),
-            SchemaKind::Type(Type::Number(_)) => Ok(("f64".to_string(), "number".to_string())),
-            SchemaKind::Type(Type::Boolean(_)) => Ok(("bool".to_string(), "boolean".to_string())),
-            SchemaKind::Type(Type::Array(arr)) => {
-                if let Some(items) = &arr.items {
-                    match items {
-                        ReferenceOr::Item(boxed_schema) => extract_type_and_format(
-                            &ReferenceOr::Item((**boxed_schema).clone()),
-                            all_schemas,
-                        ),
+/// Points `$ref`s and discriminator mappings at renamed component schemas
+fn rewrite_schema_refs(value: &mut serde_json::Value, renames: &HashMap<&str, &str>) {
+    const PREFIX: &str = "#/components/schemas/";
+    let rename = |target: &mut serde_json::Value, bare_names: bool| {
+        let Some(text) = target.as_str() else {
+            return;
+        };
+        let renamed = match text.strip_prefix(PREFIX) {
+            Some(rest) => {
+                let (key, tail) = rest.split_at(rest.find('/').unwrap_or(rest.len()));
+                renames
+                    .get(key)
+                    .map(|new_key| format!("{PREFIX}{new_key}{tail}"))
+            }
+            None if bare_names => renames.get(text).map(|new_key| new_key.to_string()),
+            None => None,
+        };
+        if let Some(renamed) = renamed {
+            *target = serde_json::Value::String(renamed);
+        }
+    };
 
-                        ReferenceOr::Reference { reference } => extract_type_and_format(
-                            &ReferenceOr::Reference {
-                                reference: reference.clone(),
-                            },
-                            all_schemas,
-                        ),
-                    }
-                } else {
-                    Ok(("serde_json::Value".to_string(), "array".to_string()))
+    match value {
+        serde_json::Value::Object(map) => {
+            if let Some(reference) = map.get_mut("$ref") {
+                rename(reference, false);
+            }
+            if let Some(serde_json::Value::Object(mapping)) = map
+                .get_mut("discriminator")
+                .and_then(|discriminator| discriminator.get_mut("mapping"))
+            {
+                for target in mapping.values_mut() {
+                    rename(target, true);
                 }
             }
-            SchemaKind::Type(Type::Object(_obj)) => {
-                Ok(("serde_json::Value".to_string(), "object".to_string()))
+            for inner in map.values_mut() {
+                rewrite_schema_refs(inner, renames);
             }
-            _ => Ok(("serde_json::Value".to_string(), "unknown".to_string())),
-        },
+        }
+        serde_json::Value::Array(items) => {
+            for inner in items {
+                rewrite_schema_refs(inner, renames);
+            }
+        }
+        _ => {}
     }
 }
 
-/// Extracts field information including type, format, and nullable flag from OpenAPI schema
-fn extract_field_info(
-    field_name: &str,
-    schema: &ReferenceOr<Schema>,
-    all_schemas: &IndexMap<String, ReferenceOr<Schema>>,
-) -> Result<(FieldInfo, Option<ModelType>)> {
-    let (mut field_type, format) = extract_type_and_format(schema, all_schemas)?;
+/// Restricts a specification to the named component schemas and everything they
+/// reference, transitively. Paths and request bodies are dropped.
+pub fn select_schemas(openapi: &OpenAPI, only: &[String]) -> Result<OpenAPI> {
+    let empty_schemas = IndexMap::new();
+    let schemas = openapi
+        .components
+        .as_ref()
+        .map_or(&empty_schemas, |components| &components.schemas);
+
+    let mut selected = HashSet::new();
+    let mut queue: VecDeque<String> = VecDeque::new();
+
+    for name in only {
+        if !schemas.contains_key(name) {
+            return Err(Error::OpenApi(format!(
+                "Schema '{name}' requested via --only does not exist"
+            )));
+        }
+        queue.push_back(name.clone());
+    }
 
-    let (is_nullable, is_array_ref, en, description) = match schema {
-        ReferenceOr::Reference { reference } => {
-            let mut is_array_ref = false;
-            let mut is_nullable = false;
+    while let Some(name) = queue.pop_front() {
+        if !selected.insert(name.clone()) {
+            continue;
+        }
+        if let Some(schema) = schemas.get(&name) {
+            let value = serde_json::to_value(schema)?;
+            let mut references = Vec::new();
+            collect_schema_references(&value, &mut references);
+            queue.extend(
+                references
+                    .into_iter()
+                    .filter(|reference| schemas.contains_key(reference)),
+            );
+        }
+    }
 
-            if let Some(type_name) = reference.strip_prefix("#/components/schemas/") {
-                if let Some(ReferenceOr::Item(schema)) = all_schemas.get(type_name) {
-                    is_nullable = schema.schema_data.nullable;
+    let mut filtered = openapi.clone();
+    filtered.paths = Default::default();
+    if let Some(components) = &mut filtered.components {
+        components.schemas.retain(|name, _| selected.contains(name));
+        components.request_bodies.clear();
+    }
 
-                    if let SchemaKind::Type(Type::Array(array)) = &schema.schema_kind {
-                        let is_items_one_of = match &array.items {
-                            Some(ReferenceOr::Item(item_schema)) => {
-                                matches!(item_schema.schema_kind, SchemaKind::OneOf { .. })
-                            }
-                            _ => false,
-                        };
+    Ok(filtered)
+}
 
-                        is_array_ref = !is_items_one_of;
+/// Collects the names of all `#/components/schemas/...` references within a JSON value
+fn collect_schema_references(value: &serde_json::Value, references: &mut Vec<String>) {
+    match value {
+        serde_json::Value::Object(map) => {
+            for (key, inner) in map {
+                if key == "$ref" {
+                    if let Some(name) = inner
+                        .as_str()
+                        .and_then(|r| r.strip_prefix("#/components/schemas/"))
+                    {
+                        references.push(name.to_string());
                     }
+                } else {
+                    collect_schema_references(inner, references);
                 }
             }
-
-            (is_nullable, is_array_ref, None, None)
         }
+        serde_json::Value::Array(items) => {
+            for inner in items {
+                collect_schema_references(inner, references);
+            }
+        }
+        _ => {}
+    }
+}
 
-        ReferenceOr::Item(schema) => {
-            if let Some(rust_type) = schema.schema_data.extensions.get(X_RUST_TYPE) {
-                if let Some(type_str) = rust_type.as_str() {
-                    field_type = type_str.to_string();
+/// Resolves a list of parameters, following `#/components/parameters/...` references.
+/// References that cannot be resolved are skipped with a warning.
+pub fn resolve_parameters<'a>(
+    parameters: &'a [ReferenceOr<Parameter>],
+    components: &'a IndexMap<String, ReferenceOr<Parameter>>,
+) -> Vec<&'a Parameter> {
+    let mut resolved = Vec::new();
+
+    for parameter_ref in parameters {
+        let mut current = parameter_ref;
+        let mut visited = HashSet::new();
+
+        let parameter = loop {
+            match current {
+                ReferenceOr::Item(parameter) => break Some(parameter),
+                ReferenceOr::Reference { reference } => {
+                    let target = reference
+                        .strip_prefix("#/components/parameters/")
+                        .filter(|name| visited.insert(*name))
+                        .and_then(|name| components.get(name));
+                    match target {
+                        Some(next) => current = next,
+                        None => {
+                            tracing::warn!("Unable to resolve parameter reference {}", reference);
+                            break None;
+                        }
+                    }
                 }
             }
+        };
 
-            let is_nullable = schema.schema_data.nullable;
-            let is_array_ref = matches!(schema.schema_kind, SchemaKind::Type(Type::Array(_)));
-            let description = schema.schema_data.description.clone();
-
-            let maybe_enum = match &schema.schema_kind {
-                SchemaKind::Type(Type::String(s)) if !s.enumeration.is_empty() => {
-                    let variants: Vec<String> =
-                        s.enumeration.iter().filter_map(|v| v.clone()).collect();
-                    field_type = to_pascal_case(field_name);
-                    Some(ModelType::Enum(EnumModel {
-                        name: to_pascal_case(field_name),
-                        variants,
-                        description: schema.schema_data.description.clone(),
-                        custom_attrs: extract_custom_attrs(schema),
-                    }))
-                }
-                SchemaKind::Type(Type::Object(obj)) => {
-                    if obj.properties.is_empty() {
-                        if let Some(additional_props) = &obj.additional_properties {
-                            match additional_props {
-                                AdditionalProperties::Schema(schema) => {
-                                    let (value_type, _) =
-                                        extract_type_and_format(&schema.clone(), all_schemas)?;
-
-                                    field_type = format!(
-                                        "std::collections::HashMap<String, {}>",
-                                        value_type
-                                    );
-                                }
+        if let Some(parameter) = parameter {
+            resolved.push(parameter);
+        }
+    }
 
-                                AdditionalProperties::Any(true) => {
-                                    field_type =
-                                        "std::collections::HashMap<String, serde_json::Value>"
-                                            .to_string();
-                                }
+    resolved
+}
 
-                                AdditionalProperties::Any(false) => {
-                                    // technically: no additional props allowed
-                                    field_type = "serde_json::Value".to_string();
-                                }
-                            }
-                            None
-                        } else {
-                            field_type = "serde_json::Value".to_string();
-                            None
-                        }
-                    } else {
-                        let struct_name = to_pascal_case(field_name);
-                        field_type = struct_name.clone();
+/// PascalCase operationId of an operation, or `backup_name` without one
+fn operation_name(operation: &openapiv3::Operation, backup_name: &str) -> String {
+    to_pascal_case(operation.operation_id.as_deref().unwrap_or(backup_name))
+}
 
-                        let wrapped_schema = ReferenceOr::Item(schema.clone());
-                        let models =
-                            parse_schema_to_model_type(&struct_name, &wrapped_schema, all_schemas)?;
+/// Follows `$ref`s that point into sibling files (e.g. `./common.yaml#/components/schemas/Foo`),
+/// copies the referenced definitions into this document's components and rewrites the
+/// references to local ones. Relative paths are resolved against `base_dir`.
+pub fn resolve_external_refs(openapi: &OpenAPI, base_dir: &Path) -> Result<OpenAPI> {
+    let mut root = serde_json::to_value(openapi)?;
+    let mut resolver = ExternalRefResolver {
+        base_dir: base_dir.to_path_buf(),
+        documents: HashMap::new(),
+        imported: HashMap::new(),
+        taken: HashSet::new(),
+        components: IndexMap::new(),
+        resolved: 0,
+        total: count_refs(&root, false),
+    };
 
-                        models
-                            .into_iter()
-                            .find(|m| matches!(m, ModelType::Struct(_)))
-                    }
+    if let Some(components) = root.get("components").and_then(|c| c.as_object()) {
+        for (section, entries) in components {
+            if let Some(entries) = entries.as_object() {
+                for name in entries.keys() {
+                    resolver.taken.insert((section.clone(), name.clone()));
                 }
-                _ => None,
-            };
-            (is_nullable, is_array_ref, maybe_enum, description)
+            }
         }
-    };
+    }
 
-    Ok((
-        FieldInfo {
-            field_type,
-            format,
-            is_nullable,
-            is_array_ref,
-            description,
-        },
-        en,
-    ))
+    resolver.rewrite(&mut root, None)?;
+
+    if resolver.components.is_empty() {
+        return Ok(openapi.clone());
+    }
+
+    let components = root
+        .as_object_mut()
+        .ok_or_else(|| Error::OpenApi("Specification root is not an object".to_string()))?
+        .entry("components")
+        .or_insert_with(|| serde_json::json!({}));
+    for (section, entries) in resolver.components {
+        let target = components
+            .as_object_mut()
+            .ok_or_else(|| Error::OpenApi("components is not an object".to_string()))?
+            .entry(section)
+            .or_insert_with(|| serde_json::json!({}));
+        if let Some(target) = target.as_object_mut() {
+            target.extend(entries);
+        }
+    }
+
+    Ok(serde_json::from_value(root)?)
 }
 
-fn resolve_all_of_fields(
-    _name: &str,
-    all_of: &[ReferenceOr<Schema>],
-    all_schemas: &IndexMap<String, ReferenceOr<Schema>>,
-) -> Result<(Vec<Field>, Vec<ModelType>)> {
-    let mut all_fields: IndexMap<String, Field> = IndexMap::new();
-    let mut models = Vec::new();
-    let mut all_required_fields = HashSet::new();
+/// State shared while importing definitions from external files
+struct ExternalRefResolver {
+    base_dir: PathBuf,
+    /// Loaded external documents, keyed by path
+    documents: HashMap<PathBuf, serde_json::Value>,
+    /// (file, JSON pointer) -> local reference it was imported as
+    imported: HashMap<(PathBuf, String), String>,
+    /// (components section, name) pairs already in use
+    taken: HashSet<(String, String)>,
+    /// Imported definitions grouped by components section
+    components: IndexMap<String, serde_json::Map<String, serde_json::Value>>,
+    /// External references resolved so far, for progress reporting
+    resolved: usize,
+    /// External references found so far, growing as definitions are imported
+    total: usize,
+}
 
-    for schema_ref in all_of {
-        let schema_to_check = match schema_ref {
+impl ExternalRefResolver {
+    /// Rewrites every `$ref` within `value`. `current_file` is the external file the value
+    /// came from, in which case local `#/...` references are relative to that file.
+    fn rewrite(
+        &mut self,
+        value: &mut serde_json::Value,
+        current_file: Option<&Path>,
+    ) -> Result<()> {
+        match value {
+            serde_json::Value::Object(map) => {
+                for (key, inner) in map.iter_mut() {
+                    if key == "$ref" {
+                        if let Some(reference) = inner.as_str() {
+                            if let Some(local) = self.resolve(reference, current_file)? {
+                                *inner = serde_json::Value::String(local);
+                            }
+                        }
+                    } else {
+                        self.rewrite(inner, current_file)?;
+                    }
+                }
+            }
+            serde_json::Value::Array(items) => {
+                for inner in items {
+                    self.rewrite(inner, current_file)?;
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Returns the local reference for `reference`, importing its target if needed.
+    /// Returns `None` for references that are already local to the root document.
+    fn resolve(&mut self, reference: &str, current_file: Option<&Path>) -> Result<Option<String>> {
+        let (file_part, pointer) = reference.split_once('#').unwrap_or((reference, ""));
+        let file = match (file_part.is_empty(), current_file) {
+            (true, None) => return Ok(None),
+            (true, Some(current)) => current.to_path_buf(),
+            (false, _) => {
+                if file_part.starts_with("http://") || file_part.starts_with("https://") {
+                    return Err(Error::OpenApi(format!(
+                        "Remote reference '{reference}' is not supported"
+                    )));
+                }
+                let dir = current_file
+                    .and_then(Path::parent)
+                    .unwrap_or(&self.base_dir);
+                let path = dir.join(file_part);
+                path.canonicalize().unwrap_or(path)
+            }
+        };
+
+        let key = (file.clone(), pointer.to_string());
+        if let Some(local) = self.imported.get(&key).cloned() {
+            self.report_resolved();
+            return Ok(Some(local));
+        }
+
+        if !self.documents.contains_key(&file) {
+            tracing::info!("Loading external file {}", file.display());
+            let document = load_document(&file)?;
+            self.documents.insert(file.clone(), document);
+        }
+        let mut target = self.documents[&file]
+            .pointer(pointer)
+            .cloned()
+            .ok_or_else(|| {
+                Error::OpenApi(format!(
+                    "Reference '{reference}' does not resolve to a definition in {}",
+                    file.display()
+                ))
+            })?;
+
+        let (section, base_name) = match pointer.split('/').collect::<Vec<_>>().as_slice() {
+            ["", "components", section, name] => (section.to_string(), unescape_pointer(name)),
+            _ => {
+                let name = pointer
+                    .rsplit('/')
+                    .next()
+                    .filter(|name| !name.is_empty())
+                    .map(unescape_pointer)
+                    .or_else(|| {
+                        file.file_stem()
+                            .map(|stem| to_pascal_case(&stem.to_string_lossy()))
+                    })
+                    .unwrap_or_else(|| "External".to_string());
+                ("schemas".to_string(), name)
+            }
+        };
+
+        let mut name = base_name.clone();
+        let mut suffix = 2;
+        while self.taken.contains(&(section.clone(), name.clone())) {
+            name = format!("{base_name}{suffix}");
+            suffix += 1;
+        }
+        if name != base_name {
+            tracing::warn!(
+                "External definition '{base_name}' renamed to '{name}' to avoid a clash"
+            );
+        }
+        self.taken.insert((section.clone(), name.clone()));
+
+        let local = format!("#/components/{section}/{name}");
+        // Registered before recursing so that cyclic references terminate
+        self.imported.insert(key, local.clone());
+        self.total += count_refs(&target, true);
+        self.rewrite(&mut target, Some(&file))?;
+        self.components
+            .entry(section)
+            .or_default()
+            .insert(name, target);
+
+        self.report_resolved();
+        Ok(Some(local))
+    }
+
+    /// Counts a resolved reference, after the ones nested in its target
+    fn report_resolved(&mut self) {
+        self.resolved += 1;
+        report_progress("Resolving external references", self.resolved, self.total);
+    }
+}
+
+/// Counts the `$ref`s within `value` that `ExternalRefResolver::resolve` imports: all of
+/// them in an external file, only those naming another file in the root document
+fn count_refs(value: &serde_json::Value, in_external_file: bool) -> usize {
+    match value {
+        serde_json::Value::Object(map) => map
+            .iter()
+            .map(|(key, inner)| match (key.as_str(), inner.as_str()) {
+                ("$ref", Some(reference)) => {
+                    usize::from(in_external_file || reference.split('#').next() != Some(""))
+                }
+                ("$ref", None) => 0,
+                _ => count_refs(inner, in_external_file),
+            })
+            .sum(),
+        serde_json::Value::Array(items) => items
+            .iter()
+            .map(|item| count_refs(item, in_external_file))
+            .sum(),
+        _ => 0,
+    }
+}
+
+/// Loads a YAML or JSON document, choosing the format from the file extension
+fn load_document(path: &Path) -> Result<serde_json::Value> {
+    let content = fs::read_to_string(path)?;
+    if path
+        .extension()
+        .is_some_and(|ext| ext == "yaml" || ext == "yml")
+    {
+        Ok(serde_yaml::from_str(&content)?)
+    } else {
+        Ok(serde_json::from_str(&content)?)
+    }
+}
+
+/// Decodes `~1` and `~0` escapes in a JSON pointer segment
+fn unescape_pointer(segment: &str) -> String {
+    segment.replace("~1", "/").replace("~0", "~")
+}
+
+/// Operations defined on a path item, with their HTTP method
+fn path_item_operations(path_item: &PathItem) -> Vec<(&'static str, &openapiv3::Operation)> {
+    [
+        ("GET", &path_item.get),
+        ("POST", &path_item.post),
+        ("PUT", &path_item.put),
+        ("DELETE", &path_item.delete),
+        ("PATCH", &path_item.patch),
+        ("HEAD", &path_item.head),
+        ("OPTIONS", &path_item.options),
+        ("TRACE", &path_item.trace),
+    ]
+    .into_iter()
+    .filter_map(|(method, operation)| operation.as_ref().map(|operation| (method, operation)))
+    .collect()
+}
+
+fn process_operation(
+    operation: &openapiv3::Operation,
+    method: &str,
+    parameters: &[&Parameter],
+    requests: &mut Vec<RequestModel>,
+    responses: &mut Vec<ResponseModel>,
+    components: &Components,
+    backup_name: &str,
+) -> Result<Vec<ModelType>> {
+    process_named_operation(
+        operation,
+        method,
+        &operation_name(operation, backup_name),
+        parameters,
+        requests,
+        responses,
+        components,
+    )
+}
+
+/// Generates the params, request, response and header models of an operation, then
+/// those of its callbacks, which are named `Callback{OperationId}` or, without an
+/// operationId, `Callback{Operation}{CallbackName}`
+fn process_named_operation(
+    operation: &openapiv3::Operation,
+    method: &str,
+    operation_name: &str,
+    parameters: &[&Parameter],
+    requests: &mut Vec<RequestModel>,
+    responses: &mut Vec<ResponseModel>,
+    components: &Components,
+) -> Result<Vec<ModelType>> {
+    let all_schemas = &components.schemas;
+    let request_bodies = &components.request_bodies;
+    let mut inline_models = Vec::new();
+
+    // Parse parameters (already merged with path-level ones)
+    if let Some(params_model) = parse_params_model(
+        &format!("{operation_name}Params"),
+        parameters,
+        all_schemas,
+        &mut inline_models,
+    )? {
+        inline_models.push(ModelType::Params(ParamsModel {
+            deprecated: operation.deprecated,
+            ..params_model
+        }));
+    }
+
+    // Parse request body
+    if let Some(request_body_ref) = &operation.request_body {
+        // Referenced bodies carry the name of their `components/requestBodies` entry
+        let request_body_data = match request_body_ref {
+            ReferenceOr::Item(request_body) => Some((request_body, None)),
             ReferenceOr::Reference { reference } => reference
-                .strip_prefix("#/components/schemas/")
-                .and_then(|schema_name| all_schemas.get(schema_name)),
-            ReferenceOr::Item(_) => Some(schema_ref),
+                .strip_prefix("#/components/requestBodies/")
+                .and_then(|rb_name| {
+                    let request_body = request_bodies.get(rb_name)?.as_item()?;
+                    Some((request_body, Some(rb_name)))
+                }),
         };
+        if request_body_data.is_none() {
+            if let ReferenceOr::Reference { reference } = request_body_ref {
+                tracing::warn!("Unable to resolve request body reference {}", reference);
+            }
+        }
 
-        if let Some(ReferenceOr::Item(schema)) = schema_to_check {
-            if let SchemaKind::Type(Type::Object(obj)) = &schema.schema_kind {
-                all_required_fields.extend(obj.required.iter().cloned());
+        if let Some((request_body, shared_name)) = request_body_data {
+            let is_required = request_body.required;
+            for (content_type, media_type) in &request_body.content {
+                if let Some(schema) = &media_type.schema {
+                    // Form encodings get their own struct, even for a referenced schema
+                    let form_body = if content_type.starts_with("multipart/form-data") {
+                        Some((format!("{operation_name}MultipartBody"), true))
+                    } else if content_type.starts_with("application/x-www-form-urlencoded") {
+                        Some((format!("{operation_name}FormBody"), false))
+                    } else {
+                        None
+                    };
+                    let form_models = match &form_body {
+                        Some((name, file_parts)) => {
+                            parse_form_body(name, schema, all_schemas, *file_parts)?
+                        }
+                        None => None,
+                    };
+                    let schema_type = if let (Some(models), Some((name, _))) =
+                        (form_models, form_body)
+                    {
+                        inline_models.extend(models);
+                        name
+                    } else if let (ReferenceOr::Item(_), Some(shared_name)) = (schema, shared_name)
+                    {
+                        // Every operation sharing the body uses the model generated for the
+                        // component, when there is one
+                        let shared_models =
+                            parse_schema_to_model_type(shared_name, schema, all_schemas)?;
+                        if shared_models.iter().any(|m| m.name() == shared_name) {
+                            inline_models.extend(shared_models);
+                            shared_name.to_string()
+                        } else {
+                            body_type_expr(schema, all_schemas)?
+                        }
+                    } else if let ReferenceOr::Item(schema_item) = schema {
+                        if matches!(schema_item.schema_kind, SchemaKind::Type(Type::Object(_))) {
+                            let model_name = title_name(schema_item)
+                                .unwrap_or_else(|| format!("{operation_name}RequestBody"));
+                            let model_types =
+                                parse_schema_to_model_type(&model_name, schema, all_schemas)?;
+                            inline_models.extend(model_types);
+                            model_name
+                        } else {
+                            body_type_expr(schema, all_schemas)?
+                        }
+                    } else {
+                        extract_type_and_format(schema, all_schemas)?.0
+                    };
+
+                    // Merge-patch bodies are sent as a companion struct of the schema
+                    let schema_type = if content_type.starts_with("application/merge-patch+json") {
+                        match merge_patch_model(&schema_type, schema, all_schemas)? {
+                            Some(patch) => {
+                                let patch_name = patch.name.clone();
+                                inline_models.push(ModelType::Struct(patch));
+                                patch_name
+                            }
+                            None => schema_type,
+                        }
+                    } else {
+                        schema_type
+                    };
+
+                    let request = RequestModel {
+                        name: format!("{operation_name}Request"),
+                        method: method.to_string(),
+                        content_type: content_type.clone(),
+                        schema: schema_type,
+                        is_required,
+                        deprecated: operation.deprecated,
+                        examples: media_type_examples(media_type, components),
+                    };
+                    requests.push(request);
+                }
             }
         }
     }
 
-    // Try hard to replace all_fields entries that are serde_json::Value
-    // Notes:
-    //  - Most of the substitions are fairly straightforward, Value, Optional Value.
-    //  - HashMap is more complex to understand, we are replacing a Value HashMap
-    //    with an actual structure type
-    fn less_value(fields: Vec<Field>, all_fields: &mut IndexMap<String, Field>) {
-        for field in fields {
-            if let Some(existing_field) = all_fields.get_mut(&field.name) {
-                // Value
-                if existing_field.field_type == "serde_json::Value" {
-                    *existing_field = field;
-                } else if existing_field.field_type == "Option<serde_json::Value>" {
-                    existing_field.field_type = format!("Option<{}>", field.field_type);
-                // HashMap Value
-                } else if existing_field.field_type
-                    == "std::collections::HashMap<String, serde_json::Value>"
-                {
-                    *existing_field = field;
-                } else if existing_field.field_type
-                    == "Option<std::collections::HashMap<String, serde_json::Value>>"
-                {
-                    existing_field.field_type = format!("Option<{}>", field.field_type);
-                // Vec Value
-                } else if existing_field.field_type == "Vec<serde_json::Value>" {
-                    existing_field.field_type = format!("Vec<{}>", field.field_type);
-                } else if existing_field.field_type == "Option<Vec<serde_json::Value>>" {
-                    existing_field.field_type = format!("Option<Vec<{}>>", field.field_type);
+    // Parse responses; the catch-all `default` response goes last, named `Default`
+    let keyed_responses = operation
+        .responses
+        .responses
+        .iter()
+        .map(|(status, response_ref)| {
+            let range = match status {
+                StatusCode::Range(digit) => Some(*digit),
+                StatusCode::Code(_) => None,
+            };
+            (status.to_string(), range, response_ref)
+        });
+    let default_response = operation
+        .responses
+        .default
+        .iter()
+        .map(|response_ref| ("Default".to_string(), None, response_ref));
+    for (status, status_range, response_ref) in keyed_responses.chain(default_response) {
+        // Referenced responses carry the name of their `components/responses` entry
+        let response_data = match response_ref {
+            ReferenceOr::Item(response) => Some((response, None)),
+            ReferenceOr::Reference { reference } => reference
+                .strip_prefix("#/components/responses/")
+                .and_then(|response_name| {
+                    let response = components.responses.get(response_name)?.as_item()?;
+                    Some((response, Some(response_name)))
+                }),
+        };
+        if response_data.is_none() {
+            if let ReferenceOr::Reference { reference } = response_ref {
+                tracing::warn!("Unable to resolve response reference {}", reference);
+            }
+        }
+
+        if let Some((response, shared_name)) = response_data {
+            for (content_type, media_type) in &response.content {
+                // Plain text and binary bodies are not decoded, whatever their schema says
+                let schema = match raw_body_type(content_type) {
+                    Some(raw_type) => raw_type.to_string(),
+                    None => {
+                        let Some(schema) = &media_type.schema else {
+                            continue;
+                        };
+                        let mut is_array = false;
+                        let schema_type = if let ReferenceOr::Item(schema_item) = schema {
+                            if matches!(schema_item.schema_kind, SchemaKind::Type(Type::Object(_)))
+                            {
+                                // A shared response gets one model, named after the component
+                                let model_name = title_name(schema_item)
+                                    .or_else(|| shared_name.map(str::to_string))
+                                    .unwrap_or_else(|| format!("{operation_name}Response{status}"));
+                                let model_types =
+                                    parse_schema_to_model_type(&model_name, schema, all_schemas)?;
+                                inline_models.extend(model_types);
+                                model_name
+                            } else if let SchemaKind::Type(Type::Array(array)) =
+                                &schema_item.schema_kind
+                            {
+                                is_array = true;
+                                let object_items = array
+                                    .items
+                                    .as_ref()
+                                    .and_then(|items| items.as_item())
+                                    .filter(|items| {
+                                        matches!(
+                                            items.schema_kind,
+                                            SchemaKind::Type(Type::Object(_))
+                                        )
+                                    });
+                                // Only inline object items get a named struct; the item type
+                                // of other arrays is used as is (`Vec<Pet>`)
+                                if let Some(items) = object_items {
+                                    let model_name = title_name(items)
+                                        .or_else(|| shared_name.map(|name| format!("{name}Item")))
+                                        .unwrap_or_else(|| {
+                                            format!("{operation_name}ResponseArrayObject{status}")
+                                        });
+                                    let model_types = parse_schema_to_model_type(
+                                        &model_name,
+                                        schema,
+                                        all_schemas,
+                                    )?;
+                                    inline_models.extend(model_types);
+                                    model_name
+                                } else {
+                                    extract_type_and_format(schema, all_schemas)?.0
+                                }
+                            } else {
+                                extract_type_and_format(schema, all_schemas)?.0
+                            }
+                        } else {
+                            extract_type_and_format(schema, all_schemas)?.0
+                        };
+                        if is_array {
+                            format!("Vec<{}>", schema_type)
+                        } else {
+                            schema_type
+                        }
+                    }
+                };
+                let response = ResponseModel {
+                    name: operation_name.to_string(),
+                    method: method.to_string(),
+                    status_code: status.clone(),
+                    status_range,
+                    content_type: content_type.clone(),
+                    schema,
+                    description: Some(response.description.clone()),
+                    deprecated: operation.deprecated,
+                    examples: media_type_examples(media_type, components),
+                };
+                responses.push(response);
+            }
+
+            inline_models.extend(parse_response_headers(
+                &format!("{operation_name}Response{status}Headers"),
+                &response.headers,
+                components,
+            )?);
+        }
+    }
+
+    for (callback_name, callback) in &operation.callbacks {
+        for path_item in callback.values() {
+            let callback_operations = path_item_operations(path_item);
+            let has_several_methods = callback_operations.len() > 1;
+            for (method, callback_operation) in callback_operations {
+                let base_name = match &callback_operation.operation_id {
+                    Some(operation_id) => to_pascal_case(operation_id),
+                    None if has_several_methods => format!(
+                        "{operation_name}{}{}",
+                        to_pascal_case(callback_name),
+                        to_pascal_case(&method.to_lowercase())
+                    ),
+                    None => format!("{operation_name}{}", to_pascal_case(callback_name)),
+                };
+                let callback_parameters = merge_parameters(
+                    resolve_parameters(&path_item.parameters, &components.parameters),
+                    resolve_parameters(&callback_operation.parameters, &components.parameters),
+                );
+                inline_models.extend(process_named_operation(
+                    callback_operation,
+                    method,
+                    &format!("Callback{base_name}"),
+                    &callback_parameters,
+                    requests,
+                    responses,
+                    components,
+                )?);
+            }
+        }
+    }
+
+    Ok(inline_models)
+}
+
+/// The `example` and `examples` of a media type, following references to
+/// `components/examples`. Examples that only have an `externalValue` are skipped.
+fn media_type_examples(
+    media_type: &openapiv3::MediaType,
+    components: &Components,
+) -> Vec<(String, serde_json::Value)> {
+    let single = media_type
+        .example
+        .iter()
+        .map(|value| ("example".to_string(), value.clone()));
+    let named = media_type.examples.iter().filter_map(|(name, example)| {
+        let example = match example {
+            ReferenceOr::Item(example) => example,
+            ReferenceOr::Reference { reference } => reference
+                .strip_prefix("#/components/examples/")
+                .and_then(|example_name| components.examples.get(example_name))
+                .and_then(|example| example.as_item())?,
+        };
+        Some((name.clone(), example.value.clone()?))
+    });
+    single.chain(named).collect()
+}
+
+/// Collects the JSON examples of component schemas, request bodies and responses with the
+/// type each one should deserialize into. Request and response examples are named after
+/// the generated request/response type, with the example name appended for `examples`
+/// entries; non-JSON media types and schemas without a generated model are skipped.
+pub fn parse_examples(
+    openapi: &OpenAPI,
+    models: &[ModelType],
+    requests: &[RequestModel],
+    responses: &[ResponseModel],
+) -> Vec<ExampleModel> {
+    let model_names: HashSet<&str> = models.iter().map(ModelType::name).collect();
+    let mut used_names = HashSet::new();
+    let mut examples = Vec::new();
+    let mut push = |name: String, type_name: &str, value: &serde_json::Value| {
+        if used_names.insert(name.clone()) {
+            examples.push(ExampleModel {
+                name,
+                type_name: type_name.to_string(),
+                value: value.clone(),
+            });
+        }
+    };
+
+    if let Some(components) = &openapi.components {
+        for (name, schema) in &components.schemas {
+            let example = schema
+                .as_item()
+                .and_then(|schema| schema.schema_data.example.as_ref());
+            if let Some(value) = example.filter(|_| model_names.contains(name.as_str())) {
+                push(name.clone(), name, value);
+            }
+        }
+    }
+
+    let bodies = requests
+        .iter()
+        .map(|r| (r.name.clone(), &r.content_type, &r.schema, &r.examples))
+        .chain(responses.iter().map(|r| {
+            let name = format!("{}{}", r.name, r.status_code);
+            (name, &r.content_type, &r.schema, &r.examples)
+        }));
+    for (type_name, content_type, schema, body_examples) in bodies {
+        if !is_json_media_type(content_type) {
+            continue;
+        }
+        for (example_name, value) in body_examples {
+            let name = if example_name == "example" {
+                type_name.clone()
+            } else {
+                format!("{type_name}{}", to_pascal_case(example_name))
+            };
+            push(name, schema, value);
+        }
+    }
+
+    examples
+}
+
+fn is_json_media_type(content_type: &str) -> bool {
+    let media_type = content_type.split(';').next().unwrap_or_default().trim();
+    media_type == "application/json" || media_type.ends_with("+json")
+}
+
+/// Rust type of a body schema without a generated model; inline arrays are wrapped in `Vec`,
+/// which extract_type_and_format leaves to the caller
+fn body_type_expr(
+    schema: &ReferenceOr<Schema>,
+    all_schemas: &IndexMap<String, ReferenceOr<Schema>>,
+) -> Result<String> {
+    let type_name = extract_type_and_format(schema, all_schemas)?.0;
+    let is_array = matches!(
+        schema,
+        ReferenceOr::Item(item @ Schema {
+            schema_kind: SchemaKind::Type(Type::Array(_)),
+            ..
+        }) if !is_tuple(item)
+    );
+    Ok(if is_array {
+        format!("Vec<{type_name}>")
+    } else {
+        type_name
+    })
+}
+
+/// Rust type of a body that is passed through undecoded: `String` for `text/plain`,
+/// `Vec<u8>` for `application/octet-stream`
+fn raw_body_type(content_type: &str) -> Option<&'static str> {
+    let media_type = content_type.split(';').next().unwrap_or_default().trim();
+    match media_type {
+        "text/plain" => Some("String"),
+        "application/octet-stream" => Some("Vec<u8>"),
+        _ => None,
+    }
+}
+
+/// Builds the struct of a form-encoded body. With `file_parts` (`multipart/form-data`),
+/// `format: binary` properties become `FilePart`, or `Vec<FilePart>` for arrays.
+/// Returns `None` when the body schema is not an object.
+fn parse_form_body(
+    name: &str,
+    schema: &ReferenceOr<Schema>,
+    all_schemas: &IndexMap<String, ReferenceOr<Schema>>,
+    file_parts: bool,
+) -> Result<Option<Vec<ModelType>>> {
+    let resolved = match schema {
+        ReferenceOr::Reference { reference } => reference
+            .strip_prefix("#/components/schemas/")
+            .and_then(|schema_name| all_schemas.get(schema_name))
+            .and_then(|schema| schema.as_item()),
+        ReferenceOr::Item(schema) => Some(schema),
+    };
+    let Some(object_schema) =
+        resolved.filter(|s| matches!(s.schema_kind, SchemaKind::Type(Type::Object(_))))
+    else {
+        return Ok(None);
+    };
+
+    let mut models =
+        parse_schema_to_model_type(name, &ReferenceOr::Item(object_schema.clone()), all_schemas)?;
+    if !file_parts {
+        return Ok(Some(models));
+    }
+    for model in &mut models {
+        if let ModelType::Struct(model) = model {
+            if model.name != name {
+                continue;
+            }
+            for field in &mut model.fields {
+                if field.field_type == "Vec<u8>" && field.format == "binary" {
+                    field.field_type = "FilePart".to_string();
+                }
+            }
+        }
+    }
+    Ok(Some(models))
+}
+
+/// Builds the `{Base}Patch` companion of an `application/merge-patch+json` body (RFC 7396):
+/// every field of the base schema but the read-only ones, as `Option<Option<T>>` so an
+/// absent field is left alone and `null` removes it. Nested objects keep their base types.
+/// The struct is only ever sent, so its write-only fields get no `--split-rw` variants.
+/// Returns `None` when the body is not an object schema.
+fn merge_patch_model(
+    base_name: &str,
+    schema: &ReferenceOr<Schema>,
+    all_schemas: &IndexMap<String, ReferenceOr<Schema>>,
+) -> Result<Option<Model>> {
+    let resolved = match schema {
+        ReferenceOr::Reference { reference } => reference
+            .strip_prefix("#/components/schemas/")
+            .and_then(|schema_name| all_schemas.get(schema_name))
+            .and_then(|schema| schema.as_item()),
+        ReferenceOr::Item(schema) => Some(schema),
+    };
+    let Some(resolved) = resolved else {
+        return Ok(None);
+    };
+
+    let models =
+        parse_schema_to_model_type(base_name, &ReferenceOr::Item(resolved.clone()), all_schemas)?;
+    let base = models.into_iter().find_map(|model| match model {
+        ModelType::Struct(model) if model.name == base_name => {
+            Some((model.fields, model.deny_unknown_fields))
+        }
+        ModelType::Composition(comp) if comp.name == base_name => Some((comp.all_fields, false)),
+        _ => None,
+    });
+    let Some((base_fields, deny_unknown_fields)) = base else {
+        return Ok(None);
+    };
+
+    let fields = base_fields
+        .into_iter()
+        .filter(|field| !field.read_only)
+        .map(|field| {
+            let field = Field {
+                write_only: false,
+                ..field
+            };
+            if field.should_flatten() {
+                return field;
+            }
+            Field {
+                is_required: false,
+                is_nullable: true,
+                default: None,
+                double_option: true,
+                ..field
+            }
+        })
+        .collect();
+    Ok(Some(Model {
+        name: format!("{base_name}Patch"),
+        fields,
+        custom_attrs: None,
+        derives: None,
+        description: Some(format!(
+            "JSON Merge Patch of `{base_name}`: `None` leaves a field unchanged, `Some(None)` removes it"
+        )),
+        xml_name: None,
+        deny_unknown_fields,
+    }))
+}
+
+/// Builds a struct for the headers of a single response, resolving
+/// `#/components/headers/...` references. `Content-Type` is ignored as the spec requires.
+fn parse_response_headers(
+    name: &str,
+    headers: &IndexMap<String, ReferenceOr<openapiv3::Header>>,
+    components: &Components,
+) -> Result<Vec<ModelType>> {
+    let mut models = Vec::new();
+    let mut fields = Vec::new();
+
+    for (header_name, header_ref) in headers {
+        if header_name.eq_ignore_ascii_case("content-type") {
+            continue;
+        }
+
+        let mut current = header_ref;
+        let mut visited = HashSet::new();
+        let header = loop {
+            match current {
+                ReferenceOr::Item(header) => break Some(header),
+                ReferenceOr::Reference { reference } => {
+                    let target = reference
+                        .strip_prefix("#/components/headers/")
+                        .filter(|target| visited.insert(*target))
+                        .and_then(|target| components.headers.get(target));
+                    match target {
+                        Some(next) => current = next,
+                        None => {
+                            tracing::warn!("Unable to resolve header reference '{reference}'");
+                            break None;
+                        }
+                    }
+                }
+            }
+        };
+        let Some(header) = header else {
+            continue;
+        };
+
+        let schema = match &header.format {
+            ParameterSchemaOrContent::Schema(schema) => schema.clone(),
+            ParameterSchemaOrContent::Content(content) => match content
+                .values()
+                .next()
+                .and_then(|media_type| media_type.schema.clone())
+            {
+                Some(schema) => schema,
+                None => continue,
+            },
+        };
+        let (field_info, field_models) =
+            extract_field_info(name, header_name, &schema, &components.schemas)?;
+        models.extend(field_models);
+
+        fields.push(Field {
+            name: header_name.clone(),
+            field_type: field_info.field_type,
+            format: field_info.format,
+            is_required: header.required,
+            is_nullable: field_info.is_nullable,
+            is_array_ref: field_info.is_array_ref,
+            description: header.description.clone().or(field_info.description),
+            serde_with: field_info.serde_with,
+            read_only: false,
+            write_only: false,
+            default: field_info.default,
+            constraints: field_info.constraints,
+            deprecated: header.deprecated.unwrap_or(false) || field_info.deprecated,
+            rust_uses: field_info.rust_uses,
+            flatten: false,
+            xml: field_info.xml,
+            double_option: false,
+            newtype: field_info.newtype,
+            rename: None,
+        });
+    }
+
+    if !fields.is_empty() {
+        models.push(ModelType::Struct(Model {
+            name: name.to_string(),
+            fields,
+            custom_attrs: None,
+            derives: None,
+            description: None,
+            xml_name: None,
+            deny_unknown_fields: false,
+        }));
+    }
+
+    Ok(models)
+}
+
+fn parse_schema_to_model_type(
+    name: &str,
+    schema: &ReferenceOr<Schema>,
+    all_schemas: &IndexMap<String, ReferenceOr<Schema>>,
+) -> Result<Vec<ModelType>> {
+    match schema {
+        ReferenceOr::Reference { .. } => Ok(Vec::new()),
+        ReferenceOr::Item(schema) => {
+            if let Some(base) = without_not(schema) {
+                tracing::warn!("Schema '{name}' uses `not`; generating its base type only");
+                return parse_schema_to_model_type(name, &ReferenceOr::Item(base), all_schemas);
+            }
+
+            // A bare `not` accepts anything that isn't the negated schema
+            if matches!(schema.schema_kind, SchemaKind::Not { .. }) {
+                tracing::warn!("Schema '{name}' only uses `not`; generating serde_json::Value");
+                return Ok(vec![ModelType::TypeAlias(TypeAliasModel {
+                    name: to_pascal_case(name),
+                    target_type: "serde_json::Value".to_string(),
+                    description: schema.schema_data.description.clone(),
+                    custom_attrs: extract_custom_attrs(schema),
+                    rust_uses: Vec::new(),
+                })]);
+            }
+
+            if let Some(rust_type) = schema.schema_data.extensions.get(X_RUST_TYPE) {
+                if let Some(type_str) = rust_type.as_str() {
+                    return Ok(vec![ModelType::TypeAlias(TypeAliasModel {
+                        name: to_pascal_case(name),
+                        target_type: type_str.to_string(),
+                        description: schema.schema_data.description.clone(),
+                        custom_attrs: extract_custom_attrs(schema),
+                        rust_uses: extract_rust_uses(schema),
+                    })]);
+                }
+            }
+
+            match &schema.schema_kind {
+                // regular objects
+                SchemaKind::Type(Type::Object(obj)) => {
+                    // Special case: object with only additionalProperties (no regular properties)
+                    if let (true, Some(additional_props)) =
+                        (obj.properties.is_empty(), &obj.additional_properties)
+                    {
+                        // Inline unions of value types (e.g. from patternProperties)
+                        // become a `{Name}Value` enum
+                        let mut models = Vec::new();
+                        let value_type = match additional_props {
+                            AdditionalProperties::Schema(value_schema)
+                                if matches!(
+                                    &**value_schema,
+                                    ReferenceOr::Item(Schema {
+                                        schema_kind: SchemaKind::OneOf { .. }
+                                            | SchemaKind::AnyOf { .. },
+                                        ..
+                                    })
+                                ) =>
+                            {
+                                let value_name = format!("{}Value", to_pascal_case(name));
+                                models = parse_schema_to_model_type(
+                                    &value_name,
+                                    value_schema,
+                                    all_schemas,
+                                )?;
+                                value_name
+                            }
+                            _ => map_value_type(additional_props, all_schemas)?,
+                        };
+                        models.push(ModelType::TypeAlias(TypeAliasModel {
+                            name: to_pascal_case(name),
+                            target_type: format!("std::collections::HashMap<String, {value_type}>"),
+                            description: schema.schema_data.description.clone(),
+                            custom_attrs: extract_custom_attrs(schema),
+                            rust_uses: Vec::new(),
+                        }));
+                        return Ok(models);
+                    }
+
+                    let mut fields = Vec::new();
+                    let mut inline_models = Vec::new();
+
+                    // Process regular properties
+                    for (field_name, field_schema) in &obj.properties {
+                        let (field_info, field_models) = match field_schema {
+                            ReferenceOr::Item(boxed_schema) => extract_field_info(
+                                name,
+                                field_name,
+                                &ReferenceOr::Item((**boxed_schema).clone()),
+                                all_schemas,
+                            )?,
+                            ReferenceOr::Reference { reference } => extract_field_info(
+                                name,
+                                field_name,
+                                &ReferenceOr::Reference {
+                                    reference: reference.clone(),
+                                },
+                                all_schemas,
+                            )?,
+                        };
+                        inline_models.extend(field_models);
+                        let is_required = obj.required.contains(field_name);
+                        fields.push(Field {
+                            name: field_name.clone(),
+                            field_type: field_info.field_type,
+                            format: field_info.format,
+                            is_required,
+                            is_array_ref: field_info.is_array_ref,
+                            is_nullable: field_info.is_nullable,
+                            description: field_info.description,
+                            serde_with: field_info.serde_with,
+                            read_only: field_info.read_only,
+                            write_only: field_info.write_only,
+                            default: field_info.default,
+                            constraints: field_info.constraints,
+                            deprecated: field_info.deprecated,
+                            rust_uses: field_info.rust_uses,
+                            flatten: false,
+                            xml: field_info.xml,
+                            double_option: false,
+                            newtype: field_info.newtype,
+                            rename: None,
+                        });
+                    }
+
+                    let mut models = inline_models;
+                    if obj.properties.is_empty() && obj.additional_properties.is_none() {
+                        models.push(ModelType::Struct(Model {
+                            name: to_pascal_case(name),
+                            fields: vec![],
+                            custom_attrs: extract_custom_attrs(schema),
+                            derives: extract_derives(schema),
+                            description: schema.schema_data.description.clone(),
+                            xml_name: xml_root_name(name, schema),
+                            deny_unknown_fields: matches!(
+                                obj.additional_properties,
+                                Some(AdditionalProperties::Any(false))
+                            ),
+                        }));
+                    } else if !fields.is_empty() {
+                        models.push(ModelType::Struct(Model {
+                            name: to_pascal_case(name),
+                            fields,
+                            custom_attrs: extract_custom_attrs(schema),
+                            derives: extract_derives(schema),
+                            description: schema.schema_data.description.clone(),
+                            xml_name: xml_root_name(name, schema),
+                            deny_unknown_fields: matches!(
+                                obj.additional_properties,
+                                Some(AdditionalProperties::Any(false))
+                            ),
+                        }));
+                    }
+                    Ok(models)
+                }
+
+                // allOf
+                SchemaKind::AllOf { all_of } => {
+                    let (all_fields, inline_models) =
+                        resolve_all_of_fields(name, all_of, all_schemas)?;
+                    let mut models = inline_models;
+
+                    if !all_fields.is_empty() {
+                        models.push(ModelType::Composition(CompositionModel {
+                            name: to_pascal_case(name),
+                            all_fields,
+                            embedded_fields: embed_all_of_members(name, all_of, all_schemas)?,
+                            bases: all_of_bases(all_of),
+                            xml_name: xml_root_name(name, schema),
+                            custom_attrs: extract_custom_attrs(schema),
+                            derives: extract_derives(schema),
+                            description: schema.schema_data.description.clone(),
+                        }));
+                    }
+
+                    Ok(models)
+                }
+
+                // oneOf
+                SchemaKind::OneOf { one_of } => {
+                    parse_union(name, schema, one_of, UnionType::OneOf, all_schemas)
+                }
+
+                // anyOf
+                SchemaKind::AnyOf { any_of } => {
+                    parse_union(name, schema, any_of, UnionType::AnyOf, all_schemas)
+                }
+
+                // oneOf/anyOf mixed with other keywords such as additionalProperties:
+                // the union is generated and the remaining keywords are ignored
+                SchemaKind::Any(any) if !any.one_of.is_empty() => {
+                    parse_union(name, schema, &any.one_of, UnionType::OneOf, all_schemas)
+                }
+                SchemaKind::Any(any) if !any.any_of.is_empty() => {
+                    parse_union(name, schema, &any.any_of, UnionType::AnyOf, all_schemas)
+                }
+
+                // enum strings
+                SchemaKind::Type(Type::String(string_type)) => {
+                    if !string_type.enumeration.is_empty() {
+                        let variants: Vec<String> = string_type
+                            .enumeration
+                            .iter()
+                            .filter_map(|value| value.clone())
+                            .collect();
+
+                        if !variants.is_empty() {
+                            let models = vec![ModelType::Enum(EnumModel {
+                                name: to_pascal_case(name),
+                                variants,
+                                description: schema.schema_data.description.clone(),
+                                custom_attrs: extract_custom_attrs(schema),
+                                derives: extract_derives(schema),
+                                extensible: is_extensible_enum(schema),
+                                integer_values: Vec::new(),
+                                mixed_values: Vec::new(),
+                            })];
+
+                            return Ok(models);
+                        }
+                    }
+                    Ok(Vec::new())
+                }
+
+                // untyped enums, including ones mixing strings, numbers and booleans
+                SchemaKind::Any(_) if enum_literals(schema).is_some() => {
+                    let values = enum_literals(schema).unwrap_or_default();
+                    Ok(vec![ModelType::Enum(EnumModel {
+                        extensible: is_extensible_enum(schema),
+                        ..literal_enum(
+                            &to_pascal_case(name),
+                            values,
+                            schema.schema_data.description.clone(),
+                            extract_custom_attrs(schema),
+                            extract_derives(schema),
+                        )
+                    })])
+                }
+
+                // integer-coded enums
+                SchemaKind::Type(Type::Integer(integer_type))
+                    if integer_type.enumeration.iter().flatten().next().is_some() =>
+                {
+                    Ok(vec![ModelType::Enum(integer_enum(
+                        &to_pascal_case(name),
+                        integer_type,
+                        schema,
+                    ))])
+                }
+
+                SchemaKind::Type(Type::Array(_)) if is_tuple(schema) => {
+                    let Some(target_type) = tuple_type(schema, all_schemas)? else {
+                        return Ok(Vec::new());
+                    };
+                    Ok(vec![ModelType::TypeAlias(TypeAliasModel {
+                        name: to_pascal_case(name),
+                        target_type,
+                        description: schema.schema_data.description.clone(),
+                        custom_attrs: extract_custom_attrs(schema),
+                        rust_uses: Vec::new(),
+                    })])
+                }
+
+                SchemaKind::Type(Type::Array(array)) => {
+                    let mut models = Vec::new();
+                    let array_name = to_pascal_case(name);
+
+                    let items = match &array.items {
+                        Some(items) => items,
+                        None => return Ok(Vec::new()),
+                    };
+
+                    match items {
+                        ReferenceOr::Item(item_schema) => match &item_schema.schema_kind {
+                            SchemaKind::Type(Type::Object(_obj)) => {
+                                let array_model_schema = ReferenceOr::Item((**item_schema).clone());
+                                let array_object_models = parse_schema_to_model_type(
+                                    name,
+                                    &array_model_schema,
+                                    all_schemas,
+                                )?;
+                                models.extend(array_object_models);
+                            }
+
+                            SchemaKind::OneOf { one_of } => {
+                                let item_type_name = format!("{array_name}Item");
+
+                                let (variants, inline_models) =
+                                    resolve_union_variants(&item_type_name, one_of, all_schemas)?;
+
+                                models.extend(inline_models);
+
+                                models.push(ModelType::Union(UnionModel {
+                                    name: item_type_name.clone(),
+                                    variants,
+                                    union_type: UnionType::OneOf,
+                                    custom_attrs: extract_custom_attrs(item_schema),
+                                    derives: extract_derives(item_schema),
+                                    description: item_schema.schema_data.description.clone(),
+                                }));
+
+                                models.push(ModelType::TypeAlias(TypeAliasModel {
+                                    name: array_name,
+                                    target_type: format!("Vec<{item_type_name}>"),
+                                    description: schema.schema_data.description.clone(),
+                                    custom_attrs: extract_custom_attrs(schema),
+                                    rust_uses: Vec::new(),
+                                }));
+                            }
+
+                            SchemaKind::Type(Type::String(s)) if !s.enumeration.is_empty() => {
+                                let item_type_name = format!("{array_name}Item");
+
+                                let variants: Vec<String> =
+                                    s.enumeration.iter().filter_map(|v| v.clone()).collect();
+
+                                models.push(ModelType::Enum(EnumModel {
+                                    name: item_type_name.clone(),
+                                    variants,
+                                    description: item_schema.schema_data.description.clone(),
+                                    custom_attrs: extract_custom_attrs(item_schema),
+                                    derives: extract_derives(item_schema),
+                                    extensible: is_extensible_enum(item_schema),
+                                    integer_values: Vec::new(),
+                                    mixed_values: Vec::new(),
+                                }));
+
+                                models.push(ModelType::TypeAlias(TypeAliasModel {
+                                    name: array_name,
+                                    target_type: format!("Vec<{item_type_name}>"),
+                                    description: schema.schema_data.description.clone(),
+                                    custom_attrs: extract_custom_attrs(schema),
+                                    rust_uses: Vec::new(),
+                                }));
+                            }
+
+                            SchemaKind::Type(Type::Integer(n)) if !n.enumeration.is_empty() => {
+                                let item_type_name = format!("{array_name}Item");
+
+                                models.push(ModelType::Enum(integer_enum(
+                                    &item_type_name,
+                                    n,
+                                    item_schema,
+                                )));
+
+                                models.push(ModelType::TypeAlias(TypeAliasModel {
+                                    name: array_name,
+                                    target_type: format!("Vec<{item_type_name}>"),
+                                    description: schema.schema_data.description.clone(),
+                                    custom_attrs: extract_custom_attrs(schema),
+                                    rust_uses: Vec::new(),
+                                }));
+                            }
+
+                            _ => {
+                                let normalized_items = match items {
+                                    ReferenceOr::Item(boxed_schema) => {
+                                        ReferenceOr::Item((**boxed_schema).clone())
+                                    }
+                                    ReferenceOr::Reference { reference } => {
+                                        ReferenceOr::Reference {
+                                            reference: reference.clone(),
+                                        }
+                                    }
+                                };
+
+                                let (inner_type, _) =
+                                    extract_type_and_format(&normalized_items, all_schemas)?;
+
+                                models.push(ModelType::TypeAlias(TypeAliasModel {
+                                    name: array_name,
+                                    target_type: format!("Vec<{inner_type}>"),
+                                    description: schema.schema_data.description.clone(),
+                                    custom_attrs: extract_custom_attrs(schema),
+                                    rust_uses: Vec::new(),
+                                }));
+                            }
+                        },
+
+                        ReferenceOr::Reference { .. } => {
+                            let normalized_items = match items {
+                                ReferenceOr::Item(boxed_schema) => {
+                                    ReferenceOr::Item((**boxed_schema).clone())
+                                }
+                                ReferenceOr::Reference { reference } => ReferenceOr::Reference {
+                                    reference: reference.clone(),
+                                },
+                            };
+
+                            let (inner_type, _) =
+                                extract_type_and_format(&normalized_items, all_schemas)?;
+
+                            models.push(ModelType::TypeAlias(TypeAliasModel {
+                                name: array_name,
+                                target_type: format!("Vec<{inner_type}>"),
+                                description: schema.schema_data.description.clone(),
+                                custom_attrs: extract_custom_attrs(schema),
+                                rust_uses: Vec::new(),
+                            }));
+                        }
+                    }
+
+                    Ok(models)
+                }
+
+                _ => Ok(Vec::new()),
+            }
+        }
+    }
+}
+
+/// Alias for a component schema that is a bare primitive, e.g.
+/// `CorrelationId: {type: string, format: uuid}` → `pub type CorrelationId = Uuid;`
+fn primitive_alias(
+    name: &str,
+    schema: &ReferenceOr<Schema>,
+    all_schemas: &IndexMap<String, ReferenceOr<Schema>>,
+) -> Result<Option<ModelType>> {
+    let ReferenceOr::Item(item) = schema else {
+        return Ok(None);
+    };
+    if !matches!(
+        item.schema_kind,
+        SchemaKind::Type(Type::String(_) | Type::Integer(_) | Type::Number(_) | Type::Boolean(_))
+    ) {
+        return Ok(None);
+    }
+    let (target_type, _) = extract_type_and_format(schema, all_schemas)?;
+    Ok(Some(ModelType::TypeAlias(TypeAliasModel {
+        name: to_pascal_case(name),
+        target_type,
+        description: item.schema_data.description.clone(),
+        custom_attrs: extract_custom_attrs(item),
+        rust_uses: Vec::new(),
+    })))
+}
+
+/// Merges path-level parameters with an operation's own. Operation parameters
+/// override path-level ones with the same name and location.
+fn merge_parameters<'a>(
+    path_level: Vec<&'a Parameter>,
+    operation_level: Vec<&'a Parameter>,
+) -> Vec<&'a Parameter> {
+    let key = |parameter: &Parameter| {
+        let location = match parameter {
+            Parameter::Path { .. } => "path",
+            Parameter::Query { .. } => "query",
+            Parameter::Header { .. } => "header",
+            Parameter::Cookie { .. } => "cookie",
+        };
+        (parameter.parameter_data_ref().name.clone(), location)
+    };
+
+    let overridden: HashSet<_> = operation_level.iter().map(|p| key(p)).collect();
+    // A parameter referenced twice (directly and through an alias) is declared once
+    let mut seen = HashSet::new();
+    path_level
+        .into_iter()
+        .filter(|parameter| !overridden.contains(&key(parameter)))
+        .chain(operation_level)
+        .filter(|parameter| seen.insert(key(parameter)))
+        .collect()
+}
+
+/// Builds the `{OperationId}Params` model from an operation's resolved parameters.
+/// Returns `None` when the operation has no parameters.
+fn parse_params_model(
+    name: &str,
+    parameters: &[&Parameter],
+    all_schemas: &IndexMap<String, ReferenceOr<Schema>>,
+    inline_models: &mut Vec<ModelType>,
+) -> Result<Option<ParamsModel>> {
+    let mut fields = Vec::new();
+
+    for parameter in parameters {
+        let location = match parameter {
+            Parameter::Path { .. } => ParameterLocation::Path,
+            Parameter::Query { .. } => ParameterLocation::Query,
+            Parameter::Header { .. } => ParameterLocation::Header,
+            Parameter::Cookie { .. } => ParameterLocation::Cookie,
+        };
+        let data = parameter.parameter_data_ref();
+
+        let schema = match &data.format {
+            ParameterSchemaOrContent::Schema(schema) => Some(schema.clone()),
+            ParameterSchemaOrContent::Content(content) => content
+                .values()
+                .next()
+                .and_then(|media_type| media_type.schema.clone()),
+        };
+        let (field_info, field_models) = match &schema {
+            Some(schema) => extract_field_info(name, &data.name, schema, all_schemas)?,
+            None => extract_field_info(
+                name,
+                &data.name,
+                &ReferenceOr::Item(Schema {
+                    schema_data: Default::default(),
+                    schema_kind: SchemaKind::Any(Default::default()),
+                }),
+                all_schemas,
+            )?,
+        };
+        inline_models.extend(field_models);
+
+        fields.push(ParamField {
+            field: Field {
+                name: data.name.clone(),
+                field_type: field_info.field_type,
+                format: field_info.format,
+                // Path parameters are always required
+                is_required: data.required || location == ParameterLocation::Path,
+                is_nullable: field_info.is_nullable,
+                is_array_ref: field_info.is_array_ref,
+                description: data.description.clone().or(field_info.description),
+                serde_with: field_info.serde_with,
+                read_only: false,
+                write_only: false,
+                default: field_info.default,
+                constraints: field_info.constraints,
+                deprecated: data.deprecated.unwrap_or(false) || field_info.deprecated,
+                rust_uses: field_info.rust_uses,
+                flatten: false,
+                xml: field_info.xml,
+                double_option: false,
+                newtype: field_info.newtype,
+                rename: None,
+            },
+            location,
+        });
+    }
+
+    if fields.is_empty() {
+        return Ok(None);
+    }
+
+    Ok(Some(ParamsModel {
+        name: name.to_string(),
+        fields,
+        deprecated: false,
+        path: None,
+    }))
+}
+
+fn parse_union(
+    name: &str,
+    schema: &Schema,
+    members: &[ReferenceOr<Schema>],
+    union_type: UnionType,
+    all_schemas: &IndexMap<String, ReferenceOr<Schema>>,
+) -> Result<Vec<ModelType>> {
+    let (variants, inline_models) = resolve_union_variants(name, members, all_schemas)?;
+    let mut models = inline_models;
+
+    models.push(ModelType::Union(UnionModel {
+        name: to_pascal_case(name),
+        variants,
+        union_type,
+        custom_attrs: extract_custom_attrs(schema),
+        derives: extract_derives(schema),
+        description: schema.schema_data.description.clone(),
+    }));
+
+    Ok(models)
+}
+
+fn extract_type_and_format(
+    schema: &ReferenceOr<Schema>,
+    all_schemas: &IndexMap<String, ReferenceOr<Schema>>,
+) -> Result<(String, String)> {
+    if let ReferenceOr::Item(item) = schema {
+        if let Some(base) = without_not(item) {
+            return extract_type_and_format(&ReferenceOr::Item(base), all_schemas);
+        }
+    }
+
+    match schema {
+        ReferenceOr::Reference { reference } => {
+            let type_name = reference.split('/').next_back().unwrap_or("Unknown");
+
+            if let Some(ReferenceOr::Item(schema)) = all_schemas.get(type_name) {
+                if matches!(schema.schema_kind, SchemaKind::OneOf { .. }) {
+                    return Ok((to_pascal_case(type_name), "oneOf".to_string()));
+                }
+            }
+            Ok((to_pascal_case(type_name), "reference".to_string()))
+        }
+
+        ReferenceOr::Item(schema) => match &schema.schema_kind {
+            SchemaKind::Type(Type::String(string_type)) => match &string_type.format {
+                VariantOrUnknownOrEmpty::Item(fmt) => match fmt {
+                    StringFormat::DateTime => {
+                        Ok(("DateTime<Utc>".to_string(), "date-time".to_string()))
+                    }
+                    StringFormat::Date => Ok(("NaiveDate".to_string(), "date".to_string())),
+                    StringFormat::Binary => Ok(("Vec<u8>".to_string(), "binary".to_string())),
+                    StringFormat::Byte => Ok(("Base64Bytes".to_string(), "byte".to_string())),
+                    _ => Ok(("String".to_string(), string_format_name(fmt).to_string())),
+                },
+                VariantOrUnknownOrEmpty::Unknown(unknown_format) => {
+                    if unknown_format.to_lowercase() == "uuid" {
+                        Ok(("Uuid".to_string(), "uuid".to_string()))
+                    } else if unknown_format == "time" {
+                        Ok(("NaiveTime".to_string(), "time".to_string()))
+                    } else if unknown_format == "ipv4" {
+                        Ok(("std::net::Ipv4Addr".to_string(), "ipv4".to_string()))
+                    } else if unknown_format == "ipv6" {
+                        Ok(("std::net::Ipv6Addr".to_string(), "ipv6".to_string()))
+                    } else {
+                        Ok(("String".to_string(), unknown_format.clone()))
+                    }
+                }
+                _ => Ok(("String".to_string(), "string".to_string())),
+            },
+            SchemaKind::Type(Type::Integer(integer_type)) => {
+                let (rust_type, mut format) = integer_type_and_format(integer_type);
+                // i64 holds every 18-digit number
+                let precision = schema.schema_data.extensions.get(X_PRECISION);
+                if precision
+                    .and_then(|p| p.as_u64())
+                    .is_some_and(|digits| digits > 18)
+                {
+                    format = "bigint".to_string();
+                }
+                Ok((rust_type.to_string(), format))
+            }
+            SchemaKind::Type(Type::Number(number_type)) => {
+                let (rust_type, format) = number_type_and_format(number_type);
+                Ok((rust_type.to_string(), format))
+            }
+            SchemaKind::Type(Type::Boolean(_)) => Ok(("bool".to_string(), "boolean".to_string())),
+            SchemaKind::Type(Type::Array(_)) if is_tuple(schema) => Ok((
+                tuple_type(schema, all_schemas)?.unwrap_or_else(|| "serde_json::Value".to_string()),
+                "tuple".to_string(),
+            )),
+            // Elements after the `prefixItems` take the `items` schema: no single type fits
+            SchemaKind::Type(Type::Array(_))
+                if schema.schema_data.extensions.contains_key(X_PREFIX_ITEMS) =>
+            {
+                Ok(("serde_json::Value".to_string(), "array".to_string()))
+            }
+            SchemaKind::Type(Type::Array(arr)) => {
+                if let Some(items) = &arr.items {
+                    match items {
+                        // The caller wraps the outer array; nested arrays keep their own Vec
+                        ReferenceOr::Item(boxed_schema)
+                            if matches!(
+                                boxed_schema.schema_kind,
+                                SchemaKind::Type(Type::Array(_))
+                            ) =>
+                        {
+                            let (inner_type, format) = extract_type_and_format(
+                                &ReferenceOr::Item((**boxed_schema).clone()),
+                                all_schemas,
+                            )?;
+                            Ok((format!("Vec<{inner_type}>"), format))
+                        }
+                        ReferenceOr::Item(boxed_schema) => extract_type_and_format(
+                            &ReferenceOr::Item((**boxed_schema).clone()),
+                            all_schemas,
+                        ),
+
+                        ReferenceOr::Reference { reference } => extract_type_and_format(
+                            &ReferenceOr::Reference {
+                                reference: reference.clone(),
+                            },
+                            all_schemas,
+                        ),
+                    }
+                } else {
+                    Ok(("serde_json::Value".to_string(), "array".to_string()))
+                }
+            }
+            SchemaKind::Type(Type::Object(obj)) => match &obj.additional_properties {
+                Some(additional_props)
+                    if obj.properties.is_empty()
+                        && !matches!(additional_props, AdditionalProperties::Any(false)) =>
+                {
+                    Ok((
+                        format!(
+                            "std::collections::HashMap<String, {}>",
+                            map_value_type(additional_props, all_schemas)?
+                        ),
+                        "map".to_string(),
+                    ))
+                }
+                _ => Ok(("serde_json::Value".to_string(), "object".to_string())),
+            },
+            // `allOf: [$ref]` is the usual way to attach `nullable` to a reference
+            SchemaKind::AllOf { all_of } if all_of.len() == 1 => {
+                extract_type_and_format(&all_of[0], all_schemas)
+            }
+            _ => Ok(("serde_json::Value".to_string(), "unknown".to_string())),
+        },
+    }
+}
+
+/// Builds an enum for an integer schema with `enum` values, one `Value{n}` variant per value
+fn integer_enum(name: &str, integer_type: &IntegerType, schema: &Schema) -> EnumModel {
+    let integer_values: Vec<i64> = integer_type.enumeration.iter().flatten().copied().collect();
+    EnumModel {
+        name: name.to_string(),
+        variants: integer_values
+            .iter()
+            .map(|value| integer_variant_name(*value))
+            .collect(),
+        description: schema.schema_data.description.clone(),
+        custom_attrs: extract_custom_attrs(schema),
+        derives: extract_derives(schema),
+        extensible: is_extensible_enum(schema),
+        integer_values,
+        mixed_values: Vec::new(),
+    }
+}
+
+/// Variant name for an integer enum value, spelling out the sign of negative values
+fn integer_variant_name(value: i64) -> String {
+    if value < 0 {
+        format!("ValueMinus{}", value.unsigned_abs())
+    } else {
+        format!("Value{value}")
+    }
+}
+
+/// Returns the `enum` values of a string, integer, number, boolean or untyped schema
+fn enum_literals(schema: &Schema) -> Option<Vec<serde_json::Value>> {
+    let values: Vec<serde_json::Value> = match &schema.schema_kind {
+        SchemaKind::Type(Type::String(s)) => s
+            .enumeration
+            .iter()
+            .flatten()
+            .map(|v| v.as_str().into())
+            .collect(),
+        SchemaKind::Type(Type::Integer(n)) => n
+            .enumeration
+            .iter()
+            .flatten()
+            .map(|v| (*v).into())
+            .collect(),
+        SchemaKind::Type(Type::Number(n)) => n
+            .enumeration
+            .iter()
+            .flatten()
+            .map(|v| (*v).into())
+            .collect(),
+        SchemaKind::Type(Type::Boolean(b)) => b
+            .enumeration
+            .iter()
+            .flatten()
+            .map(|v| (*v).into())
+            .collect(),
+        SchemaKind::Any(any)
+            if any.properties.is_empty()
+                && any.one_of.is_empty()
+                && any.any_of.is_empty()
+                && any.all_of.is_empty() =>
+        {
+            any.enumeration.clone()
+        }
+        _ => Vec::new(),
+    };
+    (!values.is_empty()).then_some(values)
+}
+
+/// Builds the enum for a list of literal values: a string enum, an integer enum, or an
+/// enum with explicit (de)serialization when the values mix types
+fn literal_enum(
+    name: &str,
+    values: Vec<serde_json::Value>,
+    description: Option<String>,
+    custom_attrs: Option<Vec<String>>,
+    derives: Option<Vec<String>>,
+) -> EnumModel {
+    let mut model = EnumModel {
+        name: name.to_string(),
+        variants: Vec::new(),
+        description,
+        custom_attrs,
+        derives,
+        extensible: false,
+        integer_values: Vec::new(),
+        mixed_values: Vec::new(),
+    };
+
+    if values.iter().all(|v| v.is_string()) {
+        model.variants = values
+            .iter()
+            .filter_map(|v| v.as_str())
+            .map(String::from)
+            .collect();
+    } else if values.iter().all(|v| v.is_i64()) {
+        model.integer_values = values.iter().filter_map(|v| v.as_i64()).collect();
+        model.variants = model
+            .integer_values
+            .iter()
+            .map(|v| integer_variant_name(*v))
+            .collect();
+    } else {
+        model.variants = values.iter().map(literal_variant_name).collect();
+        model.mixed_values = values;
+    }
+    model
+}
+
+/// Variant name for one value of a mixed-type enum
+fn literal_variant_name(value: &serde_json::Value) -> String {
+    match value {
+        serde_json::Value::String(s) => s.clone(),
+        serde_json::Value::Number(n) => match n.as_i64() {
+            Some(i) => integer_variant_name(i),
+            None => {
+                let text = n.to_string();
+                let sign = if text.starts_with('-') { "Minus" } else { "" };
+                format!(
+                    "Value{sign}{}",
+                    text.trim_start_matches('-').replace('.', "Point")
+                )
+            }
+        },
+        serde_json::Value::Bool(true) => "True".to_string(),
+        serde_json::Value::Bool(false) => "False".to_string(),
+        serde_json::Value::Null => "Null".to_string(),
+        other => to_pascal_case(&other.to_string()),
+    }
+}
+
+/// Collects the validation keywords of a property schema
+fn extract_constraints(schema: &Schema) -> Constraints {
+    match &schema.schema_kind {
+        SchemaKind::Type(Type::String(s)) => Constraints {
+            min_length: s.min_length,
+            max_length: s.max_length,
+            pattern: s.pattern.clone(),
+            ..Default::default()
+        },
+        SchemaKind::Type(Type::Integer(i)) => Constraints {
+            minimum: i.minimum.map(serde_json::Number::from),
+            maximum: i.maximum.map(serde_json::Number::from),
+            exclusive_minimum: i.exclusive_minimum && i.minimum.is_some(),
+            exclusive_maximum: i.exclusive_maximum && i.maximum.is_some(),
+            ..Default::default()
+        },
+        SchemaKind::Type(Type::Number(n)) => Constraints {
+            minimum: n.minimum.and_then(serde_json::Number::from_f64),
+            maximum: n.maximum.and_then(serde_json::Number::from_f64),
+            exclusive_minimum: n.exclusive_minimum && n.minimum.is_some(),
+            exclusive_maximum: n.exclusive_maximum && n.maximum.is_some(),
+            ..Default::default()
+        },
+        SchemaKind::Type(Type::Array(a)) => Constraints {
+            min_items: a.min_items,
+            max_items: a.max_items,
+            unique_items: a.unique_items,
+            ..Default::default()
+        },
+        _ => Constraints::default(),
+    }
+}
+
+/// PascalCase name from a schema's `title`, for naming promoted inline schemas the way the
+/// spec authors did. Titles that do not start with a letter are ignored.
+fn title_name(schema: &Schema) -> Option<String> {
+    let title = schema.schema_data.title.as_deref()?;
+    let name: String = title
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .map(|word| {
+            let mut chars = word.chars();
+            match chars.next() {
+                Some(first) => first.to_ascii_uppercase().to_string() + chars.as_str(),
+                None => String::new(),
+            }
+        })
+        .collect();
+    name.starts_with(|c: char| c.is_ascii_alphabetic())
+        .then_some(name)
+}
+
+/// Whether an inline array item schema has to be lifted into its own named type
+fn needs_named_item(item_schema: &Schema) -> bool {
+    match &item_schema.schema_kind {
+        SchemaKind::Type(Type::Object(obj)) => !obj.properties.is_empty(),
+        SchemaKind::Type(Type::String(s)) => !s.enumeration.is_empty(),
+        SchemaKind::OneOf { .. } | SchemaKind::AnyOf { .. } => true,
+        _ => false,
+    }
+}
+
+/// Resolves the value type of a map-like object from its `additionalProperties`
+fn map_value_type(
+    additional_props: &AdditionalProperties,
+    all_schemas: &IndexMap<String, ReferenceOr<Schema>>,
+) -> Result<String> {
+    match additional_props {
+        AdditionalProperties::Any(_) => Ok("serde_json::Value".to_string()),
+        AdditionalProperties::Schema(value_schema) => {
+            let (value_type, _) = extract_type_and_format(value_schema, all_schemas)?;
+            // extract_type_and_format yields the item type for inline arrays
+            match &**value_schema {
+                ReferenceOr::Item(Schema {
+                    schema_kind: SchemaKind::Type(Type::Array(_)),
+                    ..
+                }) => Ok(format!("Vec<{value_type}>")),
+                _ => Ok(value_type),
+            }
+        }
+    }
+}
+
+/// Returns the OpenAPI wire name of a known string format
+fn string_format_name(format: &StringFormat) -> &'static str {
+    match format {
+        StringFormat::Date => "date",
+        StringFormat::DateTime => "date-time",
+        StringFormat::Password => "password",
+        StringFormat::Byte => "byte",
+        StringFormat::Binary => "binary",
+    }
+}
+
+/// Picks the Rust integer width for an integer schema. Formats other than `int32`
+/// fall back to `i64` but keep their name.
+fn integer_type_and_format(integer_type: &IntegerType) -> (&'static str, String) {
+    match &integer_type.format {
+        VariantOrUnknownOrEmpty::Item(IntegerFormat::Int32) => ("i32", "int32".to_string()),
+        VariantOrUnknownOrEmpty::Item(IntegerFormat::Int64) => ("i64", "int64".to_string()),
+        VariantOrUnknownOrEmpty::Unknown(format) => ("i64", format.clone()),
+        VariantOrUnknownOrEmpty::Empty => ("i64", "integer".to_string()),
+    }
+}
+
+/// Picks the Rust float width for a number schema. Formats other than `float`
+/// fall back to `f64` but keep their name.
+fn number_type_and_format(number_type: &NumberType) -> (&'static str, String) {
+    match &number_type.format {
+        VariantOrUnknownOrEmpty::Item(NumberFormat::Float) => ("f32", "float".to_string()),
+        VariantOrUnknownOrEmpty::Item(NumberFormat::Double) => ("f64", "double".to_string()),
+        VariantOrUnknownOrEmpty::Unknown(format) => ("f64", format.clone()),
+        VariantOrUnknownOrEmpty::Empty => ("f64", "number".to_string()),
+    }
+}
+
+/// Name of an enum promoted from a property: `{Parent}{Field}`, so that `status` fields of
+/// different models get their own enums (`OrderStatus`, `UserStatus`)
+fn inline_enum_name(parent_name: &str, field_name: &str) -> String {
+    format!(
+        "{}{}",
+        to_pascal_case(parent_name),
+        to_pascal_case(field_name)
+    )
+}
+
+/// Extracts field information including type, format, and nullable flag from OpenAPI schema
+/// Inline objects are lifted into structs named `{Parent}{Field}`; these and any
+/// inline enums are returned alongside the field information.
+fn extract_field_info(
+    parent_name: &str,
+    field_name: &str,
+    schema: &ReferenceOr<Schema>,
+    all_schemas: &IndexMap<String, ReferenceOr<Schema>>,
+) -> Result<(FieldInfo, Vec<ModelType>)> {
+    if let ReferenceOr::Item(item) = schema {
+        if let Some(base) = without_not(item) {
+            tracing::warn!(
+                "Property '{parent_name}.{field_name}' uses `not`; generating its base type only"
+            );
+            return extract_field_info(
+                parent_name,
+                field_name,
+                &ReferenceOr::Item(base),
+                all_schemas,
+            );
+        }
+    }
+
+    let (mut field_type, mut format) = extract_type_and_format(schema, all_schemas)?;
+    if let ReferenceOr::Item(item) = schema {
+        let is_secret = item
+            .schema_data
+            .extensions
+            .get(X_SECRET)
+            .and_then(|value| value.as_bool())
+            .unwrap_or(false);
+        if is_secret && field_type == "String" {
+            format = "password".to_string();
+        }
+    }
+
+    let (read_only, write_only, default, constraints, deprecated, rust_uses) = match schema {
+        ReferenceOr::Item(schema) => (
+            schema.schema_data.read_only,
+            schema.schema_data.write_only,
+            schema.schema_data.default.clone(),
+            extract_constraints(schema),
+            schema.schema_data.deprecated,
+            extract_rust_uses(schema),
+        ),
+        ReferenceOr::Reference { .. } => (
+            false,
+            false,
+            None,
+            Constraints::default(),
+            false,
+            Vec::new(),
+        ),
+    };
+
+    let (is_nullable, is_array_ref, inline_models, description, serde_with) = match schema {
+        ReferenceOr::Reference { reference } => {
+            let mut is_array_ref = false;
+            let mut is_nullable = false;
+
+            if let Some(type_name) = reference.strip_prefix("#/components/schemas/") {
+                if let Some(ReferenceOr::Item(schema)) = all_schemas.get(type_name) {
+                    is_nullable = schema.schema_data.nullable;
+
+                    if let SchemaKind::Type(Type::Array(array)) = &schema.schema_kind {
+                        let is_items_one_of = match &array.items {
+                            Some(ReferenceOr::Item(item_schema)) => {
+                                matches!(item_schema.schema_kind, SchemaKind::OneOf { .. })
+                            }
+                            _ => false,
+                        };
+
+                        is_array_ref = !is_items_one_of && !is_tuple(schema);
+                    }
+                }
+            }
+
+            (is_nullable, is_array_ref, Vec::new(), None, None)
+        }
+
+        ReferenceOr::Item(schema) => {
+            if let Some(rust_type) = schema.schema_data.extensions.get(X_RUST_TYPE) {
+                if let Some(type_str) = rust_type.as_str() {
+                    field_type = type_str.to_string();
+                }
+            }
+
+            let is_nullable = schema.schema_data.nullable;
+            let is_array_ref =
+                matches!(schema.schema_kind, SchemaKind::Type(Type::Array(_))) && !is_tuple(schema);
+            let description = schema.schema_data.description.clone();
+            let serde_with = schema
+                .schema_data
+                .extensions
+                .get(X_SERDE_WITH)
+                .and_then(|value| value.as_str())
+                .map(|s| s.to_string());
+
+            let inline_models = match &schema.schema_kind {
+                SchemaKind::Type(Type::String(s)) if !s.enumeration.is_empty() => {
+                    let variants: Vec<String> =
+                        s.enumeration.iter().filter_map(|v| v.clone()).collect();
+                    field_type = inline_enum_name(parent_name, field_name);
+                    vec![ModelType::Enum(EnumModel {
+                        name: field_type.clone(),
+                        variants,
+                        description: schema.schema_data.description.clone(),
+                        custom_attrs: extract_custom_attrs(schema),
+                        derives: extract_derives(schema),
+                        extensible: is_extensible_enum(schema),
+                        integer_values: Vec::new(),
+                        mixed_values: Vec::new(),
+                    })]
+                }
+                SchemaKind::Any(_)
+                    if !schema.schema_data.extensions.contains_key(X_RUST_TYPE)
+                        && enum_literals(schema).is_some() =>
+                {
+                    field_type = inline_enum_name(parent_name, field_name);
+                    vec![ModelType::Enum(EnumModel {
+                        extensible: is_extensible_enum(schema),
+                        ..literal_enum(
+                            &field_type,
+                            enum_literals(schema).unwrap_or_default(),
+                            schema.schema_data.description.clone(),
+                            extract_custom_attrs(schema),
+                            extract_derives(schema),
+                        )
+                    })]
+                }
+                SchemaKind::Type(Type::Object(obj)) => {
+                    if obj.properties.is_empty() {
+                        // Maps and free-form objects are typed by extract_type_and_format
+                        Vec::new()
+                    } else {
+                        let struct_name = title_name(schema).unwrap_or_else(|| {
+                            format!(
+                                "{}{}",
+                                to_pascal_case(parent_name),
+                                to_pascal_case(field_name)
+                            )
+                        });
+                        field_type = struct_name.clone();
+
+                        let wrapped_schema = ReferenceOr::Item(schema.clone());
+                        parse_schema_to_model_type(&struct_name, &wrapped_schema, all_schemas)?
+                    }
+                }
+                // Inline unions, including multi-type `type: [string, integer]`
+                // (rewritten to anyOf on load)
+                SchemaKind::OneOf { .. } | SchemaKind::AnyOf { .. }
+                    if !schema.schema_data.extensions.contains_key(X_RUST_TYPE) =>
+                {
+                    let union_name = title_name(schema).unwrap_or_else(|| {
+                        format!(
+                            "{}{}",
+                            to_pascal_case(parent_name),
+                            to_pascal_case(field_name)
+                        )
+                    });
+                    field_type = union_name.clone();
+
+                    let wrapped_schema = ReferenceOr::Item(schema.clone());
+                    parse_schema_to_model_type(&union_name, &wrapped_schema, all_schemas)?
+                }
+                SchemaKind::Type(Type::Array(array))
+                    if !schema.schema_data.extensions.contains_key(X_RUST_TYPE) =>
+                {
+                    match &array.items {
+                        Some(ReferenceOr::Item(item_schema)) if needs_named_item(item_schema) => {
+                            let item_name = title_name(item_schema).unwrap_or_else(|| {
+                                format!(
+                                    "{}{}Item",
+                                    to_pascal_case(parent_name),
+                                    to_pascal_case(field_name)
+                                )
+                            });
+                            field_type = item_name.clone();
+
+                            let wrapped_schema = ReferenceOr::Item((**item_schema).clone());
+                            parse_schema_to_model_type(&item_name, &wrapped_schema, all_schemas)?
+                        }
+                        _ => Vec::new(),
+                    }
+                }
+                _ => Vec::new(),
+            };
+            (
+                is_nullable,
+                is_array_ref,
+                inline_models,
+                description,
+                serde_with,
+            )
+        }
+    };
+
+    Ok((
+        FieldInfo {
+            field_type,
+            format,
+            is_nullable,
+            is_array_ref,
+            description,
+            serde_with,
+            read_only,
+            write_only,
+            default,
+            constraints,
+            deprecated,
+            rust_uses,
+            xml: match schema {
+                ReferenceOr::Item(schema) => property_xml(schema),
+                ReferenceOr::Reference { .. } => None,
+            },
+            newtype: match schema {
+                ReferenceOr::Item(schema) => property_newtype(schema, parent_name, field_name),
+                ReferenceOr::Reference { .. } => None,
+            },
+        },
+        inline_models,
+    ))
+}
+
+/// The `xml` object of a schema, kept under `x-xml` on load
+fn schema_xml(schema: &Schema) -> Option<XmlOptions> {
+    let value = schema.schema_data.extensions.get(X_XML)?;
+    serde_json::from_value(value.clone()).ok()
+}
+
+/// Rust tuple for an array schema with `prefixItems` and no `items`, such as
+/// `(f64, f64)`. With an `items` schema further elements are allowed, and the array stays
+/// a `Vec`.
+fn tuple_type(
+    schema: &Schema,
+    all_schemas: &IndexMap<String, ReferenceOr<Schema>>,
+) -> Result<Option<String>> {
+    let Some(prefix_items) = schema.schema_data.extensions.get(X_PREFIX_ITEMS) else {
+        return Ok(None);
+    };
+    if !is_tuple(schema) {
+        return Ok(None);
+    }
+    let Ok(elements) = serde_json::from_value::<Vec<ReferenceOr<Schema>>>(prefix_items.clone())
+    else {
+        return Ok(None);
+    };
+
+    let mut types = Vec::new();
+    for element in &elements {
+        let element_type = body_type_expr(element, all_schemas)?;
+        let is_nullable = element.as_item().is_some_and(|e| e.schema_data.nullable);
+        types.push(if is_nullable {
+            format!("Option<{element_type}>")
+        } else {
+            element_type
+        });
+    }
+    Ok(Some(match types.as_slice() {
+        [single] => format!("({single},)"),
+        _ => format!("({})", types.join(", ")),
+    }))
+}
+
+/// Whether a schema is an array generated as a tuple by [`tuple_type`]
+fn is_tuple(schema: &Schema) -> bool {
+    matches!(&schema.schema_kind, SchemaKind::Type(Type::Array(array)) if array.items.is_none())
+        && schema.schema_data.extensions.contains_key(X_PREFIX_ITEMS)
+}
+
+/// The newtype an `x-newtype` property is wrapped in: the string given, or for `true` the
+/// identifier name of the field (`UserId`), falling back to the field name in PascalCase
+fn property_newtype(schema: &Schema, parent_name: &str, field_name: &str) -> Option<String> {
+    let is_array = matches!(schema.schema_kind, SchemaKind::Type(Type::Array(_)));
+    match schema.schema_data.extensions.get(X_NEWTYPE)? {
+        serde_json::Value::String(name) => Some(name.clone()),
+        serde_json::Value::Bool(true) => Some(
+            identifier_newtype_name(&to_pascal_case(parent_name), field_name, is_array)
+                .unwrap_or_else(|| to_pascal_case(field_name)),
+        ),
+        _ => None,
+    }
+}
+
+/// The `xml` object of a property, with the `xml.name` of its items for arrays
+fn property_xml(schema: &Schema) -> Option<XmlOptions> {
+    let items_name = match &schema.schema_kind {
+        SchemaKind::Type(Type::Array(array)) => array
+            .items
+            .as_ref()
+            .and_then(|items| items.as_item())
+            .and_then(|items| schema_xml(items))
+            .and_then(|xml| xml.name),
+        _ => None,
+    };
+    match (schema_xml(schema), items_name) {
+        (None, None) => None,
+        (xml, items_name) => Some(XmlOptions {
+            items_name,
+            ..xml.unwrap_or_default()
+        }),
+    }
+}
+
+/// XML root element of a schema's model: `xml.name`, falling back to the schema name
+fn xml_root_name(name: &str, schema: &Schema) -> Option<String> {
+    schema_xml(schema)
+        .and_then(|xml| xml.name)
+        .or_else(|| Some(name.to_string()))
+}
+
+fn resolve_all_of_fields(
+    name: &str,
+    all_of: &[ReferenceOr<Schema>],
+    all_schemas: &IndexMap<String, ReferenceOr<Schema>>,
+) -> Result<(Vec<Field>, Vec<ModelType>)> {
+    resolve_all_of_fields_visiting(name, all_of, all_schemas, &mut vec![name.to_string()])
+}
+
+/// Resolves `allOf` members, following referenced `allOf` schemas. `visiting` holds
+/// the schemas currently being resolved; a reference back into it is a cycle, which
+/// is skipped (with a warning) so every schema contributes its fields once.
+fn resolve_all_of_fields_visiting(
+    name: &str,
+    all_of: &[ReferenceOr<Schema>],
+    all_schemas: &IndexMap<String, ReferenceOr<Schema>>,
+    visiting: &mut Vec<String>,
+) -> Result<(Vec<Field>, Vec<ModelType>)> {
+    let mut all_fields: IndexMap<String, Field> = IndexMap::new();
+    let mut models = Vec::new();
+    let mut all_required_fields = HashSet::new();
+
+    for schema_ref in all_of {
+        let schema_to_check = match schema_ref {
+            ReferenceOr::Reference { reference } => reference
+                .strip_prefix("#/components/schemas/")
+                .and_then(|schema_name| all_schemas.get(schema_name)),
+            ReferenceOr::Item(_) => Some(schema_ref),
+        };
+
+        // A member may only list `required` names whose properties come from another member
+        if let Some(ReferenceOr::Item(schema)) = schema_to_check {
+            match &schema.schema_kind {
+                SchemaKind::Type(Type::Object(obj)) => {
+                    all_required_fields.extend(obj.required.iter().cloned())
+                }
+                SchemaKind::Any(any) => all_required_fields.extend(any.required.iter().cloned()),
+                _ => {}
+            }
+        }
+    }
+
+    // Try hard to replace all_fields entries that are serde_json::Value
+    // Notes:
+    //  - Most of the substitions are fairly straightforward, Value, Optional Value.
+    //  - HashMap is more complex to understand, we are replacing a Value HashMap
+    //    with an actual structure type
+    fn less_value(fields: Vec<Field>, all_fields: &mut IndexMap<String, Field>) {
+        for field in fields {
+            if let Some(existing_field) = all_fields.get_mut(&field.name) {
+                // Value
+                if existing_field.field_type == "serde_json::Value" {
+                    *existing_field = field;
+                } else if existing_field.field_type == "Option<serde_json::Value>" {
+                    existing_field.field_type = format!("Option<{}>", field.field_type);
+                // HashMap Value
+                } else if existing_field.field_type
+                    == "std::collections::HashMap<String, serde_json::Value>"
+                {
+                    *existing_field = field;
+                } else if existing_field.field_type
+                    == "Option<std::collections::HashMap<String, serde_json::Value>>"
+                {
+                    existing_field.field_type = format!("Option<{}>", field.field_type);
+                // Vec Value
+                } else if existing_field.field_type == "Vec<serde_json::Value>" {
+                    existing_field.field_type = format!("Vec<{}>", field.field_type);
+                } else if existing_field.field_type == "Option<Vec<serde_json::Value>>" {
+                    existing_field.field_type = format!("Option<Vec<{}>>", field.field_type);
+                }
+            } else {
+                all_fields.insert(field.name.clone(), field);
+            }
+        }
+    }
+
+    // Now collect fields from all schemas, resolving nested compositions at any depth
+    for schema_ref in all_of {
+        let (member_name, member, is_reference) = match schema_ref {
+            ReferenceOr::Reference { reference } => {
+                let Some(schema_name) = reference.strip_prefix("#/components/schemas/") else {
+                    continue;
+                };
+                let Some(referenced_schema) = all_schemas.get(schema_name) else {
+                    continue;
+                };
+                if visiting.iter().any(|visited| visited == schema_name) {
+                    tracing::warn!(
+                        "allOf cycle {} -> {schema_name}; skipping the repeated member",
+                        visiting.join(" -> ")
+                    );
+                    continue;
+                }
+                (schema_name, referenced_schema, true)
+            }
+            ReferenceOr::Item(_) => (name, schema_ref, false),
+        };
+
+        let (fields, inline_models) = match member.as_item().map(|schema| &schema.schema_kind) {
+            Some(SchemaKind::AllOf { all_of: nested }) => {
+                if is_reference {
+                    visiting.push(member_name.to_string());
+                }
+                let resolved =
+                    resolve_all_of_fields_visiting(member_name, nested, all_schemas, visiting);
+                if is_reference {
+                    visiting.pop();
+                }
+                resolved?
+            }
+            _ if member.as_item().is_some_and(is_union_schema) => {
+                embedded_member_field(name, member_name, member, is_reference, all_schemas)?
+            }
+            _ => extract_fields_from_schema(member_name, member, all_schemas)?,
+        };
+        // If we have an all_fields entry that is of type serde_json::Value, then we should replace it.
+        less_value(fields, &mut all_fields);
+        models.extend(inline_models);
+    }
+
+    // Update is_required for fields based on the merged required set
+    for field in all_fields.values_mut() {
+        if all_required_fields.contains(&field.name) {
+            field.is_required = true;
+        }
+    }
+
+    Ok((all_fields.into_values().collect(), models))
+}
+
+/// The fields of an allOf composition that keeps its members' structure: every referenced
+/// object or union becomes one `#[serde(flatten)]` field of the component type, while
+/// inline members contribute their own properties. Members closing an allOf cycle are
+/// skipped, as embedding them would make the type infinitely large.
+fn embed_all_of_members(
+    name: &str,
+    all_of: &[ReferenceOr<Schema>],
+    all_schemas: &IndexMap<String, ReferenceOr<Schema>>,
+) -> Result<Vec<Field>> {
+    let cycles = all_of_cycles(all_schemas);
+    let mut fields: IndexMap<String, Field> = IndexMap::new();
+    let mut required = HashSet::new();
+
+    for member in all_of {
+        let member_fields = match member {
+            ReferenceOr::Reference { reference } => {
+                let Some(schema_name) = reference.strip_prefix("#/components/schemas/") else {
+                    continue;
+                };
+                let in_cycle = schema_name == name
+                    || cycles.iter().any(|cycle| {
+                        cycle.iter().any(|n| n == name) && cycle.iter().any(|n| n == schema_name)
+                    });
+                let Some(referenced_schema) = all_schemas.get(schema_name) else {
+                    continue;
+                };
+                let embeddable =
+                    referenced_schema
+                        .as_item()
+                        .is_some_and(|schema| match &schema.schema_kind {
+                            SchemaKind::Type(Type::Object(_)) | SchemaKind::AllOf { .. } => true,
+                            SchemaKind::Any(any) => !any.properties.is_empty(),
+                            _ => is_union_schema(schema),
+                        });
+                if in_cycle || !embeddable {
+                    continue;
+                }
+                embedded_member_field(name, schema_name, referenced_schema, true, all_schemas)?.0
+            }
+            ReferenceOr::Item(schema) => {
+                match &schema.schema_kind {
+                    SchemaKind::Type(Type::Object(obj)) => required.extend(obj.required.clone()),
+                    SchemaKind::Any(any) => required.extend(any.required.clone()),
+                    _ => {}
+                }
+                match &schema.schema_kind {
+                    SchemaKind::AllOf { all_of: nested } => {
+                        embed_all_of_members(name, nested, all_schemas)?
+                    }
+                    _ if is_union_schema(schema) => {
+                        embedded_member_field(name, name, member, false, all_schemas)?.0
+                    }
+                    _ => extract_fields_from_schema(name, member, all_schemas)?.0,
+                }
+            }
+        };
+        for field in member_fields {
+            fields.entry(field.name.clone()).or_insert(field);
+        }
+    }
+
+    for field in fields.values_mut() {
+        if required.contains(&field.name) {
+            field.is_required = true;
+        }
+    }
+    Ok(fields.into_values().collect())
+}
+
+/// Names of the component schemas an allOf references, directly or through inline allOf
+/// members
+fn all_of_bases(all_of: &[ReferenceOr<Schema>]) -> Vec<String> {
+    let mut bases = Vec::new();
+    for member in all_of {
+        match member {
+            ReferenceOr::Reference { reference } => {
+                if let Some(schema_name) = reference.strip_prefix("#/components/schemas/") {
+                    bases.push(to_pascal_case(schema_name));
+                }
+            }
+            ReferenceOr::Item(Schema {
+                schema_kind: SchemaKind::AllOf { all_of: nested },
+                ..
+            }) => bases.extend(all_of_bases(nested)),
+            ReferenceOr::Item(_) => {}
+        }
+    }
+    bases
+}
+
+/// Whether the schema is a oneOf/anyOf, possibly mixed with other keywords
+fn is_union_schema(schema: &Schema) -> bool {
+    match &schema.schema_kind {
+        SchemaKind::OneOf { .. } | SchemaKind::AnyOf { .. } => true,
+        SchemaKind::Any(any) => !any.one_of.is_empty() || !any.any_of.is_empty(),
+        _ => false,
+    }
+}
+
+/// An allOf member embedded as a single flattened field, as unions cannot be merged field
+/// by field. Referenced members use their component type; inline unions get a
+/// `{Parent}OneOf`/`{Parent}AnyOf` enum, or one named after their title.
+fn embedded_member_field(
+    parent_name: &str,
+    member_name: &str,
+    member: &ReferenceOr<Schema>,
+    is_reference: bool,
+    all_schemas: &IndexMap<String, ReferenceOr<Schema>>,
+) -> Result<(Vec<Field>, Vec<ModelType>)> {
+    let Some(schema) = member.as_item() else {
+        return Ok((Vec::new(), Vec::new()));
+    };
+    let (type_name, models) = if is_reference {
+        (to_pascal_case(member_name), Vec::new())
+    } else {
+        let kind = match &schema.schema_kind {
+            SchemaKind::AnyOf { .. } => "AnyOf",
+            SchemaKind::Any(any) if any.one_of.is_empty() => "AnyOf",
+            _ => "OneOf",
+        };
+        let type_name =
+            title_name(schema).unwrap_or_else(|| format!("{}{kind}", to_pascal_case(parent_name)));
+        let models = parse_schema_to_model_type(&type_name, member, all_schemas)?;
+        (type_name, models)
+    };
+
+    let field = Field {
+        name: type_name.clone(),
+        field_type: type_name,
+        format: String::new(),
+        is_required: true,
+        is_nullable: false,
+        is_array_ref: false,
+        description: schema.schema_data.description.clone(),
+        serde_with: None,
+        read_only: false,
+        write_only: false,
+        default: None,
+        constraints: Constraints::default(),
+        deprecated: schema.schema_data.deprecated,
+        rust_uses: Vec::new(),
+        flatten: true,
+        xml: None,
+        double_option: false,
+        newtype: None,
+        rename: None,
+    };
+    Ok((vec![field], models))
+}
+
+fn resolve_union_variants(
+    name: &str,
+    schemas: &[ReferenceOr<Schema>],
+    all_schemas: &IndexMap<String, ReferenceOr<Schema>>,
+) -> Result<(Vec<UnionVariant>, Vec<ModelType>)> {
+    let mut variants = Vec::new();
+    let mut models = Vec::new();
+    let mut enum_values: Vec<serde_json::Value> = Vec::new();
+    let mut is_all_simple_enum = true;
+
+    for schema_ref in schemas {
+        let resolved = match schema_ref {
+            ReferenceOr::Reference { reference } => reference
+                .strip_prefix("#/components/schemas/")
+                .and_then(|n| all_schemas.get(n)),
+            ReferenceOr::Item(_) => Some(schema_ref),
+        };
+
+        match resolved
+            .and_then(|schema| schema.as_item())
+            .and_then(enum_literals)
+        {
+            Some(values) => {
+                for value in values {
+                    if !enum_values.contains(&value) {
+                        enum_values.push(value);
+                    }
+                }
+            }
+            None => is_all_simple_enum = false,
+        }
+    }
+    if is_all_simple_enum && !enum_values.is_empty() {
+        // Collective enum from multiple schemas, no single source for attrs
+        let enum_model = ModelType::Enum(literal_enum(
+            &to_pascal_case(name),
+            enum_values,
+            None,
+            None,
+            None,
+        ));
+
+        return Ok((vec![], vec![enum_model]));
+    }
+
+    // fallback for usual union-schemas
+    for (index, schema_ref) in schemas.iter().enumerate() {
+        match schema_ref {
+            ReferenceOr::Reference { reference } => {
+                if let Some(schema_name) = reference.strip_prefix("#/components/schemas/") {
+                    if let Some(referenced_schema) = all_schemas.get(schema_name) {
+                        if let ReferenceOr::Item(schema) = referenced_schema {
+                            if matches!(schema.schema_kind, SchemaKind::OneOf { .. }) {
+                                variants.push(UnionVariant {
+                                    name: to_pascal_case(schema_name),
+                                    fields: vec![],
+                                    primitive_type: None,
+                                });
+                            } else {
+                                let (fields, inline_models) = extract_fields_from_schema(
+                                    schema_name,
+                                    referenced_schema,
+                                    all_schemas,
+                                )?;
+                                variants.push(UnionVariant {
+                                    name: to_pascal_case(schema_name),
+                                    fields,
+                                    primitive_type: None,
+                                });
+                                models.extend(inline_models);
+                            }
+                        }
+                    }
+                }
+            }
+            ReferenceOr::Item(schema) => match &schema.schema_kind {
+                SchemaKind::Type(Type::String(_)) => {
+                    variants.push(UnionVariant {
+                        name: "String".to_string(),
+                        fields: vec![],
+                        primitive_type: Some("String".to_string()),
+                    });
+                }
+
+                SchemaKind::Type(Type::Integer(integer_type)) => {
+                    variants.push(UnionVariant {
+                        name: "Integer".to_string(),
+                        fields: vec![],
+                        primitive_type: Some(integer_type_and_format(integer_type).0.to_string()),
+                    });
+                }
+
+                SchemaKind::Type(Type::Number(number_type)) => {
+                    variants.push(UnionVariant {
+                        name: "Number".to_string(),
+                        fields: vec![],
+                        primitive_type: Some(number_type_and_format(number_type).0.to_string()),
+                    });
+                }
+
+                SchemaKind::Type(Type::Boolean(_)) => {
+                    variants.push(UnionVariant {
+                        name: "Boolean".to_string(),
+                        fields: vec![],
+                        primitive_type: Some("bool".to_string()),
+                    });
+                }
+
+                _ => {
+                    // Titled variants keep their title unless another variant already took it
+                    let variant_name = title_name(schema)
+                        .filter(|title| variants.iter().all(|v| &v.name != title))
+                        .unwrap_or_else(|| format!("Variant{index}"));
+                    let (fields, inline_models) = extract_fields_from_schema(
+                        &format!("{}{variant_name}", to_pascal_case(name)),
+                        schema_ref,
+                        all_schemas,
+                    )?;
+                    variants.push(UnionVariant {
+                        name: variant_name,
+                        fields,
+                        primitive_type: None,
+                    });
+                    models.extend(inline_models);
+                }
+            },
+        }
+    }
+
+    Ok((variants, models))
+}
+
+fn extract_fields_from_schema(
+    parent_name: &str,
+    schema_ref: &ReferenceOr<Schema>,
+    _all_schemas: &IndexMap<String, ReferenceOr<Schema>>,
+) -> Result<(Vec<Field>, Vec<ModelType>)> {
+    let mut fields = Vec::new();
+    let mut inline_models = Vec::new();
+
+    match schema_ref {
+        ReferenceOr::Reference { .. } => Ok((fields, inline_models)),
+        ReferenceOr::Item(schema) => {
+            // Members without `type: object` that only list properties are read the same way
+            let object = match &schema.schema_kind {
+                SchemaKind::Type(Type::Object(obj)) => Some((&obj.properties, &obj.required)),
+                SchemaKind::Any(any) if !any.properties.is_empty() => {
+                    Some((&any.properties, &any.required))
+                }
+                _ => None,
+            };
+            match (object, &schema.schema_kind) {
+                (Some((properties, required)), _) => {
+                    for (field_name, field_schema) in properties {
+                        let (field_info, field_models) = match field_schema {
+                            ReferenceOr::Item(boxed_schema) => extract_field_info(
+                                parent_name,
+                                field_name,
+                                &ReferenceOr::Item((**boxed_schema).clone()),
+                                _all_schemas,
+                            )?,
+                            ReferenceOr::Reference { reference } => extract_field_info(
+                                parent_name,
+                                field_name,
+                                &ReferenceOr::Reference {
+                                    reference: reference.clone(),
+                                },
+                                _all_schemas,
+                            )?,
+                        };
+
+                        let is_nullable = field_info.is_nullable
+                            || field_name == "value"
+                            || field_name == "default_value";
+
+                        let field_type = field_info.field_type.clone();
+
+                        let is_required = required.contains(field_name);
+                        fields.push(Field {
+                            name: field_name.clone(),
+                            field_type,
+                            format: field_info.format,
+                            is_required,
+                            is_nullable,
+                            is_array_ref: field_info.is_array_ref,
+                            description: field_info.description,
+                            serde_with: field_info.serde_with,
+                            read_only: field_info.read_only,
+                            write_only: field_info.write_only,
+                            default: field_info.default,
+                            constraints: field_info.constraints,
+                            deprecated: field_info.deprecated,
+                            rust_uses: field_info.rust_uses,
+                            flatten: false,
+                            xml: field_info.xml,
+                            double_option: false,
+                            newtype: field_info.newtype,
+                            rename: None,
+                        });
+                        inline_models.extend(field_models.into_iter().filter(
+                            |model| !matches!(model, ModelType::Struct(m) if m.fields.is_empty()),
+                        ));
+                    }
+                }
+                (None, SchemaKind::Type(Type::String(s))) if !s.enumeration.is_empty() => {
+                    let name = schema
+                        .schema_data
+                        .title
+                        .clone()
+                        .unwrap_or_else(|| "AnonymousStringEnum".to_string());
+
+                    let enum_model = ModelType::Enum(EnumModel {
+                        name,
+                        variants: s
+                            .enumeration
+                            .iter()
+                            .filter_map(|v| v.as_ref().map(|s| to_pascal_case(s)))
+                            .collect(),
+                        description: schema.schema_data.description.clone(),
+                        custom_attrs: extract_custom_attrs(schema),
+                        derives: extract_derives(schema),
+                        extensible: is_extensible_enum(schema),
+                        integer_values: Vec::new(),
+                        mixed_values: Vec::new(),
+                    });
+
+                    inline_models.push(enum_model);
+                }
+                (None, SchemaKind::Type(Type::Integer(n))) if !n.enumeration.is_empty() => {
+                    let name = schema
+                        .schema_data
+                        .title
+                        .clone()
+                        .unwrap_or_else(|| "AnonymousIntEnum".to_string());
+
+                    let enum_model = ModelType::Enum(integer_enum(&name, n, schema));
+
+                    inline_models.push(enum_model);
+                }
+
+                _ => {}
+            }
+
+            Ok((fields, inline_models))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use openapiv3::Parameter;
+    use serde_json::json;
+
+    #[test]
+    fn test_parse_nested_object_array_generates_model() {
+        let openapi_spec: OpenAPI = serde_json::from_value(json!({
+            "openapi": "3.0.0",
+            "info": { "title": "Test API", "version": "1.0.0" },
+            "paths": {
+                "/items": {
+                    "get": {
+                        "operationId": "getItems",
+                        "responses": {
+                            "200": {
+                                "description": "OK",
+                                "content": {
+                                    "application/json": {
+                                        "schema": {
+                                            "type": "object",
+                                            "properties": {
+                                                "exampleField": {
+                                                    "type": "string"
+                                                },
+                                                "objectArray": {
+                                                    "type": "array",
+                                                    "items": {
+                                                        "type": "object",
+                                                        "properties": {
+                                                            "objectArrayItemField": {
+                                                                "type": "string"
+                                                            },
+                                                        }
+                                                    }
+                                                }
+                                            }
+                                        },
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }))
+        .expect("Failed to deserialize OpenAPI spec");
+
+        let (models, _requests, responses) =
+            parse_openapi(&openapi_spec).expect("Failed to parse OpenAPI spec");
+
+        // 1. Verify that response model was created
+        assert_eq!(responses.len(), 1);
+        let response_model = &responses[0];
+        assert_eq!(response_model.name, "GetItems");
+
+        // 2. Verify that response schema references the top level object
+        assert_eq!(response_model.schema, "GetItemsResponse200");
+
+        // 3. Verify that the nested object model was generated
+        let inline_model = models
+            .iter()
+            .find(|m| m.name() == "GetItemsResponse200ObjectArrayItem");
+        assert!(
+            inline_model.is_some(),
+            "Expected a model named 'GetItemsResponse200ObjectArrayItem' to be generated"
+        );
+
+        if let Some(ModelType::Struct(model)) = inline_model {
+            assert_eq!(model.fields.len(), 1);
+
+            assert_eq!(model.fields[0].name, "objectArrayItemField");
+            assert_eq!(model.fields[0].field_type, "String");
+        } else {
+            panic!("Expected a Struct model for GetItemsResponse");
+        }
+    }
+
+    #[test]
+    fn test_parse_top_level_array_object_generates_model() {
+        let openapi_spec: OpenAPI = serde_json::from_value(json!({
+            "openapi": "3.0.0",
+            "info": { "title": "Test API", "version": "1.0.0" },
+            "paths": {
+                "/items": {
+                    "get": {
+                        "operationId": "getItems",
+                        "responses": {
+                            "200": {
+                                "description": "OK",
+                                "content": {
+                                    "application/json": {
+                                        "schema": {
+                                            "type": "array",
+                                            "items": {
+                                                "type": "object",
+                                                "properties": {
+                                                    "exampleField": {
+                                                        "type": "string"
+                                                    },
+                                                    "anotherExampleField": {
+                                                        "type": "string"
+                                                    }
+                                                }
+                                            }
+                                        },
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }))
+        .expect("Failed to deserialize OpenAPI spec");
+
+        let (models, _requests, responses) =
+            parse_openapi(&openapi_spec).expect("Failed to parse OpenAPI spec");
+
+        // 1. Verify that response model was created
+        assert_eq!(responses.len(), 1);
+        let response_model = &responses[0];
+        assert_eq!(response_model.name, "GetItems");
+
+        // 2. Verify that response schema references a Vec of the top level array object
+        assert_eq!(response_model.schema, "Vec<GetItemsResponseArrayObject200>");
+
+        // 3. Verify that the array object model was generated
+        let inline_model = models
+            .iter()
+            .find(|m| m.name() == "GetItemsResponseArrayObject200");
+        assert!(
+            inline_model.is_some(),
+            "Expected a model named 'GetItemsResponseArrayObject200' to be generated"
+        );
+
+        if let Some(ModelType::Struct(model)) = inline_model {
+            assert_eq!(model.fields.len(), 2);
+            assert_eq!(model.fields[0].name, "anotherExampleField");
+            assert_eq!(model.fields[0].field_type, "String");
+
+            assert_eq!(model.fields[1].name, "exampleField");
+            assert_eq!(model.fields[1].field_type, "String");
+        } else {
+            panic!("Expected a Struct model for GetItemsResponse");
+        }
+    }
+
+    #[test]
+    fn test_parse_inline_response_generates_model() {
+        let openapi_spec: OpenAPI = serde_json::from_value(json!({
+            "openapi": "3.0.0",
+            "info": { "title": "Test API", "version": "1.0.0" },
+            "paths": {
+                "/items": {
+                    "get": {
+                        "operationId": "getItem",
+                        "responses": {
+                            "200": {
+                                "description": "OK",
+                                "content": {
+                                    "application/json": {
+                                        "schema": {
+                                            "type": "object",
+                                            "properties": {
+                                                "name": { "type": "string" },
+                                                "value": { "type": "integer" }
+                                            },
+                                            "required": ["name"]
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }))
+        .expect("Failed to deserialize OpenAPI spec");
+
+        let (models, _requests, responses) =
+            parse_openapi(&openapi_spec).expect("Failed to parse OpenAPI spec");
+
+        // 1. Verify that response model was created
+        assert_eq!(responses.len(), 1);
+        let response_model = &responses[0];
+        assert_eq!(response_model.name, "GetItem");
+
+        // 2. Verify that response schema references a NEW model, not Value
+        assert_eq!(response_model.schema, "GetItemResponse200");
+
+        // 3. Verify that the response model itself was generated
+        let inline_model = models.iter().find(|m| m.name() == "GetItemResponse200");
+        assert!(
+            inline_model.is_some(),
+            "Expected a model named 'GetItemResponse200' to be generated"
+        );
+
+        if let Some(ModelType::Struct(model)) = inline_model {
+            assert_eq!(model.fields.len(), 2);
+            assert_eq!(model.fields[0].name, "name");
+            assert_eq!(model.fields[0].field_type, "String");
+
+            assert_eq!(model.fields[1].name, "value");
+            assert_eq!(model.fields[1].field_type, "i64");
+        } else {
+            panic!("Expected a Struct model for GetItemResponse");
+        }
+    }
+
+    #[test]
+    fn test_parse_inline_request_body_generates_model() {
+        let openapi_spec: OpenAPI = serde_json::from_value(json!({
+            "openapi": "3.0.0",
+            "info": { "title": "Test API", "version": "1.0.0" },
+            "paths": {
+                "/items": {
+                    "post": {
+                        "operationId": "createItem",
+                        "requestBody": {
+                            "content": {
+                                "application/json": {
+                                    "schema": {
+                                        "type": "object",
+                                        "properties": {
+                                            "name": { "type": "string" },
+                                            "value": { "type": "integer" }
+                                        },
+                                        "required": ["name"]
+                                    }
+                                }
+                            }
+                        },
+                        "responses": { "200": { "description": "OK" } }
+                    }
+                }
+            }
+        }))
+        .expect("Failed to deserialize OpenAPI spec");
+
+        let (models, requests, _responses) =
+            parse_openapi(&openapi_spec).expect("Failed to parse OpenAPI spec");
+
+        // 1. Verify that request model was created
+        assert_eq!(requests.len(), 1);
+        let request_model = &requests[0];
+        assert_eq!(request_model.name, "CreateItemRequest");
+
+        // 2. Verify that request schema references a NEW model, not Value
+        assert_eq!(request_model.schema, "CreateItemRequestBody");
+
+        // 3. Verify that the request body model itself was generated
+        let inline_model = models.iter().find(|m| m.name() == "CreateItemRequestBody");
+        assert!(
+            inline_model.is_some(),
+            "Expected a model named 'CreateItemRequestBody' to be generated"
+        );
+
+        if let Some(ModelType::Struct(model)) = inline_model {
+            assert_eq!(model.fields.len(), 2);
+            assert_eq!(model.fields[0].name, "name");
+            assert_eq!(model.fields[0].field_type, "String");
+            assert!(model.fields[0].is_required);
+
+            assert_eq!(model.fields[1].name, "value");
+            assert_eq!(model.fields[1].field_type, "i64");
+            assert!(!model.fields[1].is_required);
+        } else {
+            panic!("Expected a Struct model for CreateItemRequestBody");
+        }
+    }
+
+    #[test]
+    fn test_parse_ref_request_body_works() {
+        let openapi_spec: OpenAPI = serde_json::from_value(json!({
+            "openapi": "3.0.0",
+            "info": { "title": "Test API", "version": "1.0.0" },
+            "components": {
+                "schemas": {
+                    "ItemData": {
+                        "type": "object",
+                        "properties": {
+                            "name": { "type": "string" }
+                        }
+                    }
+                },
+                "requestBodies": {
+                    "CreateItem": {
+                        "content": {
+                            "application/json": {
+                                "schema": { "$ref": "#/components/schemas/ItemData" }
+                            }
+                        }
+                    }
+                }
+            },
+            "paths": {
+                "/items": {
+                    "post": {
+                        "operationId": "createItem",
+                        "requestBody": { "$ref": "#/components/requestBodies/CreateItem" },
+                        "responses": { "200": { "description": "OK" } }
+                    }
+                }
+            }
+        }))
+        .expect("Failed to deserialize OpenAPI spec");
+
+        let (models, requests, _responses) =
+            parse_openapi(&openapi_spec).expect("Failed to parse OpenAPI spec");
+
+        // Verify that request model was created
+        assert_eq!(requests.len(), 1);
+        let request_model = &requests[0];
+        assert_eq!(request_model.name, "CreateItemRequest");
+
+        // Verify that schema references an existing model
+        assert_eq!(request_model.schema, "ItemData");
+
+        // Verify that ItemData model exists in the models list
+        assert!(models.iter().any(|m| m.name() == "ItemData"));
+    }
+
+    #[test]
+    fn test_parse_no_request_body() {
+        let openapi_spec: OpenAPI = serde_json::from_value(json!({
+            "openapi": "3.0.0",
+            "info": { "title": "Test API", "version": "1.0.0" },
+            "paths": {
+                "/items": {
+                    "get": {
+                        "operationId": "listItems",
+                        "responses": { "200": { "description": "OK" } }
+                    }
+                }
+            }
+        }))
+        .expect("Failed to deserialize OpenAPI spec");
+
+        let (_models, requests, _responses) =
+            parse_openapi(&openapi_spec).expect("Failed to parse OpenAPI spec");
+
+        // Verify that no request models were created
+        assert!(requests.is_empty());
+    }
+
+    #[test]
+    fn test_nullable_reference_field() {
+        // Test verifies that nullable is correctly read from the target schema when using $ref
+        let openapi_spec: OpenAPI = serde_json::from_value(json!({
+            "openapi": "3.0.0",
+            "info": { "title": "Test API", "version": "1.0.0" },
+            "paths": {},
+            "components": {
+                "schemas": {
+                    "NullableUser": {
+                        "type": "object",
+                        "nullable": true,
+                        "properties": {
+                            "name": { "type": "string" }
+                        }
+                    },
+                    "Post": {
+                        "type": "object",
+                        "properties": {
+                            "author": {
+                                "$ref": "#/components/schemas/NullableUser"
+                            }
+                        }
+                    }
+                }
+            }
+        }))
+        .expect("Failed to deserialize OpenAPI spec");
+
+        let (models, _, _) = parse_openapi(&openapi_spec).expect("Failed to parse OpenAPI spec");
+
+        // Find Post model
+        let post_model = models.iter().find(|m| m.name() == "Post");
+        assert!(post_model.is_some(), "Expected Post model to be generated");
+
+        if let Some(ModelType::Struct(post)) = post_model {
+            let author_field = post.fields.iter().find(|f| f.name == "author");
+            assert!(author_field.is_some(), "Expected author field");
+
+            // Verify that nullable is correctly handled for reference type
+            // (nullable is taken from the target schema NullableUser)
+            let author = author_field.unwrap();
+            assert!(
+                author.is_nullable,
+                "Expected author field to be nullable (from referenced schema)"
+            );
+        } else {
+            panic!("Expected Post to be a Struct");
+        }
+    }
+
+    #[test]
+    fn test_allof_required_fields_merge() {
+        let openapi_spec: OpenAPI = serde_json::from_value(json!({
+            "openapi": "3.0.0",
+            "info": { "title": "Test API", "version": "1.0.0" },
+            "paths": {},
+            "components": {
+                "schemas": {
+                    "BaseEntity": {
+                        "type": "object",
+                        "properties": {
+                            "id": { "type": "string" },
+                            "created": { "type": "string" }
+                        },
+                        "required": ["id"]
+                    },
+                    "Person": {
+                        "allOf": [
+                            { "$ref": "#/components/schemas/BaseEntity" },
+                            {
+                                "type": "object",
+                                "properties": {
+                                    "name": { "type": "string" },
+                                    "age": { "type": "integer" }
+                                },
+                                "required": ["name"]
+                            }
+                        ]
+                    }
+                }
+            }
+        }))
+        .expect("Failed to deserialize OpenAPI spec");
+
+        let (models, _, _) = parse_openapi(&openapi_spec).expect("Failed to parse OpenAPI spec");
+
+        // Find Person model
+        let person_model = models.iter().find(|m| m.name() == "Person");
+        assert!(
+            person_model.is_some(),
+            "Expected Person model to be generated"
+        );
+
+        if let Some(ModelType::Composition(person)) = person_model {
+            // Verify that id (from BaseEntity) is required
+            let id_field = person.all_fields.iter().find(|f| f.name == "id");
+            assert!(id_field.is_some(), "Expected id field");
+            assert!(
+                id_field.unwrap().is_required,
+                "Expected id to be required from BaseEntity"
+            );
+
+            // Verify that name (from second object) is required
+            let name_field = person.all_fields.iter().find(|f| f.name == "name");
+            assert!(name_field.is_some(), "Expected name field");
+            assert!(
+                name_field.unwrap().is_required,
+                "Expected name to be required from inline object"
+            );
+
+            // Verify that created and age are not required
+            let created_field = person.all_fields.iter().find(|f| f.name == "created");
+            assert!(created_field.is_some(), "Expected created field");
+            assert!(
+                !created_field.unwrap().is_required,
+                "Expected created to be optional"
+            );
+
+            let age_field = person.all_fields.iter().find(|f| f.name == "age");
+            assert!(age_field.is_some(), "Expected age field");
+            assert!(
+                !age_field.unwrap().is_required,
+                "Expected age to be optional"
+            );
+        } else {
+            panic!("Expected Person to be a Composition");
+        }
+    }
+
+    #[test]
+    fn test_x_rust_type_generates_type_alias() {
+        let openapi_spec: OpenAPI = serde_json::from_value(json!({
+            "openapi": "3.0.0",
+            "info": { "title": "Test API", "version": "1.0.0" },
+            "paths": {},
+            "components": {
+                "schemas": {
+                    "User": {
+                        "type": "object",
+                        "x-rust-type": "crate::domain::User",
+                        "description": "Custom domain user type",
+                        "properties": {
+                            "name": { "type": "string" },
+                            "age": { "type": "integer" }
+                        }
+                    }
+                }
+            }
+        }))
+        .expect("Failed to deserialize OpenAPI spec");
+
+        let (models, _, _) = parse_openapi(&openapi_spec).expect("Failed to parse OpenAPI spec");
+
+        // Verify that TypeAlias is created, not Struct
+        let user_model = models.iter().find(|m| m.name() == "User");
+        assert!(user_model.is_some(), "Expected User model");
+
+        match user_model.unwrap() {
+            ModelType::TypeAlias(alias) => {
+                assert_eq!(alias.name, "User");
+                assert_eq!(alias.target_type, "crate::domain::User");
+                assert_eq!(
+                    alias.description,
+                    Some("Custom domain user type".to_string())
+                );
+            }
+            _ => panic!("Expected TypeAlias, got different type"),
+        }
+    }
+
+    #[test]
+    fn test_x_rust_type_works_with_enum() {
+        let openapi_spec: OpenAPI = serde_json::from_value(json!({
+            "openapi": "3.0.0",
+            "info": { "title": "Test API", "version": "1.0.0" },
+            "paths": {},
+            "components": {
+                "schemas": {
+                    "Status": {
+                        "type": "string",
+                        "enum": ["active", "inactive"],
+                        "x-rust-type": "crate::domain::Status"
+                    }
+                }
+            }
+        }))
+        .expect("Failed to deserialize OpenAPI spec");
+
+        let (models, _, _) = parse_openapi(&openapi_spec).expect("Failed to parse OpenAPI spec");
+
+        let status_model = models.iter().find(|m| m.name() == "Status");
+        assert!(status_model.is_some(), "Expected Status model");
+
+        // Should be TypeAlias, not Enum
+        assert!(
+            matches!(status_model.unwrap(), ModelType::TypeAlias(_)),
+            "Expected TypeAlias for enum with x-rust-type"
+        );
+    }
+
+    #[test]
+    fn test_x_rust_type_works_with_oneof() {
+        let openapi_spec: OpenAPI = serde_json::from_value(json!({
+            "openapi": "3.0.0",
+            "info": { "title": "Test API", "version": "1.0.0" },
+            "paths": {},
+            "components": {
+                "schemas": {
+                    "Payment": {
+                        "oneOf": [
+                            { "type": "object", "properties": { "card": { "type": "string" } } },
+                            { "type": "object", "properties": { "cash": { "type": "number" } } }
+                        ],
+                        "x-rust-type": "payments::Payment"
+                    }
+                }
+            }
+        }))
+        .expect("Failed to deserialize OpenAPI spec");
+
+        let (models, _, _) = parse_openapi(&openapi_spec).expect("Failed to parse OpenAPI spec");
+
+        let payment_model = models.iter().find(|m| m.name() == "Payment");
+        assert!(payment_model.is_some(), "Expected Payment model");
+
+        // Should be TypeAlias, not Union
+        match payment_model.unwrap() {
+            ModelType::TypeAlias(alias) => {
+                assert_eq!(alias.target_type, "payments::Payment");
+            }
+            _ => panic!("Expected TypeAlias for oneOf with x-rust-type"),
+        }
+    }
+
+    #[test]
+    fn test_x_rust_attrs_on_struct() {
+        let openapi_spec: OpenAPI = serde_json::from_value(json!({
+            "openapi": "3.0.0",
+            "info": { "title": "Test API", "version": "1.0.0" },
+            "paths": {},
+            "components": {
+                "schemas": {
+                    "User": {
+                        "type": "object",
+                        "x-rust-attrs": [
+                            "#[derive(Serialize, Deserialize)]",
+                            "#[serde(rename_all = \"camelCase\")]"
+                        ],
+                        "properties": {
+                            "name": { "type": "string" }
+                        }
+                    }
+                }
+            }
+        }))
+        .expect("Failed to deserialize OpenAPI spec");
+
+        let (models, _, _) = parse_openapi(&openapi_spec).expect("Failed to parse OpenAPI spec");
+
+        let user_model = models.iter().find(|m| m.name() == "User");
+        assert!(user_model.is_some(), "Expected User model");
+
+        match user_model.unwrap() {
+            ModelType::Struct(model) => {
+                assert!(model.custom_attrs.is_some(), "Expected custom_attrs");
+                let attrs = model.custom_attrs.as_ref().unwrap();
+                assert_eq!(attrs.len(), 2);
+                assert_eq!(attrs[0], "#[derive(Serialize, Deserialize)]");
+                assert_eq!(attrs[1], "#[serde(rename_all = \"camelCase\")]");
+            }
+            _ => panic!("Expected Struct model"),
+        }
+    }
+
+    #[test]
+    fn test_x_rust_attrs_on_enum() {
+        let openapi_spec: OpenAPI = serde_json::from_value(json!({
+            "openapi": "3.0.0",
+            "info": { "title": "Test API", "version": "1.0.0" },
+            "paths": {},
+            "components": {
+                "schemas": {
+                    "Status": {
+                        "type": "string",
+                        "enum": ["active", "inactive"],
+                        "x-rust-attrs": ["#[serde(rename_all = \"UPPERCASE\")]"]
+                    }
+                }
+            }
+        }))
+        .expect("Failed to deserialize OpenAPI spec");
+
+        let (models, _, _) = parse_openapi(&openapi_spec).expect("Failed to parse OpenAPI spec");
+
+        let status_model = models.iter().find(|m| m.name() == "Status");
+        assert!(status_model.is_some(), "Expected Status model");
+
+        match status_model.unwrap() {
+            ModelType::Enum(enum_model) => {
+                assert!(enum_model.custom_attrs.is_some());
+                let attrs = enum_model.custom_attrs.as_ref().unwrap();
+                assert_eq!(attrs.len(), 1);
+                assert_eq!(attrs[0], "#[serde(rename_all = \"UPPERCASE\")]");
+            }
+            _ => panic!("Expected Enum model"),
+        }
+    }
+
+    #[test]
+    fn test_x_rust_attrs_with_x_rust_type() {
+        let openapi_spec: OpenAPI = serde_json::from_value(json!({
+            "openapi": "3.0.0",
+            "info": { "title": "Test API", "version": "1.0.0" },
+            "paths": {},
+            "components": {
+                "schemas": {
+                    "User": {
+                        "type": "object",
+                        "x-rust-type": "crate::domain::User",
+                        "x-rust-attrs": ["#[cfg_attr(test, derive(Default))]"],
+                        "properties": {
+                            "name": { "type": "string" }
+                        }
+                    }
+                }
+            }
+        }))
+        .expect("Failed to deserialize OpenAPI spec");
+
+        let (models, _, _) = parse_openapi(&openapi_spec).expect("Failed to parse OpenAPI spec");
+
+        let user_model = models.iter().find(|m| m.name() == "User");
+        assert!(user_model.is_some(), "Expected User model");
+
+        // Should be TypeAlias with attributes
+        match user_model.unwrap() {
+            ModelType::TypeAlias(alias) => {
+                assert_eq!(alias.target_type, "crate::domain::User");
+                assert!(alias.custom_attrs.is_some());
+                let attrs = alias.custom_attrs.as_ref().unwrap();
+                assert_eq!(attrs.len(), 1);
+                assert_eq!(attrs[0], "#[cfg_attr(test, derive(Default))]");
+            }
+            _ => panic!("Expected TypeAlias with custom attrs"),
+        }
+    }
+
+    #[test]
+    fn test_x_rust_attrs_empty_array() {
+        let openapi_spec: OpenAPI = serde_json::from_value(json!({
+            "openapi": "3.0.0",
+            "info": { "title": "Test API", "version": "1.0.0" },
+            "paths": {},
+            "components": {
+                "schemas": {
+                    "User": {
+                        "type": "object",
+                        "x-rust-attrs": [],
+                        "properties": {
+                            "name": { "type": "string" }
+                        }
+                    }
+                }
+            }
+        }))
+        .expect("Failed to deserialize OpenAPI spec");
+
+        let (models, _, _) = parse_openapi(&openapi_spec).expect("Failed to parse OpenAPI spec");
+
+        let user_model = models.iter().find(|m| m.name() == "User");
+        assert!(user_model.is_some());
+
+        match user_model.unwrap() {
+            ModelType::Struct(model) => {
+                // Empty array should result in None
+                assert!(model.custom_attrs.is_none());
+            }
+            _ => panic!("Expected Struct"),
+        }
+    }
+
+    #[test]
+    fn test_x_rust_type_on_string_property() {
+        let openapi_spec: OpenAPI = serde_json::from_value(json!({
+            "openapi": "3.0.0",
+            "info": { "title": "Test API", "version": "1.0.0" },
+            "paths": {},
+            "components": {
+                "schemas": {
+                    "Document": {
+                        "type": "object",
+                        "description": "Document with custom version type",
+                        "properties": {
+                            "title": { "type": "string", "description": "Document title." },
+                            "content": { "type": "string", "description": "Document content." },
+                            "version": {
+                                "type": "string",
+                                "format": "semver",
+                                "x-rust-type": "semver::Version",
+                                "description": "Semantic version."
+                            }
+                        },
+                        "required": ["title", "content", "version"]
+                    }
+                }
+            }
+        }))
+        .expect("Failed to deserialize OpenAPI spec");
+
+        let (models, _, _) = parse_openapi(&openapi_spec).expect("Failed to parse OpenAPI spec");
+
+        let document_model = models.iter().find(|m| m.name() == "Document");
+        assert!(document_model.is_some(), "Expected Document model");
+
+        match document_model.unwrap() {
+            ModelType::Struct(model) => {
+                // Verify that version field has custom type
+                let version_field = model.fields.iter().find(|f| f.name == "version");
+                assert!(version_field.is_some(), "Expected version field");
+                assert_eq!(version_field.unwrap().field_type, "semver::Version");
+
+                // Verify other fields have regular types
+                let title_field = model.fields.iter().find(|f| f.name == "title");
+                assert_eq!(title_field.unwrap().field_type, "String");
+
+                let content_field = model.fields.iter().find(|f| f.name == "content");
+                assert_eq!(content_field.unwrap().field_type, "String");
+            }
+            _ => panic!("Expected Struct"),
+        }
+    }
+
+    #[test]
+    fn test_x_rust_type_on_integer_property() {
+        let openapi_spec: OpenAPI = serde_json::from_value(json!({
+            "openapi": "3.0.0",
+            "info": { "title": "Test API", "version": "1.0.0" },
+            "paths": {},
+            "components": {
+                "schemas": {
+                    "Configuration": {
+                        "type": "object",
+                        "description": "Configuration with custom duration type",
+                        "properties": {
+                            "timeout": {
+                                "type": "integer",
+                                "x-rust-type": "std::time::Duration",
+                                "description": "Timeout duration."
+                            },
+                            "retries": { "type": "integer" }
+                        },
+                        "required": ["timeout", "retries"]
+                    }
+                }
+            }
+        }))
+        .expect("Failed to deserialize OpenAPI spec");
+
+        let (models, _, _) = parse_openapi(&openapi_spec).expect("Failed to parse OpenAPI spec");
+
+        let config_model = models.iter().find(|m| m.name() == "Configuration");
+        assert!(config_model.is_some(), "Expected Configuration model");
+
+        match config_model.unwrap() {
+            ModelType::Struct(model) => {
+                // Verify that timeout field has custom type
+                let timeout_field = model.fields.iter().find(|f| f.name == "timeout");
+                assert!(timeout_field.is_some(), "Expected timeout field");
+                assert_eq!(timeout_field.unwrap().field_type, "std::time::Duration");
+
+                // Verify other field has regular i64 type
+                let retries_field = model.fields.iter().find(|f| f.name == "retries");
+                assert_eq!(retries_field.unwrap().field_type, "i64");
+            }
+            _ => panic!("Expected Struct"),
+        }
+    }
+
+    #[test]
+    fn test_x_rust_type_on_number_property() {
+        let openapi_spec: OpenAPI = serde_json::from_value(json!({
+            "openapi": "3.0.0",
+            "info": { "title": "Test API", "version": "1.0.0" },
+            "paths": {},
+            "components": {
+                "schemas": {
+                    "Product": {
+                        "type": "object",
+                        "description": "Product with custom decimal type",
+                        "properties": {
+                            "price": {
+                                "type": "number",
+                                "x-rust-type": "decimal::Decimal",
+                                "description": "Product price."
+                            },
+                            "quantity": { "type": "number" }
+                        },
+                        "required": ["price", "quantity"]
+                    }
+                }
+            }
+        }))
+        .expect("Failed to deserialize OpenAPI spec");
+
+        let (models, _, _) = parse_openapi(&openapi_spec).expect("Failed to parse OpenAPI spec");
+
+        let product_model = models.iter().find(|m| m.name() == "Product");
+        assert!(product_model.is_some(), "Expected Product model");
+
+        match product_model.unwrap() {
+            ModelType::Struct(model) => {
+                // Verify that price field has custom type
+                let price_field = model.fields.iter().find(|f| f.name == "price");
+                assert!(price_field.is_some(), "Expected price field");
+                assert_eq!(price_field.unwrap().field_type, "decimal::Decimal");
+
+                // Verify other field has regular f64 type
+                let quantity_field = model.fields.iter().find(|f| f.name == "quantity");
+                assert_eq!(quantity_field.unwrap().field_type, "f64");
+            }
+            _ => panic!("Expected Struct"),
+        }
+    }
+
+    #[test]
+    fn test_x_rust_type_on_nullable_property() {
+        let openapi_spec: OpenAPI = serde_json::from_value(json!({
+            "openapi": "3.0.0",
+            "info": { "title": "Test API", "version": "1.0.0" },
+            "paths": {},
+            "components": {
+                "schemas": {
+                    "Settings": {
+                        "type": "object",
+                        "description": "Settings with nullable custom type",
+                        "properties": {
+                            "settings": {
+                                "type": "string",
+                                "x-rust-type": "serde_json::Value",
+                                "nullable": true,
+                                "description": "Optional settings."
+                            }
+                        }
+                    }
+                }
+            }
+        }))
+        .expect("Failed to deserialize OpenAPI spec");
+
+        let (models, _, _) = parse_openapi(&openapi_spec).expect("Failed to parse OpenAPI spec");
+
+        let settings_model = models.iter().find(|m| m.name() == "Settings");
+        assert!(settings_model.is_some(), "Expected Settings model");
+
+        match settings_model.unwrap() {
+            ModelType::Struct(model) => {
+                let settings_field = model.fields.iter().find(|f| f.name == "settings");
+                assert!(settings_field.is_some(), "Expected settings field");
+
+                let field = settings_field.unwrap();
+                assert_eq!(field.field_type, "serde_json::Value");
+                assert!(field.is_nullable, "Expected field to be nullable");
+            }
+            _ => panic!("Expected Struct"),
+        }
+    }
+
+    #[test]
+    fn test_multiple_properties_with_x_rust_type() {
+        let openapi_spec: OpenAPI = serde_json::from_value(json!({
+            "openapi": "3.0.0",
+            "info": { "title": "Test API", "version": "1.0.0" },
+            "paths": {},
+            "components": {
+                "schemas": {
+                    "ComplexModel": {
+                        "type": "object",
+                        "description": "Model with multiple custom-typed properties",
+                        "properties": {
+                            "id": {
+                                "type": "string",
+                                "format": "uuid",
+                                "x-rust-type": "uuid::Uuid"
+                            },
+                            "price": {
+                                "type": "number",
+                                "x-rust-type": "decimal::Decimal"
+                            },
+                            "timeout": {
+                                "type": "integer",
+                                "x-rust-type": "std::time::Duration"
+                            },
+                            "regular_field": { "type": "string" }
+                        },
+                        "required": ["id", "price", "timeout"]
+                    }
+                }
+            }
+        }))
+        .expect("Failed to deserialize OpenAPI spec");
+
+        let (models, _, _) = parse_openapi(&openapi_spec).expect("Failed to parse OpenAPI spec");
+
+        let model = models.iter().find(|m| m.name() == "ComplexModel");
+        assert!(model.is_some(), "Expected ComplexModel model");
+
+        match model.unwrap() {
+            ModelType::Struct(struct_model) => {
+                // Verify all custom types
+                let id_field = struct_model.fields.iter().find(|f| f.name == "id");
+                assert_eq!(id_field.unwrap().field_type, "uuid::Uuid");
+
+                let price_field = struct_model.fields.iter().find(|f| f.name == "price");
+                assert_eq!(price_field.unwrap().field_type, "decimal::Decimal");
+
+                let timeout_field = struct_model.fields.iter().find(|f| f.name == "timeout");
+                assert_eq!(timeout_field.unwrap().field_type, "std::time::Duration");
+
+                // Verify regular field
+                let regular_field = struct_model
+                    .fields
+                    .iter()
+                    .find(|f| f.name == "regular_field");
+                assert_eq!(regular_field.unwrap().field_type, "String");
+
+                // Verify nullable flags for required/optional fields
+                assert!(!id_field.unwrap().is_nullable, "id should not be nullable");
+                assert!(
+                    !price_field.unwrap().is_nullable,
+                    "price should not be nullable"
+                );
+                assert!(
+                    !timeout_field.unwrap().is_nullable,
+                    "timeout should not be nullable"
+                );
+                // regular_field is not in required, but generator doesn't mark it as nullable
+                // (this is expected behavior - nullable only for explicitly nullable fields)
+            }
+            _ => panic!("Expected Struct"),
+        }
+    }
+
+    #[test]
+    fn test_resolve_parameters_follows_component_references() {
+        let openapi_spec: OpenAPI = serde_json::from_value(json!({
+            "openapi": "3.0.0",
+            "info": { "title": "Test API", "version": "1.0.0" },
+            "paths": {
+                "/items": {
+                    "get": {
+                        "operationId": "listItems",
+                        "parameters": [
+                            { "$ref": "#/components/parameters/PageSize" },
+                            { "$ref": "#/components/parameters/Alias" },
+                            { "$ref": "#/components/parameters/Missing" },
+                            { "name": "q", "in": "query", "schema": { "type": "string" } }
+                        ],
+                        "responses": { "200": { "description": "OK" } }
+                    }
+                }
+            },
+            "components": {
+                "parameters": {
+                    "PageSize": {
+                        "name": "pageSize",
+                        "in": "query",
+                        "schema": { "type": "integer" }
+                    },
+                    "Alias": { "$ref": "#/components/parameters/PageSize" }
+                }
+            }
+        }))
+        .expect("Failed to deserialize OpenAPI spec");
+
+        let Some(ReferenceOr::Item(path_item)) = openapi_spec.paths.paths.get("/items") else {
+            panic!("Expected /items path");
+        };
+        let operation = path_item.get.as_ref().expect("Expected GET operation");
+        let components = &openapi_spec.components.as_ref().unwrap().parameters;
+
+        let resolved = resolve_parameters(&operation.parameters, components);
+        let names: Vec<&str> = resolved
+            .iter()
+            .map(|p| p.parameter_data_ref().name.as_str())
+            .collect();
+
+        assert_eq!(names, vec!["pageSize", "pageSize", "q"]);
+    }
+
+    #[test]
+    fn test_operation_parameters_generate_params_model() {
+        let openapi_spec: OpenAPI = serde_json::from_value(json!({
+            "openapi": "3.0.0",
+            "info": { "title": "Test API", "version": "1.0.0" },
+            "paths": {
+                "/items/{itemId}": {
+                    "get": {
+                        "operationId": "getItem",
+                        "parameters": [
+                            {
+                                "name": "itemId",
+                                "in": "path",
+                                "required": true,
+                                "schema": { "type": "string", "format": "uuid" }
+                            },
+                            { "$ref": "#/components/parameters/PageSize" },
+                            { "name": "X-Trace", "in": "header", "schema": { "type": "string" } }
+                        ],
+                        "responses": { "200": { "description": "OK" } }
+                    }
+                }
+            },
+            "components": {
+                "parameters": {
+                    "PageSize": {
+                        "name": "pageSize",
+                        "in": "query",
+                        "description": "Items per page",
+                        "schema": { "type": "integer" }
+                    }
+                }
+            }
+        }))
+        .expect("Failed to deserialize OpenAPI spec");
+
+        let (models, _, _) = parse_openapi(&openapi_spec).expect("Failed to parse OpenAPI spec");
+
+        let params = models
+            .iter()
+            .find_map(|m| match m {
+                ModelType::Params(p) if p.name == "GetItemParams" => Some(p),
+                _ => None,
+            })
+            .expect("GetItemParams model should be generated");
+
+        assert_eq!(params.fields.len(), 3);
+        assert_eq!(params.fields[0].field.name, "itemId");
+        assert_eq!(params.fields[0].field.field_type, "Uuid");
+        assert!(params.fields[0].field.is_required);
+        assert_eq!(params.fields[0].location, ParameterLocation::Path);
+        assert_eq!(params.fields[1].field.name, "pageSize");
+        assert!(!params.fields[1].field.is_required);
+        assert_eq!(
+            params.fields[1].field.description.as_deref(),
+            Some("Items per page")
+        );
+        assert_eq!(params.fields[1].location, ParameterLocation::Query);
+        assert_eq!(params.fields[2].location, ParameterLocation::Header);
+    }
+
+    #[test]
+    fn test_params_model_covers_path_query_and_header() {
+        let openapi_spec: OpenAPI = serde_json::from_value(json!({
+            "openapi": "3.0.0",
+            "info": { "title": "Test API", "version": "1.0.0" },
+            "paths": {
+                "/pets/{petId}": {
+                    "get": {
+                        "operationId": "getPet",
+                        "parameters": [
+                            { "name": "petId", "in": "path", "schema": { "type": "string" } },
+                            { "name": "limit", "in": "query", "schema": { "type": "integer" } },
+                            {
+                                "name": "X-Request-Id",
+                                "in": "header",
+                                "required": true,
+                                "schema": { "type": "string" }
+                            }
+                        ],
+                        "responses": { "200": { "description": "OK" } }
+                    }
+                }
+            }
+        }))
+        .expect("Failed to deserialize OpenAPI spec");
+
+        let (models, _, _) = parse_openapi(&openapi_spec).expect("Failed to parse");
+        let params = models
+            .iter()
+            .find_map(|m| match m {
+                ModelType::Params(params) if params.name == "GetPetParams" => Some(params),
+                _ => None,
+            })
+            .expect("GetPetParams model should exist");
+
+        let summary: Vec<_> = params
+            .fields
+            .iter()
+            .map(|p| {
+                (
+                    p.field.name.as_str(),
+                    p.field.field_type.as_str(),
+                    p.field.is_required,
+                    p.location,
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("petId", "String", true, ParameterLocation::Path),
+                ("limit", "i64", false, ParameterLocation::Query),
+                ("X-Request-Id", "String", true, ParameterLocation::Header),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_path_level_parameters_merge_into_operations() {
+        let openapi_spec: OpenAPI = serde_json::from_value(json!({
+            "openapi": "3.0.0",
+            "info": { "title": "Test API", "version": "1.0.0" },
+            "paths": {
+                "/pets/{petId}": {
+                    "parameters": [
+                        { "name": "petId", "in": "path", "schema": { "type": "string" } },
+                        { "name": "verbose", "in": "query", "schema": { "type": "boolean" } }
+                    ],
+                    "get": {
+                        "operationId": "getPet",
+                        "parameters": [
+                            { "name": "verbose", "in": "query", "schema": { "type": "integer" } },
+                            { "name": "verbose", "in": "header", "schema": { "type": "string" } }
+                        ],
+                        "responses": { "200": { "description": "OK" } }
+                    },
+                    "delete": {
+                        "operationId": "deletePet",
+                        "responses": { "204": { "description": "Deleted" } }
+                    }
                 }
-            } else {
-                all_fields.insert(field.name.clone(), field);
             }
-        }
+        }))
+        .expect("Failed to deserialize OpenAPI spec");
+
+        let (models, requests, responses) = parse_openapi(&openapi_spec).expect("Failed to parse");
+        let params = |name: &str| {
+            models
+                .iter()
+                .find_map(|m| match m {
+                    ModelType::Params(params) if params.name == name => Some(
+                        params
+                            .fields
+                            .iter()
+                            .map(|p| (p.field.name.clone(), p.field.field_type.clone(), p.location))
+                            .collect::<Vec<_>>(),
+                    ),
+                    _ => None,
+                })
+                .unwrap_or_else(|| panic!("{name} model should exist"))
+        };
+
+        assert_eq!(
+            params("GetPetParams"),
+            vec![
+                (
+                    "petId".to_string(),
+                    "String".to_string(),
+                    ParameterLocation::Path
+                ),
+                (
+                    "verbose".to_string(),
+                    "i64".to_string(),
+                    ParameterLocation::Query
+                ),
+                (
+                    "verbose".to_string(),
+                    "String".to_string(),
+                    ParameterLocation::Header
+                ),
+            ]
+        );
+        assert_eq!(
+            params("DeletePetParams"),
+            vec![
+                (
+                    "petId".to_string(),
+                    "String".to_string(),
+                    ParameterLocation::Path
+                ),
+                (
+                    "verbose".to_string(),
+                    "bool".to_string(),
+                    ParameterLocation::Query
+                ),
+            ]
+        );
+
+        // The query parameter and header sharing a name get distinct Rust fields
+        let code = crate::generator::generate_models(&models, &requests, &responses)
+            .expect("Failed to generate models");
+        let get_pet = code
+            .split("pub struct GetPetParams {")
+            .nth(1)
+            .and_then(|rest| rest.split('}').next())
+            .expect("Expected GetPetParams struct");
+        assert!(get_pet.contains("    pub verbose: Option<i64>,\n"));
+        assert!(get_pet.contains(
+            "    #[serde(rename = \"verbose\")]\n    pub verbose_header: Option<String>,\n"
+        ));
+        assert_eq!(get_pet.matches("pub verbose:").count(), 1);
     }
 
-    // Now collect fields from all schemas
-    for schema_ref in all_of {
-        match schema_ref {
-            ReferenceOr::Reference { reference } => {
-                if let Some(schema_name) = reference.strip_prefix("#/components/schemas/") {
-                    if let Some(referenced_schema) = all_schemas.get(schema_name) {
-                        let (fields, inline_models) =
-                            extract_fields_from_schema(referenced_schema, all_schemas)?;
-                        // If we have an all_fields entry that is of type serde_json::Value, then we should replace it.
-                        less_value(fields, &mut all_fields);
-                        models.extend(inline_models);
+    #[test]
+    fn test_select_schemas_keeps_transitive_dependencies() {
+        let openapi_spec: OpenAPI = serde_json::from_value(json!({
+            "openapi": "3.0.0",
+            "info": { "title": "Test API", "version": "1.0.0" },
+            "paths": {},
+            "components": {
+                "schemas": {
+                    "User": {
+                        "type": "object",
+                        "properties": {
+                            "address": { "$ref": "#/components/schemas/Address" },
+                            "roles": {
+                                "type": "array",
+                                "items": { "$ref": "#/components/schemas/Role" }
+                            }
+                        }
+                    },
+                    "Address": {
+                        "type": "object",
+                        "properties": {
+                            "country": { "$ref": "#/components/schemas/Country" }
+                        }
+                    },
+                    "Country": { "type": "string", "enum": ["DE", "FR"] },
+                    "Role": { "type": "string", "enum": ["admin", "user"] },
+                    "Order": {
+                        "type": "object",
+                        "properties": { "id": { "type": "string" } }
                     }
                 }
             }
-            ReferenceOr::Item(_schema) => {
-                let (fields, inline_models) = extract_fields_from_schema(schema_ref, all_schemas)?;
-                // If we have an all_fields entry that is of type serde_json::Value, then we should replace it.
-                less_value(fields, &mut all_fields);
-                models.extend(inline_models);
-            }
-        }
-    }
-
-    // Update is_required for fields based on the merged required set
-    for field in all_fields.values_mut() {
-        if all_required_fields.contains(&field.name) {
-            field.is_required = true;
-        }
-    }
+        }))
+        .expect("Failed to deserialize OpenAPI spec");
 
-    Ok((all_fields.into_values().collect(), models))
-}
+        let selected =
+            select_schemas(&openapi_spec, &["User".to_string()]).expect("Failed to select schemas");
+        let (models, _, _) = parse_openapi(&selected).expect("Failed to parse OpenAPI spec");
 
-fn resolve_union_variants(
-    name: &str,
-    schemas: &[ReferenceOr<Schema>],
-    all_schemas: &IndexMap<String, ReferenceOr<Schema>>,
-) -> Result<(Vec<UnionVariant>, Vec<ModelType>)> {
-    use std::collections::BTreeSet;
+        let names: Vec<&str> = models.iter().map(|m| m.name()).collect();
+        assert!(names.contains(&"User"));
+        assert!(names.contains(&"Address"));
+        assert!(names.contains(&"Country"));
+        assert!(names.contains(&"Role"));
+        assert!(!names.contains(&"Order"));
+    }
 
-    let mut variants = Vec::new();
-    let mut models = Vec::new();
-    let mut enum_values: BTreeSet<String> = BTreeSet::new();
-    let mut is_all_simple_enum = true;
+    #[test]
+    fn test_select_schemas_rejects_unknown_schema() {
+        let openapi_spec: OpenAPI = serde_json::from_value(json!({
+            "openapi": "3.0.0",
+            "info": { "title": "Test API", "version": "1.0.0" },
+            "paths": {}
+        }))
+        .expect("Failed to deserialize OpenAPI spec");
 
-    for schema_ref in schemas {
-        let resolved = match schema_ref {
-            ReferenceOr::Reference { reference } => reference
-                .strip_prefix("#/components/schemas/")
-                .and_then(|n| all_schemas.get(n)),
-            ReferenceOr::Item(_) => Some(schema_ref),
-        };
+        assert!(select_schemas(&openapi_spec, &["Missing".to_string()]).is_err());
+    }
 
-        let Some(resolved_schema) = resolved else {
-            is_all_simple_enum = false;
-            continue;
-        };
+    #[test]
+    fn test_resolve_external_refs_imports_sibling_schemas() {
+        let dir = std::env::temp_dir().join(format!("oapi-ext-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).expect("Failed to create temp dir");
+        fs::write(
+            dir.join("common.yaml"),
+            r##"
+components:
+  schemas:
+    Address:
+      type: object
+      properties:
+        street:
+          type: string
+        country:
+          $ref: "#/components/schemas/Country"
+    Country:
+      type: string
+      enum: [US, DE]
+"##,
+        )
+        .expect("Failed to write common.yaml");
 
-        match resolved_schema {
-            ReferenceOr::Item(schema) => match &schema.schema_kind {
-                SchemaKind::Type(Type::String(s)) if !s.enumeration.is_empty() => {
-                    enum_values.extend(s.enumeration.iter().filter_map(|v| v.as_ref().cloned()));
-                }
-                SchemaKind::Type(Type::Integer(n)) if !n.enumeration.is_empty() => {
-                    enum_values.extend(
-                        n.enumeration
-                            .iter()
-                            .filter_map(|v| v.map(|num| format!("Value{num}"))),
-                    );
+        let openapi_spec: OpenAPI = serde_json::from_value(json!({
+            "openapi": "3.0.0",
+            "info": { "title": "Test API", "version": "1.0.0" },
+            "paths": {},
+            "components": {
+                "schemas": {
+                    "User": {
+                        "type": "object",
+                        "properties": {
+                            "address": { "$ref": "./common.yaml#/components/schemas/Address" }
+                        }
+                    }
                 }
+            }
+        }))
+        .expect("Failed to deserialize OpenAPI spec");
 
-                _ => is_all_simple_enum = false,
-            },
-            ReferenceOr::Reference { reference } => {
-                if let Some(n) = reference.strip_prefix("#/components/schemas/") {
-                    if let Some(ReferenceOr::Item(inner)) = all_schemas.get(n) {
-                        if let SchemaKind::Type(Type::String(s)) = &inner.schema_kind {
-                            let values: Vec<String> = s
-                                .enumeration
-                                .iter()
-                                .filter_map(|v| v.as_ref().cloned())
-                                .collect();
-                            enum_values.extend(values);
-                        } else {
-                            is_all_simple_enum = false;
+        let mut resolved = None;
+        let logs = capture_info_logs(|| {
+            resolved = Some(resolve_external_refs(&openapi_spec, &dir));
+        });
+        fs::remove_dir_all(&dir).ok();
+        let resolved = resolved.unwrap().expect("Failed to resolve");
+        // Address from the root document, then Country from common.yaml
+        assert!(logs.contains("Resolving external references: 1/2"));
+        assert!(logs.contains("Resolving external references: 2/2"));
+
+        let schemas = &resolved.components.as_ref().unwrap().schemas;
+        assert!(schemas.contains_key("Address"));
+        assert!(schemas.contains_key("Country"));
+
+        let (models, _, _) = parse_openapi(&resolved).expect("Failed to parse");
+        let user = models
+            .iter()
+            .find_map(|m| match m {
+                ModelType::Struct(model) if model.name == "User" => Some(model),
+                _ => None,
+            })
+            .expect("User model should exist");
+        assert_eq!(user.fields[0].field_type, "Address");
+        assert!(models
+            .iter()
+            .any(|m| matches!(m, ModelType::Enum(e) if e.name == "Country")));
+    }
+
+    #[test]
+    fn test_spec_fingerprint_covers_external_files() {
+        let dir = std::env::temp_dir().join(format!("oapi-ext-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).expect("Failed to create temp dir");
+        let openapi_spec: OpenAPI = serde_json::from_value(json!({
+            "openapi": "3.0.0",
+            "info": { "title": "Test API", "version": "1.0.0" },
+            "paths": {},
+            "components": {
+                "schemas": {
+                    "User": {
+                        "type": "object",
+                        "properties": {
+                            "role": { "$ref": "./common.yaml#/components/schemas/Role" }
                         }
                     }
                 }
             }
-        }
+        }))
+        .expect("Failed to deserialize OpenAPI spec");
+        let fingerprint = |common: &str| {
+            fs::write(dir.join("common.yaml"), common).expect("Failed to write common.yaml");
+            let resolved = resolve_external_refs(&openapi_spec, &dir).expect("Failed to resolve");
+            crate::generator::spec_fingerprint(&resolved).expect("Failed to fingerprint")
+        };
+
+        let before = fingerprint("components:\n  schemas:\n    Role:\n      type: string\n");
+        let after = fingerprint(
+            "components:\n  schemas:\n    Role:\n      type: string\n      enum: [admin]\n",
+        );
+        fs::remove_dir_all(&dir).ok();
+
+        assert_ne!(
+            before, after,
+            "Editing a referenced file should change the fingerprint"
+        );
     }
-    if is_all_simple_enum && !enum_values.is_empty() {
-        let enum_name = to_pascal_case(name);
-        let enum_model = ModelType::Enum(EnumModel {
-            name: enum_name.clone(),
-            variants: enum_values.iter().map(|v| to_pascal_case(v)).collect(),
-            description: None,
-            custom_attrs: None, // Collective enum from multiple schemas, no single source for attrs
-        });
 
-        return Ok((vec![], vec![enum_model]));
+    #[test]
+    fn test_resolve_external_refs_reports_missing_file() {
+        let openapi_spec: OpenAPI = serde_json::from_value(json!({
+            "openapi": "3.0.0",
+            "info": { "title": "Test API", "version": "1.0.0" },
+            "paths": {},
+            "components": {
+                "schemas": {
+                    "User": { "$ref": "./missing.yaml#/components/schemas/User" }
+                }
+            }
+        }))
+        .expect("Failed to deserialize OpenAPI spec");
+
+        assert!(resolve_external_refs(&openapi_spec, Path::new("/nonexistent")).is_err());
     }
 
-    // fallback for usual union-schemas
-    for (index, schema_ref) in schemas.iter().enumerate() {
-        match schema_ref {
-            ReferenceOr::Reference { reference } => {
-                if let Some(schema_name) = reference.strip_prefix("#/components/schemas/") {
-                    if let Some(referenced_schema) = all_schemas.get(schema_name) {
-                        if let ReferenceOr::Item(schema) = referenced_schema {
-                            if matches!(schema.schema_kind, SchemaKind::OneOf { .. }) {
-                                variants.push(UnionVariant {
-                                    name: to_pascal_case(schema_name),
-                                    fields: vec![],
-                                    primitive_type: None,
-                                });
-                            } else {
-                                let (fields, inline_models) =
-                                    extract_fields_from_schema(referenced_schema, all_schemas)?;
-                                variants.push(UnionVariant {
-                                    name: to_pascal_case(schema_name),
-                                    fields,
-                                    primitive_type: None,
-                                });
-                                models.extend(inline_models);
+    #[test]
+    fn test_load_openapi_downgrades_31_documents() {
+        let content = json!({
+            "openapi": "3.1.0",
+            "info": { "title": "Test API", "version": "1.0.0" },
+            "components": {
+                "schemas": {
+                    "Owner": {
+                        "type": "object",
+                        "properties": { "name": { "type": "string" } }
+                    },
+                    "Pet": {
+                        "type": "object",
+                        "required": ["id", "owner"],
+                        "properties": {
+                            "id": { "type": "integer", "exclusiveMinimum": 0 },
+                            "name": { "type": ["string", "null"], "examples": ["Rex"] },
+                            "owner": {
+                                "oneOf": [
+                                    { "$ref": "#/components/schemas/Owner" },
+                                    { "type": "null" }
+                                ]
                             }
                         }
                     }
                 }
             }
-            ReferenceOr::Item(schema) => match &schema.schema_kind {
-                SchemaKind::Type(Type::String(_)) => {
-                    variants.push(UnionVariant {
-                        name: "String".to_string(),
-                        fields: vec![],
-                        primitive_type: Some("String".to_string()),
-                    });
-                }
+        })
+        .to_string();
 
-                SchemaKind::Type(Type::Integer(_)) => {
-                    variants.push(UnionVariant {
-                        name: "Integer".to_string(),
-                        fields: vec![],
-                        primitive_type: Some("i64".to_string()),
-                    });
-                }
+        let openapi = load_openapi(&content, false).expect("3.1 document should load");
+        assert_eq!(openapi.openapi, "3.0.3");
 
-                SchemaKind::Type(Type::Number(_)) => {
-                    variants.push(UnionVariant {
-                        name: "Number".to_string(),
-                        fields: vec![],
-                        primitive_type: Some("f64".to_string()),
-                    });
-                }
+        let (models, _, _) = parse_openapi(&openapi).expect("Failed to parse");
+        let pet = models
+            .iter()
+            .find_map(|m| match m {
+                ModelType::Struct(model) if model.name == "Pet" => Some(model),
+                _ => None,
+            })
+            .expect("Pet model should exist");
+        let field = |name: &str| pet.fields.iter().find(|f| f.name == name).unwrap();
+
+        assert_eq!(field("id").field_type, "i64");
+        assert_eq!(field("name").field_type, "String");
+        assert!(field("name").is_nullable);
+        assert_eq!(field("owner").field_type, "Owner");
+        assert!(field("owner").is_nullable);
+    }
 
-                SchemaKind::Type(Type::Boolean(_)) => {
-                    variants.push(UnionVariant {
-                        name: "Boolean".to_string(),
-                        fields: vec![],
-                        primitive_type: Some("Boolean".to_string()),
-                    });
+    #[test]
+    fn test_load_openapi_converts_swagger_2() {
+        let content = json!({
+            "swagger": "2.0",
+            "info": { "title": "Test API", "version": "1.0.0" },
+            "host": "api.example.com",
+            "basePath": "/v1",
+            "parameters": {
+                "PetBody": {
+                    "in": "body",
+                    "name": "body",
+                    "required": true,
+                    "schema": { "$ref": "#/definitions/Pet" }
                 }
-
-                _ => {
-                    let (fields, inline_models) =
-                        extract_fields_from_schema(schema_ref, all_schemas)?;
-                    let variant_name = format!("Variant{index}");
-                    variants.push(UnionVariant {
-                        name: variant_name,
-                        fields,
-                        primitive_type: None,
-                    });
-                    models.extend(inline_models);
+            },
+            "paths": {
+                "/pets": {
+                    "get": {
+                        "operationId": "listPets",
+                        "parameters": [
+                            { "name": "limit", "in": "query", "type": "integer" }
+                        ],
+                        "responses": {
+                            "200": {
+                                "description": "ok",
+                                "schema": { "$ref": "#/definitions/Pet" }
+                            }
+                        }
+                    },
+                    "post": {
+                        "operationId": "createPet",
+                        "parameters": [{ "$ref": "#/parameters/PetBody" }],
+                        "responses": { "201": { "description": "created" } }
+                    }
                 }
             },
-        }
-    }
+            "definitions": {
+                "Pet": {
+                    "type": "object",
+                    "properties": {
+                        "tag": { "type": "string", "x-nullable": true }
+                    }
+                }
+            }
+        })
+        .to_string();
 
-    Ok((variants, models))
-}
+        let openapi = load_openapi(&content, false).expect("Swagger document should load");
+        assert_eq!(openapi.servers[0].url, "https://api.example.com/v1");
 
-fn extract_fields_from_schema(
-    schema_ref: &ReferenceOr<Schema>,
-    _all_schemas: &IndexMap<String, ReferenceOr<Schema>>,
-) -> Result<(Vec<Field>, Vec<ModelType>)> {
-    let mut fields = Vec::new();
-    let mut inline_models = Vec::new();
+        let components = openapi.components.as_ref().unwrap();
+        assert!(components.schemas.contains_key("Pet"));
+        assert!(components.request_bodies.contains_key("PetBody"));
 
-    match schema_ref {
-        ReferenceOr::Reference { .. } => Ok((fields, inline_models)),
-        ReferenceOr::Item(schema) => {
-            match &schema.schema_kind {
-                SchemaKind::Type(Type::Object(obj)) => {
-                    for (field_name, field_schema) in &obj.properties {
-                        let (field_info, inline_model) = match field_schema {
-                            ReferenceOr::Item(boxed_schema) => extract_field_info(
-                                field_name,
-                                &ReferenceOr::Item((**boxed_schema).clone()),
-                                _all_schemas,
-                            )?,
-                            ReferenceOr::Reference { reference } => extract_field_info(
-                                field_name,
-                                &ReferenceOr::Reference {
-                                    reference: reference.clone(),
-                                },
-                                _all_schemas,
-                            )?,
-                        };
+        let pets = openapi.paths.paths["/pets"].as_item().unwrap();
+        let list = pets.get.as_ref().unwrap();
+        match &list.parameters[0] {
+            ReferenceOr::Item(Parameter::Query { parameter_data, .. }) => {
+                assert_eq!(parameter_data.name, "limit");
+            }
+            other => panic!("Expected query parameter, got {other:?}"),
+        }
+        assert!(matches!(
+            pets.post.as_ref().unwrap().request_body,
+            Some(ReferenceOr::Reference { ref reference })
+                if reference == "#/components/requestBodies/PetBody"
+        ));
+
+        let (models, _, responses) = parse_openapi(&openapi).expect("Failed to parse");
+        let pet = models
+            .iter()
+            .find_map(|m| match m {
+                ModelType::Struct(model) if model.name == "Pet" => Some(model),
+                _ => None,
+            })
+            .expect("Pet model should exist");
+        assert!(pet.fields[0].is_nullable);
+        assert!(!responses.is_empty());
+    }
+
+    #[test]
+    fn test_downgrade_31_rewrites_schema_keywords() {
+        let mut document = json!({
+            "openapi": "3.1.0",
+            "info": { "title": "Test API", "version": "1.0.0" },
+            "components": {
+                "schemas": {
+                    "Value": { "type": ["string", "integer"] },
+                    "Kind": { "const": "dog" },
+                    "Blob": { "type": "string", "contentEncoding": "base64" },
+                    "Limit": { "type": "number", "exclusiveMaximum": 10 }
+                }
+            }
+        });
 
-                        let is_nullable = field_info.is_nullable
-                            || field_name == "value"
-                            || field_name == "default_value";
+        downgrade_openapi_31(&mut document);
+        let schemas = &document["components"]["schemas"];
 
-                        let field_type = field_info.field_type.clone();
+        assert_eq!(document["paths"], json!({}));
+        assert_eq!(
+            schemas["Value"],
+            json!({ "anyOf": [{ "type": "string" }, { "type": "integer" }] })
+        );
+        assert_eq!(
+            schemas["Kind"],
+            json!({ "type": "string", "enum": ["dog"] })
+        );
+        assert_eq!(schemas["Blob"]["format"], "byte");
+        assert_eq!(schemas["Limit"]["maximum"], 10);
+        assert_eq!(schemas["Limit"]["exclusiveMaximum"], true);
+    }
 
-                        let is_required = obj.required.contains(field_name);
-                        fields.push(Field {
-                            name: field_name.clone(),
-                            field_type,
-                            format: field_info.format,
-                            is_required,
-                            is_nullable,
-                            is_array_ref: field_info.is_array_ref,
-                            description: field_info.description,
-                        });
-                        if let Some(inline_model) = inline_model {
-                            match &inline_model {
-                                ModelType::Struct(m) if m.fields.is_empty() => {}
-                                _ => inline_models.push(inline_model),
-                            }
+    #[test]
+    fn test_additional_properties_generate_typed_maps() {
+        let openapi_spec: OpenAPI = serde_json::from_value(json!({
+            "openapi": "3.0.0",
+            "info": { "title": "Test API", "version": "1.0.0" },
+            "paths": {},
+            "components": {
+                "schemas": {
+                    "Pet": {
+                        "type": "object",
+                        "properties": { "name": { "type": "string" } }
+                    },
+                    "Inventory": {
+                        "type": "object",
+                        "properties": {
+                            "pets": {
+                                "type": "object",
+                                "additionalProperties": { "$ref": "#/components/schemas/Pet" }
+                            },
+                            "litters": {
+                                "type": "object",
+                                "additionalProperties": {
+                                    "type": "array",
+                                    "items": { "$ref": "#/components/schemas/Pet" }
+                                }
+                            },
+                            "matrix": {
+                                "type": "object",
+                                "additionalProperties": {
+                                    "type": "object",
+                                    "additionalProperties": { "type": "integer" }
+                                }
+                            },
+                            "extra": { "type": "object", "additionalProperties": true }
                         }
                     }
                 }
-                SchemaKind::Type(Type::String(s)) if !s.enumeration.is_empty() => {
-                    let name = schema
-                        .schema_data
-                        .title
-                        .clone()
-                        .unwrap_or_else(|| "AnonymousStringEnum".to_string());
+            }
+        }))
+        .expect("Failed to deserialize OpenAPI spec");
 
-                    let enum_model = ModelType::Enum(EnumModel {
-                        name,
-                        variants: s
-                            .enumeration
-                            .iter()
-                            .filter_map(|v| v.as_ref().map(|s| to_pascal_case(s)))
-                            .collect(),
-                        description: schema.schema_data.description.clone(),
-                        custom_attrs: extract_custom_attrs(schema),
-                    });
+        let (models, _, _) = parse_openapi(&openapi_spec).expect("Failed to parse");
+        let inventory = models
+            .iter()
+            .find_map(|m| match m {
+                ModelType::Struct(model) if model.name == "Inventory" => Some(model),
+                _ => None,
+            })
+            .expect("Inventory model should exist");
+        let field_type = |name: &str| {
+            inventory
+                .fields
+                .iter()
+                .find(|f| f.name == name)
+                .map(|f| f.field_type.clone())
+                .unwrap()
+        };
 
-                    inline_models.push(enum_model);
+        assert_eq!(field_type("pets"), "std::collections::HashMap<String, Pet>");
+        assert_eq!(
+            field_type("litters"),
+            "std::collections::HashMap<String, Vec<Pet>>"
+        );
+        assert_eq!(
+            field_type("matrix"),
+            "std::collections::HashMap<String, std::collections::HashMap<String, i64>>"
+        );
+        assert_eq!(
+            field_type("extra"),
+            "std::collections::HashMap<String, serde_json::Value>"
+        );
+        // Inline maps are typed in place rather than emitted as aliases
+        assert!(!models
+            .iter()
+            .any(|m| matches!(m, ModelType::TypeAlias(alias) if alias.name == "Pets")));
+    }
+
+    #[test]
+    fn test_inline_objects_become_parent_qualified_structs() {
+        let openapi_spec: OpenAPI = serde_json::from_value(json!({
+            "openapi": "3.0.0",
+            "info": { "title": "Test API", "version": "1.0.0" },
+            "paths": {},
+            "components": {
+                "schemas": {
+                    "User": {
+                        "type": "object",
+                        "properties": {
+                            "address": {
+                                "type": "object",
+                                "properties": {
+                                    "street": { "type": "string" },
+                                    "geo": {
+                                        "type": "object",
+                                        "properties": { "lat": { "type": "number" } }
+                                    }
+                                }
+                            }
+                        }
+                    },
+                    "Company": {
+                        "type": "object",
+                        "properties": {
+                            "address": {
+                                "type": "object",
+                                "properties": { "city": { "type": "string" } }
+                            }
+                        }
+                    }
                 }
-                SchemaKind::Type(Type::Integer(n)) if !n.enumeration.is_empty() => {
-                    let name = schema
-                        .schema_data
-                        .title
-                        .clone()
-                        .unwrap_or_else(|| "AnonymousIntEnum".to_string());
+            }
+        }))
+        .expect("Failed to deserialize OpenAPI spec");
 
-                    let enum_model = ModelType::Enum(EnumModel {
-                        name,
-                        variants: n
-                            .enumeration
-                            .iter()
-                            .filter_map(|v| v.map(|num| format!("Value{num}")))
-                            .collect(),
-                        description: schema.schema_data.description.clone(),
-                        custom_attrs: extract_custom_attrs(schema),
-                    });
+        let (models, _, _) = parse_openapi(&openapi_spec).expect("Failed to parse");
+        let find_struct = |name: &str| {
+            models
+                .iter()
+                .find_map(|m| match m {
+                    ModelType::Struct(model) if model.name == name => Some(model),
+                    _ => None,
+                })
+                .unwrap_or_else(|| panic!("{name} model should exist"))
+        };
 
-                    inline_models.push(enum_model);
-                }
+        assert_eq!(find_struct("User").fields[0].field_type, "UserAddress");
+        assert_eq!(
+            find_struct("Company").fields[0].field_type,
+            "CompanyAddress"
+        );
+        let address = find_struct("UserAddress");
+        assert!(address
+            .fields
+            .iter()
+            .any(|f| f.name == "geo" && f.field_type == "UserAddressGeo"));
+        assert_eq!(find_struct("UserAddressGeo").fields[0].field_type, "f64");
+    }
 
-                _ => {}
+    #[test]
+    fn test_inline_array_items_become_named_types() {
+        let openapi_spec: OpenAPI = serde_json::from_value(json!({
+            "openapi": "3.0.0",
+            "info": { "title": "Test API", "version": "1.0.0" },
+            "paths": {},
+            "components": {
+                "schemas": {
+                    "User": {
+                        "type": "object",
+                        "properties": {
+                            "contacts": {
+                                "type": "array",
+                                "items": {
+                                    "type": "object",
+                                    "properties": { "email": { "type": "string" } }
+                                }
+                            },
+                            "tags": { "type": "array", "items": { "type": "string" } },
+                            "grid": {
+                                "type": "array",
+                                "items": { "type": "array", "items": { "type": "integer" } }
+                            }
+                        }
+                    }
+                }
             }
+        }))
+        .expect("Failed to deserialize OpenAPI spec");
 
-            Ok((fields, inline_models))
-        }
-    }
-}
+        let (models, _, _) = parse_openapi(&openapi_spec).expect("Failed to parse");
+        let user = models
+            .iter()
+            .find_map(|m| match m {
+                ModelType::Struct(model) if model.name == "User" => Some(model),
+                _ => None,
+            })
+            .expect("User model should exist");
+        let field = |name: &str| user.fields.iter().find(|f| f.name == name).unwrap();
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use serde_json::json;
+        assert_eq!(field("contacts").field_type, "UserContactsItem");
+        assert!(field("contacts").is_array_ref);
+        assert!(models
+            .iter()
+            .any(|m| matches!(m, ModelType::Struct(s) if s.name == "UserContactsItem")));
+
+        // Primitive items are used directly instead of through a `Vec` alias
+        assert_eq!(field("tags").field_type, "String");
+        assert_eq!(field("grid").field_type, "Vec<i64>");
+        assert!(!models.iter().any(|m| m.name() == "UserTagsItem"));
+    }
 
     #[test]
-    fn test_parse_nested_object_array_generates_model() {
+    fn test_response_headers_generate_struct() {
         let openapi_spec: OpenAPI = serde_json::from_value(json!({
             "openapi": "3.0.0",
             "info": { "title": "Test API", "version": "1.0.0" },
             "paths": {
-                "/items": {
+                "/pets": {
                     "get": {
-                        "operationId": "getItems",
+                        "operationId": "listPets",
                         "responses": {
                             "200": {
                                 "description": "OK",
-                                "content": {
-                                    "application/json": {
-                                        "schema": {
-                                            "type": "object",
-                                            "properties": {
-                                                "exampleField": {
-                                                    "type": "string"
-                                                },
-                                                "objectArray": {
-                                                    "type": "array",
-                                                    "items": {
-                                                        "type": "object",
-                                                        "properties": {
-                                                            "objectArrayItemField": {
-                                                                "type": "string"
-                                                            },
-                                                        }
-                                                    }
-                                                }
-                                            }
-                                        },
-                                    }
+                                "headers": {
+                                    "X-Next-Cursor": { "schema": { "type": "string" } },
+                                    "X-RateLimit-Remaining": {
+                                        "required": true,
+                                        "schema": { "type": "integer" }
+                                    },
+                                    "ETag": { "$ref": "#/components/headers/ETag" },
+                                    "Content-Type": { "schema": { "type": "string" } }
                                 }
                             }
                         }
                     }
                 }
+            },
+            "components": {
+                "headers": {
+                    "ETag": { "schema": { "type": "string" } }
+                }
             }
         }))
         .expect("Failed to deserialize OpenAPI spec");
 
-        let (models, _requests, responses) =
-            parse_openapi(&openapi_spec).expect("Failed to parse OpenAPI spec");
+        let (models, _, _) = parse_openapi(&openapi_spec).expect("Failed to parse");
+        let headers = models
+            .iter()
+            .find_map(|m| match m {
+                ModelType::Struct(model) if model.name == "ListPetsResponse200Headers" => {
+                    Some(model)
+                }
+                _ => None,
+            })
+            .expect("ListPetsResponse200Headers model should exist");
 
-        // 1. Verify that response model was created
-        assert_eq!(responses.len(), 1);
-        let response_model = &responses[0];
-        assert_eq!(response_model.name, "GetItems");
+        let summary: Vec<_> = headers
+            .fields
+            .iter()
+            .map(|f| (f.name.as_str(), f.field_type.as_str(), f.is_required))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("ETag", "String", false),
+                ("X-Next-Cursor", "String", false),
+                ("X-RateLimit-Remaining", "i64", true),
+            ]
+        );
+    }
 
-        // 2. Verify that response schema references the top level object
-        assert_eq!(response_model.schema, "GetItemsResponse200");
+    #[test]
+    fn test_integer_formats_pick_width() {
+        let openapi_spec: OpenAPI = serde_json::from_value(json!({
+            "openapi": "3.0.0",
+            "info": { "title": "Test API", "version": "1.0.0" },
+            "paths": {},
+            "components": {
+                "schemas": {
+                    "Counter": {
+                        "type": "object",
+                        "required": ["small", "big", "plain", "custom"],
+                        "properties": {
+                            "small": { "type": "integer", "format": "int32" },
+                            "big": { "type": "integer", "format": "int64" },
+                            "plain": { "type": "integer" },
+                            "custom": {
+                                "type": "integer",
+                                "format": "int32",
+                                "x-rust-type": "u16"
+                            }
+                        }
+                    }
+                }
+            }
+        }))
+        .expect("Failed to deserialize OpenAPI spec");
 
-        // 3. Verify that the nested object model was generated
-        let inline_model = models.iter().find(|m| m.name() == "ObjectArrayItem");
-        assert!(
-            inline_model.is_some(),
-            "Expected a model named 'ObjectArrayItem' to be generated"
-        );
+        let (models, _, _) = parse_openapi(&openapi_spec).expect("Failed to parse OpenAPI spec");
+        let ModelType::Struct(model) = &models[0] else {
+            panic!("Expected Struct");
+        };
+        let field = |name: &str| model.fields.iter().find(|f| f.name == name).unwrap();
+
+        assert_eq!(field("small").field_type, "i32");
+        assert_eq!(field("small").format, "int32");
+        assert_eq!(field("big").field_type, "i64");
+        assert_eq!(field("big").format, "int64");
+        assert_eq!(field("plain").field_type, "i64");
+        assert_eq!(field("plain").format, "integer");
+        assert_eq!(field("custom").field_type, "u16");
+        assert_eq!(field("custom").format, "int32");
+    }
 
-        if let Some(ModelType::Struct(model)) = inline_model {
-            assert_eq!(model.fields.len(), 1);
+    #[test]
+    fn test_number_formats_pick_width() {
+        let openapi_spec: OpenAPI = serde_json::from_value(json!({
+            "openapi": "3.0.0",
+            "info": { "title": "Test API", "version": "1.0.0" },
+            "paths": {},
+            "components": {
+                "schemas": {
+                    "Reading": {
+                        "type": "object",
+                        "required": ["single", "double", "plain"],
+                        "properties": {
+                            "single": { "type": "number", "format": "float" },
+                            "double": { "type": "number", "format": "double" },
+                            "plain": { "type": "number" }
+                        }
+                    }
+                }
+            }
+        }))
+        .expect("Failed to deserialize OpenAPI spec");
+
+        let (models, _, _) = parse_openapi(&openapi_spec).expect("Failed to parse OpenAPI spec");
+        let ModelType::Struct(model) = &models[0] else {
+            panic!("Expected Struct");
+        };
+        let field = |name: &str| model.fields.iter().find(|f| f.name == name).unwrap();
+
+        assert_eq!(field("single").field_type, "f32");
+        assert_eq!(field("single").format, "float");
+        assert_eq!(field("double").field_type, "f64");
+        assert_eq!(field("double").format, "double");
+        assert_eq!(field("plain").field_type, "f64");
+        assert_eq!(field("plain").format, "number");
+    }
+
+    #[test]
+    fn test_ip_formats_use_std_net_types() {
+        let openapi_spec: OpenAPI = serde_json::from_value(json!({
+            "openapi": "3.0.0",
+            "info": { "title": "Test API", "version": "1.0.0" },
+            "paths": {},
+            "components": {
+                "schemas": {
+                    "Peer": {
+                        "type": "object",
+                        "properties": {
+                            "v4": { "type": "string", "format": "ipv4" },
+                            "v6": { "type": "string", "format": "ipv6" }
+                        }
+                    }
+                }
+            }
+        }))
+        .expect("Failed to deserialize OpenAPI spec");
+
+        let (models, _, _) = parse_openapi(&openapi_spec).expect("Failed to parse OpenAPI spec");
+        let ModelType::Struct(model) = &models[0] else {
+            panic!("Expected Struct");
+        };
 
-            assert_eq!(model.fields[0].name, "objectArrayItemField");
-            assert_eq!(model.fields[0].field_type, "String");
-        } else {
-            panic!("Expected a Struct model for GetItemsResponse");
-        }
+        assert_eq!(model.fields[0].field_type, "std::net::Ipv4Addr");
+        assert_eq!(model.fields[0].format, "ipv4");
+        assert_eq!(model.fields[1].field_type, "std::net::Ipv6Addr");
+        assert_eq!(model.fields[1].format, "ipv6");
     }
 
     #[test]
-    fn test_parse_top_level_array_object_generates_model() {
+    fn test_recursive_references_are_boxed() {
         let openapi_spec: OpenAPI = serde_json::from_value(json!({
             "openapi": "3.0.0",
             "info": { "title": "Test API", "version": "1.0.0" },
-            "paths": {
-                "/items": {
-                    "get": {
-                        "operationId": "getItems",
-                        "responses": {
-                            "200": {
-                                "description": "OK",
-                                "content": {
-                                    "application/json": {
-                                        "schema": {
-                                            "type": "array",
-                                            "items": {
-                                                "type": "object",
-                                                "properties": {
-                                                    "exampleField": {
-                                                        "type": "string"
-                                                    },
-                                                    "anotherExampleField": {
-                                                        "type": "string"
-                                                    }
-                                                }
-                                            }
-                                        },
-                                    }
-                                }
+            "paths": {},
+            "components": {
+                "schemas": {
+                    "Node": {
+                        "type": "object",
+                        "properties": {
+                            "next": { "$ref": "#/components/schemas/Node" },
+                            "children": {
+                                "type": "array",
+                                "items": { "$ref": "#/components/schemas/Node" }
                             }
                         }
+                    },
+                    "Holder": {
+                        "type": "object",
+                        "properties": { "item": { "$ref": "#/components/schemas/Item" } }
+                    },
+                    "Item": {
+                        "type": "object",
+                        "properties": { "holder": { "$ref": "#/components/schemas/Holder" } }
                     }
                 }
             }
         }))
         .expect("Failed to deserialize OpenAPI spec");
 
-        let (models, _requests, responses) =
-            parse_openapi(&openapi_spec).expect("Failed to parse OpenAPI spec");
-
-        // 1. Verify that response model was created
-        assert_eq!(responses.len(), 1);
-        let response_model = &responses[0];
-        assert_eq!(response_model.name, "GetItems");
-
-        // 2. Verify that response schema references a Vec of the top level array object
-        assert_eq!(response_model.schema, "Vec<GetItemsResponseArrayObject200>");
-
-        // 3. Verify that the array object model was generated
-        let inline_model = models
-            .iter()
-            .find(|m| m.name() == "GetItemsResponseArrayObject200");
-        assert!(
-            inline_model.is_some(),
-            "Expected a model named 'GetItemsResponseArrayObject200' to be generated"
-        );
-
-        if let Some(ModelType::Struct(model)) = inline_model {
-            assert_eq!(model.fields.len(), 2);
-            assert_eq!(model.fields[0].name, "anotherExampleField");
-            assert_eq!(model.fields[0].field_type, "String");
+        let (models, _, _) = parse_openapi(&openapi_spec).expect("Failed to parse OpenAPI spec");
+        let field_type = |model: &str, field: &str| {
+            let Some(ModelType::Struct(m)) = models.iter().find(|m| m.name() == model) else {
+                panic!("Expected {model} struct");
+            };
+            m.fields
+                .iter()
+                .find(|f| f.name == field)
+                .unwrap()
+                .field_type
+                .clone()
+        };
 
-            assert_eq!(model.fields[1].name, "exampleField");
-            assert_eq!(model.fields[1].field_type, "String");
-        } else {
-            panic!("Expected a Struct model for GetItemsResponse");
-        }
+        assert_eq!(field_type("Node", "next"), "Box<Node>");
+        // Vec already provides indirection
+        assert_eq!(field_type("Node", "children"), "Node");
+        // Only the edge closing the cycle is boxed
+        assert_eq!(field_type("Holder", "item"), "Item");
+        assert_eq!(field_type("Item", "holder"), "Box<Holder>");
     }
 
     #[test]
-    fn test_parse_inline_response_generates_model() {
+    fn test_all_of_follows_referenced_compositions_and_breaks_cycles() {
         let openapi_spec: OpenAPI = serde_json::from_value(json!({
             "openapi": "3.0.0",
             "info": { "title": "Test API", "version": "1.0.0" },
-            "paths": {
-                "/items": {
-                    "get": {
-                        "operationId": "getItem",
-                        "responses": {
-                            "200": {
-                                "description": "OK",
-                                "content": {
-                                    "application/json": {
-                                        "schema": {
-                                            "type": "object",
-                                            "properties": {
-                                                "name": { "type": "string" },
-                                                "value": { "type": "integer" }
-                                            },
-                                            "required": ["name"]
-                                        }
-                                    }
-                                }
-                            }
-                        }
+            "paths": {},
+            "components": {
+                "schemas": {
+                    "Base": {
+                        "type": "object",
+                        "required": ["id"],
+                        "properties": { "id": { "type": "string" } }
+                    },
+                    "Parent": {
+                        "allOf": [
+                            { "$ref": "#/components/schemas/Base" },
+                            { "type": "object", "properties": { "p": { "type": "string" } } }
+                        ]
+                    },
+                    "Child": {
+                        "allOf": [
+                            { "$ref": "#/components/schemas/Parent" },
+                            { "type": "object", "properties": { "c": { "type": "string" } } }
+                        ]
+                    },
+                    "Left": {
+                        "allOf": [
+                            { "$ref": "#/components/schemas/Right" },
+                            { "type": "object", "properties": { "l": { "type": "string" } } }
+                        ]
+                    },
+                    "Right": {
+                        "allOf": [
+                            { "$ref": "#/components/schemas/Left" },
+                            { "type": "object", "properties": { "r": { "type": "string" } } }
+                        ]
                     }
                 }
             }
         }))
         .expect("Failed to deserialize OpenAPI spec");
 
-        let (models, _requests, responses) =
-            parse_openapi(&openapi_spec).expect("Failed to parse OpenAPI spec");
-
-        // 1. Verify that response model was created
-        assert_eq!(responses.len(), 1);
-        let response_model = &responses[0];
-        assert_eq!(response_model.name, "GetItem");
-
-        // 2. Verify that response schema references a NEW model, not Value
-        assert_eq!(response_model.schema, "GetItemResponse200");
+        let (models, _, _) = parse_openapi(&openapi_spec).expect("Failed to parse OpenAPI spec");
+        let field_names = |name: &str| {
+            let Some(ModelType::Composition(comp)) = models.iter().find(|m| m.name() == name)
+            else {
+                panic!("Expected {name} composition");
+            };
+            comp.all_fields
+                .iter()
+                .map(|f| (f.name.clone(), f.is_required))
+                .collect::<Vec<_>>()
+        };
 
-        // 3. Verify that the response model itself was generated
-        let inline_model = models.iter().find(|m| m.name() == "GetItemResponse200");
-        assert!(
-            inline_model.is_some(),
-            "Expected a model named 'GetItemResponse200' to be generated"
+        assert_eq!(
+            field_names("Child"),
+            vec![
+                ("id".to_string(), true),
+                ("p".to_string(), false),
+                ("c".to_string(), false)
+            ]
+        );
+        let left: Vec<String> = field_names("Left").into_iter().map(|(n, _)| n).collect();
+        assert_eq!(left, vec!["r", "l"]);
+        let right: Vec<String> = field_names("Right").into_iter().map(|(n, _)| n).collect();
+        assert_eq!(right, vec!["l", "r"]);
+
+        assert_eq!(
+            collect_warnings(&openapi_spec),
+            vec!["allOf cycle Left -> Right -> Left; the repeated member is skipped"]
         );
-
-        if let Some(ModelType::Struct(model)) = inline_model {
-            assert_eq!(model.fields.len(), 2);
-            assert_eq!(model.fields[0].name, "name");
-            assert_eq!(model.fields[0].field_type, "String");
-
-            assert_eq!(model.fields[1].name, "value");
-            assert_eq!(model.fields[1].field_type, "i64");
-        } else {
-            panic!("Expected a Struct model for GetItemResponse");
-        }
     }
 
     #[test]
-    fn test_parse_inline_request_body_generates_model() {
+    fn test_not_generates_base_type_and_warns() {
         let openapi_spec: OpenAPI = serde_json::from_value(json!({
             "openapi": "3.0.0",
             "info": { "title": "Test API", "version": "1.0.0" },
-            "paths": {
-                "/items": {
-                    "post": {
-                        "operationId": "createItem",
-                        "requestBody": {
-                            "content": {
-                                "application/json": {
-                                    "schema": {
-                                        "type": "object",
-                                        "properties": {
-                                            "name": { "type": "string" },
-                                            "value": { "type": "integer" }
-                                        },
-                                        "required": ["name"]
-                                    }
-                                }
+            "paths": {},
+            "components": {
+                "schemas": {
+                    "Account": {
+                        "type": "object",
+                        "required": ["name"],
+                        "properties": {
+                            "name": {
+                                "type": "string",
+                                "not": { "enum": ["root"] }
                             }
                         },
-                        "responses": { "200": { "description": "OK" } }
+                        "not": { "required": ["legacyId"] }
+                    },
+                    "Anything": {
+                        "not": { "type": "string" }
                     }
                 }
             }
         }))
         .expect("Failed to deserialize OpenAPI spec");
 
-        let (models, requests, _responses) =
-            parse_openapi(&openapi_spec).expect("Failed to parse OpenAPI spec");
-
-        // 1. Verify that request model was created
-        assert_eq!(requests.len(), 1);
-        let request_model = &requests[0];
-        assert_eq!(request_model.name, "CreateItemRequest");
+        let (models, _, _) = parse_openapi(&openapi_spec).expect("Failed to parse OpenAPI spec");
 
-        // 2. Verify that request schema references a NEW model, not Value
-        assert_eq!(request_model.schema, "CreateItemRequestBody");
+        let Some(ModelType::Struct(account)) = models.iter().find(|m| m.name() == "Account") else {
+            panic!("Expected Account struct");
+        };
+        assert_eq!(account.fields[0].name, "name");
+        assert_eq!(account.fields[0].field_type, "String");
 
-        // 3. Verify that the request body model itself was generated
-        let inline_model = models.iter().find(|m| m.name() == "CreateItemRequestBody");
-        assert!(
-            inline_model.is_some(),
-            "Expected a model named 'CreateItemRequestBody' to be generated"
+        let Some(ModelType::TypeAlias(anything)) = models.iter().find(|m| m.name() == "Anything")
+        else {
+            panic!("Expected Anything alias");
+        };
+        assert_eq!(anything.target_type, "serde_json::Value");
+
+        assert_eq!(
+            collect_warnings(&openapi_spec),
+            vec![
+                "'Account' uses `not`; the negated schema is not encoded in the generated type",
+                "'Account.name' uses `not`; the negated schema is not encoded in the generated type",
+                "'Anything' uses `not`; the negated schema is not encoded in the generated type",
+            ]
         );
+    }
 
-        if let Some(ModelType::Struct(model)) = inline_model {
-            assert_eq!(model.fields.len(), 2);
-            assert_eq!(model.fields[0].name, "name");
-            assert_eq!(model.fields[0].field_type, "String");
-            assert!(model.fields[0].is_required);
+    #[test]
+    fn test_const_in_30_documents_becomes_single_value_enum() {
+        let document = json!({
+            "openapi": "3.0.3",
+            "info": { "title": "Test API", "version": "1.0.0" },
+            "paths": {},
+            "components": {
+                "schemas": {
+                    "Dog": {
+                        "type": "object",
+                        "required": ["kind"],
+                        "properties": {
+                            "kind": { "const": "dog" },
+                            "const": { "type": "string" }
+                        }
+                    },
+                    "Version": { "type": "integer", "const": 2 }
+                }
+            }
+        });
 
-            assert_eq!(model.fields[1].name, "value");
-            assert_eq!(model.fields[1].field_type, "i64");
-            assert!(!model.fields[1].is_required);
-        } else {
-            panic!("Expected a Struct model for CreateItemRequestBody");
-        }
+        let openapi = load_openapi(&document.to_string(), false).expect("Failed to load spec");
+        let (models, _, _) = parse_openapi(&openapi).expect("Failed to parse OpenAPI spec");
+
+        let Some(ModelType::Struct(dog)) = models.iter().find(|m| m.name() == "Dog") else {
+            panic!("Expected Dog struct");
+        };
+        let field = |name: &str| dog.fields.iter().find(|f| f.name == name).unwrap();
+        assert_eq!(field("kind").field_type, "DogKind");
+        // A property named `const` is not the keyword
+        assert_eq!(field("const").field_type, "String");
+
+        let Some(ModelType::Enum(kind)) = models.iter().find(|m| m.name() == "DogKind") else {
+            panic!("Expected DogKind enum");
+        };
+        assert_eq!(kind.variants, vec!["dog"]);
+
+        let schemas = &openapi.components.as_ref().unwrap().schemas;
+        let Some(ReferenceOr::Item(version)) = schemas.get("Version") else {
+            panic!("Expected Version schema");
+        };
+        let SchemaKind::Type(Type::Integer(integer)) = &version.schema_kind else {
+            panic!("Expected integer schema");
+        };
+        assert_eq!(integer.enumeration, vec![Some(2)]);
     }
 
     #[test]
-    fn test_parse_ref_request_body_works() {
-        let openapi_spec: OpenAPI = serde_json::from_value(json!({
-            "openapi": "3.0.0",
+    fn test_null_type_arrays_in_30_documents_are_nullable() {
+        let document = json!({
+            "openapi": "3.0.3",
             "info": { "title": "Test API", "version": "1.0.0" },
+            "paths": {},
             "components": {
                 "schemas": {
-                    "ItemData": {
+                    "Owner": {
+                        "type": "object",
+                        "properties": { "id": { "type": "string" } }
+                    },
+                    "Pet": {
                         "type": "object",
+                        "required": ["name", "tag", "owner"],
                         "properties": {
-                            "name": { "type": "string" }
-                        }
-                    }
-                },
-                "requestBodies": {
-                    "CreateItem": {
-                        "content": {
-                            "application/json": {
-                                "schema": { "$ref": "#/components/schemas/ItemData" }
+                            "name": { "type": ["string", "null"] },
+                            "tag": { "type": ["integer", "null"], "format": "int32" },
+                            "owner": {
+                                "oneOf": [
+                                    { "$ref": "#/components/schemas/Owner" },
+                                    { "type": "null" }
+                                ]
                             }
                         }
                     }
                 }
-            },
-            "paths": {
-                "/items": {
-                    "post": {
-                        "operationId": "createItem",
-                        "requestBody": { "$ref": "#/components/requestBodies/CreateItem" },
-                        "responses": { "200": { "description": "OK" } }
-                    }
-                }
             }
-        }))
-        .expect("Failed to deserialize OpenAPI spec");
+        });
 
-        let (models, requests, _responses) =
-            parse_openapi(&openapi_spec).expect("Failed to parse OpenAPI spec");
+        let openapi = load_openapi(&document.to_string(), false).expect("Failed to load spec");
+        let (models, _, _) = parse_openapi(&openapi).expect("Failed to parse OpenAPI spec");
 
-        // Verify that request model was created
-        assert_eq!(requests.len(), 1);
-        let request_model = &requests[0];
-        assert_eq!(request_model.name, "CreateItemRequest");
+        let Some(ModelType::Struct(pet)) = models.iter().find(|m| m.name() == "Pet") else {
+            panic!("Expected Pet struct");
+        };
+        let field = |name: &str| pet.fields.iter().find(|f| f.name == name).unwrap();
+        for (name, field_type) in [("name", "String"), ("tag", "i32"), ("owner", "Owner")] {
+            assert_eq!(field(name).field_type, field_type);
+            assert!(field(name).is_nullable, "{name} should be nullable");
+        }
+    }
+
+    #[test]
+    fn test_multi_type_property_becomes_untagged_enum() {
+        let doc = json!({
+            "openapi": "3.0.0",
+            "info": {"title": "t", "version": "1"},
+            "paths": {},
+            "components": {"schemas": {"Setting": {
+                "type": "object",
+                "required": ["value"],
+                "properties": {
+                    "value": {"type": ["string", "integer", "boolean"]}
+                }
+            }}}
+        });
+        let openapi = load_openapi(&doc.to_string(), false).unwrap();
+        let (models, _, _) = parse_openapi(&openapi).expect("parse");
 
-        // Verify that schema references an existing model
-        assert_eq!(request_model.schema, "ItemData");
+        let setting = models
+            .iter()
+            .find_map(|m| match m {
+                ModelType::Struct(s) if s.name == "Setting" => Some(s),
+                _ => None,
+            })
+            .expect("Setting struct");
+        assert_eq!(setting.fields[0].field_type, "SettingValue");
 
-        // Verify that ItemData model exists in the models list
-        assert!(models.iter().any(|m| m.name() == "ItemData"));
+        let union = models
+            .iter()
+            .find_map(|m| match m {
+                ModelType::Union(u) if u.name == "SettingValue" => Some(u),
+                _ => None,
+            })
+            .expect("SettingValue union");
+        let primitives: Vec<_> = union
+            .variants
+            .iter()
+            .map(|v| v.primitive_type.as_deref())
+            .collect();
+        assert_eq!(primitives, vec![Some("String"), Some("i64"), Some("bool")]);
     }
 
     #[test]
-    fn test_parse_no_request_body() {
+    fn test_string_formats_use_openapi_names() {
         let openapi_spec: OpenAPI = serde_json::from_value(json!({
             "openapi": "3.0.0",
             "info": { "title": "Test API", "version": "1.0.0" },
-            "paths": {
-                "/items": {
-                    "get": {
-                        "operationId": "listItems",
-                        "responses": { "200": { "description": "OK" } }
+            "paths": {},
+            "components": {
+                "schemas": {
+                    "Credentials": {
+                        "type": "object",
+                        "properties": {
+                            "secret": { "type": "string", "format": "password" },
+                            "file": { "type": "string", "format": "binary" },
+                            "payload": { "type": "string", "format": "byte" }
+                        }
                     }
                 }
             }
         }))
         .expect("Failed to deserialize OpenAPI spec");
 
-        let (_models, requests, _responses) =
-            parse_openapi(&openapi_spec).expect("Failed to parse OpenAPI spec");
+        let (models, _, _) = parse_openapi(&openapi_spec).expect("Failed to parse OpenAPI spec");
 
-        // Verify that no request models were created
-        assert!(requests.is_empty());
+        let Some(ModelType::Struct(model)) = models.iter().find(|m| m.name() == "Credentials")
+        else {
+            panic!("Expected Credentials struct");
+        };
+        let format_of = |name: &str| {
+            model
+                .fields
+                .iter()
+                .find(|f| f.name == name)
+                .map(|f| f.format.clone())
+                .unwrap()
+        };
+        assert_eq!(format_of("secret"), "password");
+        assert_eq!(format_of("file"), "binary");
+        assert_eq!(format_of("payload"), "byte");
+
+        let type_of = |name: &str| {
+            model
+                .fields
+                .iter()
+                .find(|f| f.name == name)
+                .map(|f| f.field_type.clone())
+                .unwrap()
+        };
+        assert_eq!(type_of("payload"), "Base64Bytes");
     }
 
     #[test]
-    fn test_nullable_reference_field() {
-        // Test verifies that nullable is correctly read from the target schema when using $ref
+    fn test_union_with_additional_properties_warns_and_generates_union() {
         let openapi_spec: OpenAPI = serde_json::from_value(json!({
             "openapi": "3.0.0",
             "info": { "title": "Test API", "version": "1.0.0" },
             "paths": {},
             "components": {
                 "schemas": {
-                    "NullableUser": {
+                    "Cat": {
                         "type": "object",
-                        "nullable": true,
-                        "properties": {
-                            "name": { "type": "string" }
-                        }
+                        "properties": { "meow": { "type": "boolean" } }
                     },
-                    "Post": {
+                    "Dog": {
                         "type": "object",
-                        "properties": {
-                            "author": {
-                                "$ref": "#/components/schemas/NullableUser"
-                            }
-                        }
+                        "properties": { "bark": { "type": "boolean" } }
+                    },
+                    "Pet": {
+                        "oneOf": [
+                            { "$ref": "#/components/schemas/Cat" },
+                            { "$ref": "#/components/schemas/Dog" }
+                        ],
+                        "additionalProperties": true
                     }
                 }
             }
         }))
         .expect("Failed to deserialize OpenAPI spec");
 
-        let (models, _, _) = parse_openapi(&openapi_spec).expect("Failed to parse OpenAPI spec");
-
-        // Find Post model
-        let post_model = models.iter().find(|m| m.name() == "Post");
-        assert!(post_model.is_some(), "Expected Post model to be generated");
-
-        if let Some(ModelType::Struct(post)) = post_model {
-            let author_field = post.fields.iter().find(|f| f.name == "author");
-            assert!(author_field.is_some(), "Expected author field");
+        let warnings = collect_warnings(&openapi_spec);
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].contains("'Pet'"));
+        assert!(warnings[0].contains("additionalProperties"));
 
-            // Verify that nullable is correctly handled for reference type
-            // (nullable is taken from the target schema NullableUser)
-            let author = author_field.unwrap();
-            assert!(
-                author.is_nullable,
-                "Expected author field to be nullable (from referenced schema)"
-            );
-        } else {
-            panic!("Expected Post to be a Struct");
+        let (models, _, _) = parse_openapi(&openapi_spec).expect("Failed to parse OpenAPI spec");
+        match models.iter().find(|m| m.name() == "Pet") {
+            Some(ModelType::Union(union)) => {
+                assert!(matches!(union.union_type, UnionType::OneOf));
+                assert_eq!(union.variants.len(), 2);
+            }
+            _ => panic!("Expected Pet union"),
         }
     }
 
     #[test]
-    fn test_allof_required_fields_merge() {
-        let openapi_spec: OpenAPI = serde_json::from_value(json!({
+    fn test_pattern_properties_become_union_valued_map() {
+        let doc = json!({
             "openapi": "3.0.0",
             "info": { "title": "Test API", "version": "1.0.0" },
             "paths": {},
             "components": {
                 "schemas": {
-                    "BaseEntity": {
+                    "Metrics": {
                         "type": "object",
-                        "properties": {
-                            "id": { "type": "string" },
-                            "created": { "type": "string" }
-                        },
-                        "required": ["id"]
-                    },
-                    "Person": {
-                        "allOf": [
-                            { "$ref": "#/components/schemas/BaseEntity" },
-                            {
-                                "type": "object",
-                                "properties": {
-                                    "name": { "type": "string" },
-                                    "age": { "type": "integer" }
-                                },
-                                "required": ["name"]
-                            }
-                        ]
+                        "patternProperties": {
+                            "^count_": { "type": "integer" },
+                            "^name_": { "type": "string" }
+                        }
                     }
                 }
             }
-        }))
-        .expect("Failed to deserialize OpenAPI spec");
+        });
+        let openapi_spec = load_openapi(&doc.to_string(), false).unwrap();
 
-        let (models, _, _) = parse_openapi(&openapi_spec).expect("Failed to parse OpenAPI spec");
+        let warnings = collect_warnings(&openapi_spec);
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].contains("'Metrics' uses patternProperties (^count_, ^name_)"));
 
-        // Find Person model
-        let person_model = models.iter().find(|m| m.name() == "Person");
-        assert!(
-            person_model.is_some(),
-            "Expected Person model to be generated"
-        );
+        let (models, _, _) = parse_openapi(&openapi_spec).expect("Failed to parse OpenAPI spec");
+        match models.iter().find(|m| m.name() == "Metrics") {
+            Some(ModelType::TypeAlias(alias)) => assert_eq!(
+                alias.target_type,
+                "std::collections::HashMap<String, MetricsValue>"
+            ),
+            _ => panic!("Expected Metrics alias"),
+        }
+        match models.iter().find(|m| m.name() == "MetricsValue") {
+            Some(ModelType::Union(union)) => assert_eq!(union.variants.len(), 2),
+            _ => panic!("Expected MetricsValue union"),
+        }
+    }
 
-        if let Some(ModelType::Composition(person)) = person_model {
-            // Verify that id (from BaseEntity) is required
-            let id_field = person.all_fields.iter().find(|f| f.name == "id");
-            assert!(id_field.is_some(), "Expected id field");
-            assert!(
-                id_field.unwrap().is_required,
-                "Expected id to be required from BaseEntity"
-            );
+    #[test]
+    fn test_webhooks_generate_request_models() {
+        let doc = json!({
+            "openapi": "3.1.0",
+            "info": { "title": "Test API", "version": "1.0.0" },
+            "webhooks": {
+                "newPet": {
+                    "post": {
+                        "operationId": "newPet",
+                        "requestBody": {
+                            "content": {
+                                "application/json": {
+                                    "schema": { "$ref": "#/components/schemas/Pet" }
+                                }
+                            }
+                        },
+                        "responses": { "200": { "description": "ok" } }
+                    }
+                }
+            },
+            "components": {
+                "schemas": {
+                    "Pet": {
+                        "type": "object",
+                        "properties": { "id": { "type": "integer" } }
+                    }
+                }
+            }
+        });
+        let openapi_spec = load_openapi(&doc.to_string(), false).unwrap();
 
-            // Verify that name (from second object) is required
-            let name_field = person.all_fields.iter().find(|f| f.name == "name");
-            assert!(name_field.is_some(), "Expected name field");
-            assert!(
-                name_field.unwrap().is_required,
-                "Expected name to be required from inline object"
-            );
+        let (_, requests, _) = parse_openapi(&openapi_spec).expect("Failed to parse OpenAPI spec");
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].name, "NewPetRequest");
+        assert_eq!(requests[0].schema, "Pet");
+    }
 
-            // Verify that created and age are not required
-            let created_field = person.all_fields.iter().find(|f| f.name == "created");
-            assert!(created_field.is_some(), "Expected created field");
-            assert!(
-                !created_field.unwrap().is_required,
-                "Expected created to be optional"
-            );
+    #[test]
+    fn test_callbacks_generate_prefixed_models() {
+        let openapi_spec: OpenAPI = serde_json::from_value(json!({
+            "openapi": "3.0.0",
+            "info": { "title": "Test API", "version": "1.0.0" },
+            "paths": {
+                "/subscribe": {
+                    "post": {
+                        "operationId": "subscribe",
+                        "responses": { "201": { "description": "ok" } },
+                        "callbacks": {
+                            "onEvent": {
+                                "{$request.body#/callbackUrl}": {
+                                    "post": {
+                                        "requestBody": {
+                                            "content": {
+                                                "application/json": {
+                                                    "schema": {
+                                                        "type": "object",
+                                                        "properties": {
+                                                            "message": { "type": "string" }
+                                                        }
+                                                    }
+                                                }
+                                            }
+                                        },
+                                        "responses": { "200": { "description": "ok" } }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }))
+        .expect("Failed to deserialize OpenAPI spec");
 
-            let age_field = person.all_fields.iter().find(|f| f.name == "age");
-            assert!(age_field.is_some(), "Expected age field");
-            assert!(
-                !age_field.unwrap().is_required,
-                "Expected age to be optional"
-            );
-        } else {
-            panic!("Expected Person to be a Composition");
-        }
+        let (models, requests, _) =
+            parse_openapi(&openapi_spec).expect("Failed to parse OpenAPI spec");
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].name, "CallbackSubscribeOnEventRequest");
+        assert_eq!(requests[0].schema, "CallbackSubscribeOnEventRequestBody");
+        assert!(models
+            .iter()
+            .any(|m| m.name() == "CallbackSubscribeOnEventRequestBody"));
     }
 
     #[test]
-    fn test_x_rust_type_generates_type_alias() {
+    fn test_text_and_binary_responses_are_raw() {
         let openapi_spec: OpenAPI = serde_json::from_value(json!({
             "openapi": "3.0.0",
             "info": { "title": "Test API", "version": "1.0.0" },
-            "paths": {},
-            "components": {
-                "schemas": {
-                    "User": {
-                        "type": "object",
-                        "x-rust-type": "crate::domain::User",
-                        "description": "Custom domain user type",
-                        "properties": {
-                            "name": { "type": "string" },
-                            "age": { "type": "integer" }
+            "paths": {
+                "/export": {
+                    "get": {
+                        "operationId": "export",
+                        "responses": {
+                            "200": {
+                                "description": "ok",
+                                "content": {
+                                    "text/plain; charset=utf-8": {},
+                                    "application/octet-stream": {
+                                        "schema": { "type": "string", "format": "binary" }
+                                    }
+                                }
+                            }
                         }
                     }
                 }
@@ -1638,105 +6900,213 @@ mod tests {
         }))
         .expect("Failed to deserialize OpenAPI spec");
 
-        let (models, _, _) = parse_openapi(&openapi_spec).expect("Failed to parse OpenAPI spec");
-
-        // Verify that TypeAlias is created, not Struct
-        let user_model = models.iter().find(|m| m.name() == "User");
-        assert!(user_model.is_some(), "Expected User model");
+        let (_, _, responses) = parse_openapi(&openapi_spec).expect("Failed to parse OpenAPI spec");
+        let schemas: Vec<(&str, &str)> = responses
+            .iter()
+            .map(|r| (r.content_type.as_str(), r.schema.as_str()))
+            .collect();
+        assert_eq!(
+            schemas,
+            vec![
+                ("application/octet-stream", "Vec<u8>"),
+                ("text/plain; charset=utf-8", "String")
+            ]
+        );
+    }
 
-        match user_model.unwrap() {
-            ModelType::TypeAlias(alias) => {
-                assert_eq!(alias.name, "User");
-                assert_eq!(alias.target_type, "crate::domain::User");
-                assert_eq!(
-                    alias.description,
-                    Some("Custom domain user type".to_string())
-                );
+    #[test]
+    fn test_default_response_is_parsed() {
+        let openapi_spec: OpenAPI = serde_json::from_value(json!({
+            "openapi": "3.0.0",
+            "info": { "title": "Test API", "version": "1.0.0" },
+            "paths": {
+                "/pets": {
+                    "get": {
+                        "operationId": "listPets",
+                        "responses": {
+                            "200": { "description": "ok" },
+                            "default": {
+                                "description": "unexpected error",
+                                "content": {
+                                    "application/json": {
+                                        "schema": {
+                                            "type": "object",
+                                            "properties": { "message": { "type": "string" } }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
             }
-            _ => panic!("Expected TypeAlias, got different type"),
-        }
+        }))
+        .expect("Failed to deserialize OpenAPI spec");
+
+        let (models, _, responses) =
+            parse_openapi(&openapi_spec).expect("Failed to parse OpenAPI spec");
+        assert_eq!(responses.len(), 1);
+        assert_eq!(responses[0].status_code, "Default");
+        assert_eq!(responses[0].schema, "ListPetsResponseDefault");
+        assert!(models.iter().any(|m| m.name() == "ListPetsResponseDefault"));
     }
 
     #[test]
-    fn test_x_rust_type_works_with_enum() {
+    fn test_range_status_codes_are_named_and_exposed() {
         let openapi_spec: OpenAPI = serde_json::from_value(json!({
             "openapi": "3.0.0",
             "info": { "title": "Test API", "version": "1.0.0" },
-            "paths": {},
-            "components": {
-                "schemas": {
-                    "Status": {
-                        "type": "string",
-                        "enum": ["active", "inactive"],
-                        "x-rust-type": "crate::domain::Status"
+            "paths": {
+                "/foo": {
+                    "get": {
+                        "operationId": "getFoo",
+                        "responses": {
+                            "200": {
+                                "description": "ok",
+                                "content": { "application/json": { "schema": { "type": "string" } } }
+                            },
+                            "4XX": {
+                                "description": "client error",
+                                "content": {
+                                    "application/json": {
+                                        "schema": {
+                                            "type": "object",
+                                            "properties": { "message": { "type": "string" } }
+                                        }
+                                    }
+                                }
+                            }
+                        }
                     }
                 }
             }
         }))
         .expect("Failed to deserialize OpenAPI spec");
 
-        let (models, _, _) = parse_openapi(&openapi_spec).expect("Failed to parse OpenAPI spec");
-
-        let status_model = models.iter().find(|m| m.name() == "Status");
-        assert!(status_model.is_some(), "Expected Status model");
-
-        // Should be TypeAlias, not Enum
-        assert!(
-            matches!(status_model.unwrap(), ModelType::TypeAlias(_)),
-            "Expected TypeAlias for enum with x-rust-type"
-        );
+        let (models, _, responses) =
+            parse_openapi(&openapi_spec).expect("Failed to parse OpenAPI spec");
+        let exact = responses.iter().find(|r| r.status_code == "200").unwrap();
+        assert_eq!(exact.status_range, None);
+        let range = responses.iter().find(|r| r.status_code == "4XX").unwrap();
+        assert_eq!(range.status_range, Some(4));
+        assert_eq!(range.schema, "GetFooResponse4XX");
+        assert!(models.iter().any(|m| m.name() == "GetFooResponse4XX"));
     }
 
     #[test]
-    fn test_x_rust_type_works_with_oneof() {
+    fn test_media_type_examples_are_collected() {
         let openapi_spec: OpenAPI = serde_json::from_value(json!({
             "openapi": "3.0.0",
             "info": { "title": "Test API", "version": "1.0.0" },
-            "paths": {},
+            "paths": {
+                "/pets": {
+                    "post": {
+                        "operationId": "createPet",
+                        "requestBody": {
+                            "content": {
+                                "application/json": {
+                                    "schema": { "$ref": "#/components/schemas/Pet" },
+                                    "example": { "name": "a" }
+                                },
+                                "application/xml": {
+                                    "schema": { "$ref": "#/components/schemas/Pet" },
+                                    "example": "<pet/>"
+                                }
+                            }
+                        },
+                        "responses": {
+                            "200": {
+                                "description": "ok",
+                                "content": {
+                                    "application/json": {
+                                        "schema": {
+                                            "type": "array",
+                                            "items": { "$ref": "#/components/schemas/Pet" }
+                                        },
+                                        "examples": {
+                                            "one": { "$ref": "#/components/examples/OnePet" },
+                                            "remote": { "externalValue": "https://example.com/pets.json" }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            },
             "components": {
+                "examples": { "OnePet": { "value": [{ "name": "b" }] } },
                 "schemas": {
-                    "Payment": {
-                        "oneOf": [
-                            { "type": "object", "properties": { "card": { "type": "string" } } },
-                            { "type": "object", "properties": { "cash": { "type": "number" } } }
-                        ],
-                        "x-rust-type": "payments::Payment"
+                    "Pet": {
+                        "type": "object",
+                        "properties": { "name": { "type": "string" } },
+                        "example": { "name": "c" }
                     }
                 }
             }
         }))
         .expect("Failed to deserialize OpenAPI spec");
 
-        let (models, _, _) = parse_openapi(&openapi_spec).expect("Failed to parse OpenAPI spec");
-
-        let payment_model = models.iter().find(|m| m.name() == "Payment");
-        assert!(payment_model.is_some(), "Expected Payment model");
-
-        // Should be TypeAlias, not Union
-        match payment_model.unwrap() {
-            ModelType::TypeAlias(alias) => {
-                assert_eq!(alias.target_type, "payments::Payment");
-            }
-            _ => panic!("Expected TypeAlias for oneOf with x-rust-type"),
-        }
+        let (models, requests, responses) =
+            parse_openapi(&openapi_spec).expect("Failed to parse OpenAPI spec");
+        assert_eq!(responses[0].schema, "Vec<Pet>");
+        let examples = parse_examples(&openapi_spec, &models, &requests, &responses);
+        let summary: Vec<(&str, &str, String)> = examples
+            .iter()
+            .map(|e| (e.name.as_str(), e.type_name.as_str(), e.value.to_string()))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("Pet", "Pet", r#"{"name":"c"}"#.to_string()),
+                ("CreatePetRequest", "Pet", r#"{"name":"a"}"#.to_string()),
+                (
+                    "CreatePet200One",
+                    "Vec<Pet>",
+                    r#"[{"name":"b"}]"#.to_string()
+                ),
+            ]
+        );
     }
 
     #[test]
-    fn test_x_rust_attrs_on_struct() {
+    fn test_titles_name_inline_schemas() {
         let openapi_spec: OpenAPI = serde_json::from_value(json!({
             "openapi": "3.0.0",
             "info": { "title": "Test API", "version": "1.0.0" },
             "paths": {},
             "components": {
                 "schemas": {
-                    "User": {
+                    "Order": {
                         "type": "object",
-                        "x-rust-attrs": [
-                            "#[derive(Serialize, Deserialize)]",
-                            "#[serde(rename_all = \"camelCase\")]"
-                        ],
                         "properties": {
-                            "name": { "type": "string" }
+                            "shipping": {
+                                "title": "postal address",
+                                "type": "object",
+                                "properties": { "city": { "type": "string" } }
+                            },
+                            "lines": {
+                                "type": "array",
+                                "items": {
+                                    "title": "OrderLine",
+                                    "type": "object",
+                                    "properties": { "sku": { "type": "string" } }
+                                }
+                            },
+                            "payment": {
+                                "oneOf": [
+                                    {
+                                        "title": "Card",
+                                        "type": "object",
+                                        "properties": { "number": { "type": "string" } }
+                                    },
+                                    {
+                                        "title": "Card",
+                                        "type": "object",
+                                        "properties": { "token": { "type": "string" } }
+                                    }
+                                ]
+                            }
                         }
                     }
                 }
@@ -1745,70 +7115,134 @@ mod tests {
         .expect("Failed to deserialize OpenAPI spec");
 
         let (models, _, _) = parse_openapi(&openapi_spec).expect("Failed to parse OpenAPI spec");
-
-        let user_model = models.iter().find(|m| m.name() == "User");
-        assert!(user_model.is_some(), "Expected User model");
-
-        match user_model.unwrap() {
-            ModelType::Struct(model) => {
-                assert!(model.custom_attrs.is_some(), "Expected custom_attrs");
-                let attrs = model.custom_attrs.as_ref().unwrap();
-                assert_eq!(attrs.len(), 2);
-                assert_eq!(attrs[0], "#[derive(Serialize, Deserialize)]");
-                assert_eq!(attrs[1], "#[serde(rename_all = \"camelCase\")]");
-            }
-            _ => panic!("Expected Struct model"),
-        }
+        let Some(ModelType::Struct(order)) = models.iter().find(|m| m.name() == "Order") else {
+            panic!("Expected an Order struct");
+        };
+        let field_type = |name: &str| {
+            order
+                .fields
+                .iter()
+                .find(|f| f.name == name)
+                .map(|f| f.field_type.clone())
+                .unwrap()
+        };
+        assert_eq!(field_type("shipping"), "PostalAddress");
+        assert_eq!(field_type("lines"), "OrderLine");
+        assert!(models.iter().any(|m| m.name() == "PostalAddress"));
+        assert!(models.iter().any(|m| m.name() == "OrderLine"));
+
+        let Some(ModelType::Union(payment)) = models.iter().find(|m| m.name() == "OrderPayment")
+        else {
+            panic!("Expected an OrderPayment union");
+        };
+        let variant_names: Vec<&str> = payment.variants.iter().map(|v| v.name.as_str()).collect();
+        assert_eq!(variant_names, vec!["Card", "Variant1"]);
     }
 
     #[test]
-    fn test_x_rust_attrs_on_enum() {
+    fn test_component_parameters_are_declared_once() {
         let openapi_spec: OpenAPI = serde_json::from_value(json!({
             "openapi": "3.0.0",
             "info": { "title": "Test API", "version": "1.0.0" },
-            "paths": {},
-            "components": {
-                "schemas": {
-                    "Status": {
-                        "type": "string",
-                        "enum": ["active", "inactive"],
-                        "x-rust-attrs": ["#[serde(rename_all = \"UPPERCASE\")]"]
+            "paths": {
+                "/items": {
+                    "parameters": [{ "$ref": "#/components/parameters/PageSize" }],
+                    "get": {
+                        "operationId": "listItems",
+                        "parameters": [
+                            { "$ref": "#/components/parameters/PageSize" },
+                            { "$ref": "#/components/parameters/Limit" }
+                        ],
+                        "responses": { "200": { "description": "OK" } }
                     }
                 }
+            },
+            "components": {
+                "parameters": {
+                    "PageSize": {
+                        "name": "pageSize",
+                        "in": "query",
+                        "required": true,
+                        "schema": { "type": "integer", "format": "int32" }
+                    },
+                    "Limit": { "$ref": "#/components/parameters/PageSize" }
+                }
             }
         }))
         .expect("Failed to deserialize OpenAPI spec");
 
-        let (models, _, _) = parse_openapi(&openapi_spec).expect("Failed to parse OpenAPI spec");
-
-        let status_model = models.iter().find(|m| m.name() == "Status");
-        assert!(status_model.is_some(), "Expected Status model");
+        let (models, _, _) = parse_openapi(&openapi_spec).expect("Failed to parse");
+        let params = models
+            .iter()
+            .find_map(|m| match m {
+                ModelType::Params(params) if params.name == "ListItemsParams" => Some(params),
+                _ => None,
+            })
+            .expect("ListItemsParams model should exist");
 
-        match status_model.unwrap() {
-            ModelType::Enum(enum_model) => {
-                assert!(enum_model.custom_attrs.is_some());
-                let attrs = enum_model.custom_attrs.as_ref().unwrap();
-                assert_eq!(attrs.len(), 1);
-                assert_eq!(attrs[0], "#[serde(rename_all = \"UPPERCASE\")]");
-            }
-            _ => panic!("Expected Enum model"),
-        }
+        let summary: Vec<_> = params
+            .fields
+            .iter()
+            .map(|p| {
+                (
+                    p.field.name.as_str(),
+                    p.field.field_type.as_str(),
+                    p.field.is_required,
+                )
+            })
+            .collect();
+        assert_eq!(summary, vec![("pageSize", "i32", true)]);
     }
 
     #[test]
-    fn test_x_rust_attrs_with_x_rust_type() {
+    fn test_request_body_references_share_the_component_model() {
         let openapi_spec: OpenAPI = serde_json::from_value(json!({
             "openapi": "3.0.0",
             "info": { "title": "Test API", "version": "1.0.0" },
-            "paths": {},
+            "paths": {
+                "/users": {
+                    "post": {
+                        "operationId": "createUser",
+                        "requestBody": { "$ref": "#/components/requestBodies/NewUser" },
+                        "responses": { "200": { "description": "OK" } }
+                    },
+                    "put": {
+                        "operationId": "replaceUsers",
+                        "requestBody": {
+                            "content": {
+                                "application/json": {
+                                    "schema": {
+                                        "type": "array",
+                                        "items": { "$ref": "#/components/schemas/Tag" }
+                                    }
+                                }
+                            }
+                        },
+                        "responses": { "200": { "description": "OK" } }
+                    }
+                },
+                "/admins": {
+                    "post": {
+                        "operationId": "createAdmin",
+                        "requestBody": { "$ref": "#/components/requestBodies/NewUser" },
+                        "responses": { "200": { "description": "OK" } }
+                    }
+                }
+            },
             "components": {
                 "schemas": {
-                    "User": {
-                        "type": "object",
-                        "x-rust-type": "crate::domain::User",
-                        "x-rust-attrs": ["#[cfg_attr(test, derive(Default))]"],
-                        "properties": {
-                            "name": { "type": "string" }
+                    "Tag": { "type": "object", "properties": { "name": { "type": "string" } } }
+                },
+                "requestBodies": {
+                    "NewUser": {
+                        "required": true,
+                        "content": {
+                            "application/json": {
+                                "schema": {
+                                    "type": "object",
+                                    "properties": { "name": { "type": "string" } }
+                                }
+                            }
                         }
                     }
                 }
@@ -1816,37 +7250,55 @@ mod tests {
         }))
         .expect("Failed to deserialize OpenAPI spec");
 
-        let (models, _, _) = parse_openapi(&openapi_spec).expect("Failed to parse OpenAPI spec");
-
-        let user_model = models.iter().find(|m| m.name() == "User");
-        assert!(user_model.is_some(), "Expected User model");
-
-        // Should be TypeAlias with attributes
-        match user_model.unwrap() {
-            ModelType::TypeAlias(alias) => {
-                assert_eq!(alias.target_type, "crate::domain::User");
-                assert!(alias.custom_attrs.is_some());
-                let attrs = alias.custom_attrs.as_ref().unwrap();
-                assert_eq!(attrs.len(), 1);
-                assert_eq!(attrs[0], "#[cfg_attr(test, derive(Default))]");
-            }
-            _ => panic!("Expected TypeAlias with custom attrs"),
-        }
+        let (models, requests, _) = parse_openapi(&openapi_spec).expect("Failed to parse");
+        let summary: Vec<_> = requests
+            .iter()
+            .map(|r| (r.name.as_str(), r.schema.as_str(), r.is_required))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("CreateAdminRequest", "NewUser", true),
+                ("CreateUserRequest", "NewUser", true),
+                ("ReplaceUsersRequest", "Vec<Tag>", false),
+            ]
+        );
+        assert_eq!(models.iter().filter(|m| m.name() == "NewUser").count(), 1);
     }
 
     #[test]
-    fn test_x_rust_attrs_empty_array() {
+    fn test_response_references_share_the_component_model() {
         let openapi_spec: OpenAPI = serde_json::from_value(json!({
             "openapi": "3.0.0",
             "info": { "title": "Test API", "version": "1.0.0" },
-            "paths": {},
+            "paths": {
+                "/users": {
+                    "get": {
+                        "operationId": "getUser",
+                        "responses": {
+                            "404": { "$ref": "#/components/responses/NotFound" }
+                        }
+                    },
+                    "delete": {
+                        "operationId": "deleteUser",
+                        "responses": {
+                            "default": { "$ref": "#/components/responses/NotFound" },
+                            "500": { "$ref": "#/components/responses/Missing" }
+                        }
+                    }
+                }
+            },
             "components": {
-                "schemas": {
-                    "User": {
-                        "type": "object",
-                        "x-rust-attrs": [],
-                        "properties": {
-                            "name": { "type": "string" }
+                "responses": {
+                    "NotFound": {
+                        "description": "Not found",
+                        "content": {
+                            "application/json": {
+                                "schema": {
+                                    "type": "object",
+                                    "properties": { "message": { "type": "string" } }
+                                }
+                            }
                         }
                     }
                 }
@@ -1854,181 +7306,386 @@ mod tests {
         }))
         .expect("Failed to deserialize OpenAPI spec");
 
-        let (models, _, _) = parse_openapi(&openapi_spec).expect("Failed to parse OpenAPI spec");
-
-        let user_model = models.iter().find(|m| m.name() == "User");
-        assert!(user_model.is_some());
-
-        match user_model.unwrap() {
-            ModelType::Struct(model) => {
-                // Empty array should result in None
-                assert!(model.custom_attrs.is_none());
-            }
-            _ => panic!("Expected Struct"),
-        }
+        let (models, _, responses) = parse_openapi(&openapi_spec).expect("Failed to parse");
+        let summary: Vec<_> = responses
+            .iter()
+            .map(|r| (r.name.as_str(), r.status_code.as_str(), r.schema.as_str()))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("GetUser", "404", "NotFound"),
+                ("DeleteUser", "Default", "NotFound"),
+            ]
+        );
+        assert_eq!(responses[0].description.as_deref(), Some("Not found"));
+        assert_eq!(models.iter().filter(|m| m.name() == "NotFound").count(), 1);
     }
 
     #[test]
-    fn test_x_rust_type_on_string_property() {
+    fn test_reference_schemas_become_type_aliases() {
         let openapi_spec: OpenAPI = serde_json::from_value(json!({
             "openapi": "3.0.0",
             "info": { "title": "Test API", "version": "1.0.0" },
             "paths": {},
             "components": {
                 "schemas": {
-                    "Document": {
-                        "type": "object",
-                        "description": "Document with custom version type",
-                        "properties": {
-                            "title": { "type": "string", "description": "Document title." },
-                            "content": { "type": "string", "description": "Document content." },
-                            "version": {
-                                "type": "string",
-                                "format": "semver",
-                                "x-rust-type": "semver::Version",
-                                "description": "Semantic version."
-                            }
-                        },
-                        "required": ["title", "content", "version"]
-                    }
+                    "Owner": { "$ref": "#/components/schemas/Person" },
+                    "Person": { "$ref": "#/components/schemas/User" },
+                    "User": { "type": "object", "properties": { "name": { "type": "string" } } },
+                    "Dangling": { "$ref": "#/components/schemas/Missing" }
                 }
             }
         }))
-        .expect("Failed to deserialize OpenAPI spec");
-
-        let (models, _, _) = parse_openapi(&openapi_spec).expect("Failed to parse OpenAPI spec");
-
-        let document_model = models.iter().find(|m| m.name() == "Document");
-        assert!(document_model.is_some(), "Expected Document model");
-
-        match document_model.unwrap() {
-            ModelType::Struct(model) => {
-                // Verify that version field has custom type
-                let version_field = model.fields.iter().find(|f| f.name == "version");
-                assert!(version_field.is_some(), "Expected version field");
-                assert_eq!(version_field.unwrap().field_type, "semver::Version");
-
-                // Verify other fields have regular types
-                let title_field = model.fields.iter().find(|f| f.name == "title");
-                assert_eq!(title_field.unwrap().field_type, "String");
-
-                let content_field = model.fields.iter().find(|f| f.name == "content");
-                assert_eq!(content_field.unwrap().field_type, "String");
-            }
-            _ => panic!("Expected Struct"),
-        }
+        .expect("Failed to deserialize OpenAPI spec");
+
+        let (models, _, _) = parse_openapi(&openapi_spec).expect("Failed to parse");
+        let aliases: Vec<_> = models
+            .iter()
+            .filter_map(|model| match model {
+                ModelType::TypeAlias(alias) => {
+                    Some((alias.name.as_str(), alias.target_type.as_str()))
+                }
+                _ => None,
+            })
+            .collect();
+        assert_eq!(aliases, vec![("Person", "User"), ("Owner", "Person")]);
     }
 
     #[test]
-    fn test_x_rust_type_on_integer_property() {
+    fn test_nested_all_of_compositions_are_flattened() {
         let openapi_spec: OpenAPI = serde_json::from_value(json!({
             "openapi": "3.0.0",
             "info": { "title": "Test API", "version": "1.0.0" },
             "paths": {},
             "components": {
                 "schemas": {
-                    "Configuration": {
+                    "Base": {
                         "type": "object",
-                        "description": "Configuration with custom duration type",
-                        "properties": {
-                            "timeout": {
-                                "type": "integer",
-                                "x-rust-type": "std::time::Duration",
-                                "description": "Timeout duration."
-                            },
-                            "retries": { "type": "integer" }
-                        },
-                        "required": ["timeout", "retries"]
+                        "required": ["id"],
+                        "properties": { "id": { "type": "string" } }
+                    },
+                    "Named": {
+                        "allOf": [
+                            { "$ref": "#/components/schemas/Base" },
+                            { "allOf": [{
+                                "type": "object",
+                                "properties": {
+                                    "name": { "type": "string" },
+                                    "status": { "type": "string", "enum": ["active", "retired"] }
+                                }
+                            }] }
+                        ]
+                    },
+                    "Dog": { "type": "object", "properties": { "bark": { "type": "boolean" } } },
+                    "Cat": { "type": "object", "properties": { "meow": { "type": "boolean" } } },
+                    "Pet": {
+                        "allOf": [
+                            { "$ref": "#/components/schemas/Named" },
+                            { "properties": { "age": { "type": "integer" } } },
+                            { "required": ["name", "age"] },
+                            { "oneOf": [
+                                { "$ref": "#/components/schemas/Dog" },
+                                { "$ref": "#/components/schemas/Cat" }
+                            ] }
+                        ]
                     }
                 }
             }
         }))
         .expect("Failed to deserialize OpenAPI spec");
 
-        let (models, _, _) = parse_openapi(&openapi_spec).expect("Failed to parse OpenAPI spec");
-
-        let config_model = models.iter().find(|m| m.name() == "Configuration");
-        assert!(config_model.is_some(), "Expected Configuration model");
-
-        match config_model.unwrap() {
-            ModelType::Struct(model) => {
-                // Verify that timeout field has custom type
-                let timeout_field = model.fields.iter().find(|f| f.name == "timeout");
-                assert!(timeout_field.is_some(), "Expected timeout field");
-                assert_eq!(timeout_field.unwrap().field_type, "std::time::Duration");
-
-                // Verify other field has regular i64 type
-                let retries_field = model.fields.iter().find(|f| f.name == "retries");
-                assert_eq!(retries_field.unwrap().field_type, "i64");
-            }
-            _ => panic!("Expected Struct"),
-        }
+        let (models, _, _) = parse_openapi(&openapi_spec).expect("Failed to parse");
+        let Some(ModelType::Composition(pet)) = models.iter().find(|m| m.name() == "Pet") else {
+            panic!("Expected a Pet composition");
+        };
+        let fields: Vec<_> = pet
+            .all_fields
+            .iter()
+            .map(|f| {
+                (
+                    f.name.as_str(),
+                    f.field_type.as_str(),
+                    f.is_required,
+                    f.flatten,
+                )
+            })
+            .collect();
+        assert_eq!(
+            fields,
+            vec![
+                ("id", "String", true, false),
+                ("name", "String", true, false),
+                ("status", "NamedStatus", false, false),
+                ("age", "i64", true, false),
+                ("PetOneOf", "PetOneOf", true, true),
+            ]
+        );
+        assert!(models.iter().any(|m| m.name() == "NamedStatus"));
+        assert!(matches!(
+            models.iter().find(|m| m.name() == "PetOneOf"),
+            Some(ModelType::Union(_))
+        ));
     }
 
     #[test]
-    fn test_x_rust_type_on_number_property() {
+    fn test_head_options_and_trace_operations_are_parsed() {
         let openapi_spec: OpenAPI = serde_json::from_value(json!({
             "openapi": "3.0.0",
             "info": { "title": "Test API", "version": "1.0.0" },
-            "paths": {},
-            "components": {
-                "schemas": {
-                    "Product": {
-                        "type": "object",
-                        "description": "Product with custom decimal type",
-                        "properties": {
-                            "price": {
-                                "type": "number",
-                                "x-rust-type": "decimal::Decimal",
-                                "description": "Product price."
-                            },
-                            "quantity": { "type": "number" }
+            "paths": {
+                "/files": {
+                    "head": {
+                        "operationId": "checkFile",
+                        "responses": { "204": { "description": "Exists" } }
+                    },
+                    "options": {
+                        "operationId": "fileOptions",
+                        "requestBody": {
+                            "content": { "application/json": { "schema": { "type": "string" } } }
                         },
-                        "required": ["price", "quantity"]
+                        "responses": {
+                            "200": {
+                                "description": "Allowed methods",
+                                "content": {
+                                    "application/json": {
+                                        "schema": { "type": "array", "items": { "type": "string" } }
+                                    }
+                                }
+                            }
+                        }
+                    },
+                    "trace": {
+                        "operationId": "traceFile",
+                        "responses": {
+                            "200": {
+                                "description": "Echo",
+                                "content": { "message/http": { "schema": { "type": "string" } } }
+                            }
+                        }
                     }
                 }
             }
         }))
         .expect("Failed to deserialize OpenAPI spec");
 
-        let (models, _, _) = parse_openapi(&openapi_spec).expect("Failed to parse OpenAPI spec");
+        let (_, requests, responses) = parse_openapi(&openapi_spec).expect("Failed to parse");
+        let requests: Vec<_> = requests
+            .iter()
+            .map(|r| (r.name.as_str(), r.method.as_str()))
+            .collect();
+        assert_eq!(requests, vec![("FileOptionsRequest", "OPTIONS")]);
+        let responses: Vec<_> = responses
+            .iter()
+            .map(|r| (r.name.as_str(), r.method.as_str(), r.schema.as_str()))
+            .collect();
+        assert_eq!(
+            responses,
+            vec![
+                ("FileOptions", "OPTIONS", "Vec<String>"),
+                ("TraceFile", "TRACE", "String"),
+            ]
+        );
+    }
 
-        let product_model = models.iter().find(|m| m.name() == "Product");
-        assert!(product_model.is_some(), "Expected Product model");
+    #[test]
+    fn test_xml_objects_are_kept_on_load() {
+        let yaml = r#"
+openapi: 3.0.0
+info: { title: Test API, version: 1.0.0 }
+paths: {}
+components:
+  schemas:
+    pet:
+      type: object
+      xml: { name: Pet }
+      properties:
+        id: { type: integer, xml: { attribute: true } }
+        tags:
+          type: array
+          items: { type: string, xml: { name: tag } }
+        xml: { type: string }
+"#;
+        let openapi_spec = load_openapi(yaml, true).expect("Failed to load");
+        let (models, _, _) = parse_openapi(&openapi_spec).expect("Failed to parse");
+        let Some(ModelType::Struct(pet)) = models.iter().find(|m| m.name() == "Pet") else {
+            panic!("Expected a Pet struct");
+        };
+        assert_eq!(pet.xml_name.as_deref(), Some("Pet"));
+        let xml = |name: &str| {
+            pet.fields
+                .iter()
+                .find(|f| f.name == name)
+                .and_then(|f| f.xml.clone())
+        };
+        assert_eq!(
+            xml("id"),
+            Some(XmlOptions {
+                attribute: true,
+                ..XmlOptions::default()
+            })
+        );
+        assert_eq!(
+            xml("tags"),
+            Some(XmlOptions {
+                items_name: Some("tag".to_string()),
+                ..XmlOptions::default()
+            })
+        );
+        // A property named "xml" is a schema, not the keyword
+        assert_eq!(xml("xml"), None);
+    }
 
-        match product_model.unwrap() {
-            ModelType::Struct(model) => {
-                // Verify that price field has custom type
-                let price_field = model.fields.iter().find(|f| f.name == "price");
-                assert!(price_field.is_some(), "Expected price field");
-                assert_eq!(price_field.unwrap().field_type, "decimal::Decimal");
+    #[test]
+    fn test_ref_siblings_are_kept_on_load() {
+        let yaml = r#"
+openapi: 3.0.0
+info: { title: Test API, version: 1.0.0 }
+paths: {}
+components:
+  schemas:
+    Address:
+      type: object
+      description: A postal address
+      properties:
+        city: { type: string }
+    User:
+      type: object
+      required: [home]
+      properties:
+        home:
+          $ref: '#/components/schemas/Address'
+          description: Where the user lives
+          nullable: true
+        plain: { $ref: '#/components/schemas/Address' }
+"#;
+        let openapi_spec = load_openapi(yaml, true).expect("Failed to load");
+        let (models, _, _) = parse_openapi(&openapi_spec).expect("Failed to parse");
+        let Some(ModelType::Struct(user)) = models.iter().find(|m| m.name() == "User") else {
+            panic!("Expected a User struct");
+        };
+        let home = user.fields.iter().find(|f| f.name == "home").unwrap();
+        assert_eq!(home.field_type, "Address");
+        assert!(home.is_nullable);
+        assert_eq!(home.description.as_deref(), Some("Where the user lives"));
+        let plain = user.fields.iter().find(|f| f.name == "plain").unwrap();
+        assert_eq!(plain.field_type, "Address");
+        assert!(!plain.is_nullable);
+        assert_eq!(plain.description, None);
+    }
 
-                // Verify other field has regular f64 type
-                let quantity_field = model.fields.iter().find(|f| f.name == "quantity");
-                assert_eq!(quantity_field.unwrap().field_type, "f64");
-            }
-            _ => panic!("Expected Struct"),
-        }
+    #[test]
+    fn test_prefix_items_become_tuples() {
+        let yaml = r#"
+openapi: 3.1.0
+info: { title: Test API, version: 1.0.0 }
+paths: {}
+components:
+  schemas:
+    Point:
+      type: array
+      prefixItems: [{ type: number }, { type: number }]
+      items: false
+    Shape:
+      type: object
+      required: [origin]
+      properties:
+        origin: { $ref: '#/components/schemas/Point' }
+        label:
+          type: array
+          prefixItems: [{ type: string }, { type: [integer, "null"] }]
+        open:
+          type: array
+          prefixItems: [{ type: string }]
+          items: { type: integer }
+"#;
+        let openapi_spec = load_openapi(yaml, true).expect("Failed to load");
+        let (models, _, _) = parse_openapi(&openapi_spec).expect("Failed to parse");
+        let Some(ModelType::TypeAlias(point)) = models.iter().find(|m| m.name() == "Point") else {
+            panic!("Expected a Point alias");
+        };
+        assert_eq!(point.target_type, "(f64, f64)");
+
+        let Some(ModelType::Struct(shape)) = models.iter().find(|m| m.name() == "Shape") else {
+            panic!("Expected a Shape struct");
+        };
+        let field = |name: &str| shape.fields.iter().find(|f| f.name == name).unwrap();
+        assert_eq!(field("origin").field_type, "Point");
+        assert!(!field("origin").is_array_ref);
+        assert_eq!(field("label").field_type, "(String, Option<i64>)");
+        assert!(!field("label").is_array_ref);
+        assert_eq!(field("open").field_type, "serde_json::Value");
+        assert!(field("open").is_array_ref);
     }
 
     #[test]
-    fn test_x_rust_type_on_nullable_property() {
+    fn test_conditionals_merge_branch_properties_and_warn() {
+        let yaml = r#"
+openapi: 3.1.0
+info: { title: Test API, version: 1.0.0 }
+paths: {}
+components:
+  schemas:
+    Payment:
+      type: object
+      required: [method]
+      properties:
+        method: { type: string }
+        then: { type: string }
+      if: { properties: { method: { const: card } } }
+      then:
+        required: [cardNumber]
+        properties: { cardNumber: { type: string } }
+      else:
+        properties: { iban: { type: string } }
+"#;
+        let openapi_spec = load_openapi(yaml, true).expect("Failed to load");
+        let (models, _, _) = parse_openapi(&openapi_spec).expect("Failed to parse");
+        let Some(ModelType::Struct(payment)) = models.iter().find(|m| m.name() == "Payment") else {
+            panic!("Expected a Payment struct");
+        };
+        let fields: Vec<(&str, bool)> = payment
+            .fields
+            .iter()
+            .map(|f| (f.name.as_str(), f.is_required))
+            .collect();
+        assert_eq!(
+            fields,
+            vec![
+                ("cardNumber", false),
+                ("iban", false),
+                ("method", true),
+                ("then", false)
+            ]
+        );
+
+        assert_eq!(
+            collect_warnings(&openapi_spec),
+            vec![
+                "Schema 'Payment' uses if/then/else; generating one struct with the properties \
+                 of the then and else branches as optional fields"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_inline_enums_are_named_after_their_parent() {
         let openapi_spec: OpenAPI = serde_json::from_value(json!({
             "openapi": "3.0.0",
             "info": { "title": "Test API", "version": "1.0.0" },
             "paths": {},
             "components": {
                 "schemas": {
-                    "Settings": {
+                    "Order": {
                         "type": "object",
-                        "description": "Settings with nullable custom type",
                         "properties": {
-                            "settings": {
-                                "type": "string",
-                                "x-rust-type": "serde_json::Value",
-                                "nullable": true,
-                                "description": "Optional settings."
-                            }
+                            "status": { "type": "string", "enum": ["placed", "shipped"] }
+                        }
+                    },
+                    "User": {
+                        "type": "object",
+                        "properties": {
+                            "status": { "type": "string", "enum": ["active", "banned"] }
                         }
                     }
                 }
@@ -2037,95 +7694,312 @@ mod tests {
         .expect("Failed to deserialize OpenAPI spec");
 
         let (models, _, _) = parse_openapi(&openapi_spec).expect("Failed to parse OpenAPI spec");
-
-        let settings_model = models.iter().find(|m| m.name() == "Settings");
-        assert!(settings_model.is_some(), "Expected Settings model");
-
-        match settings_model.unwrap() {
-            ModelType::Struct(model) => {
-                let settings_field = model.fields.iter().find(|f| f.name == "settings");
-                assert!(settings_field.is_some(), "Expected settings field");
-
-                let field = settings_field.unwrap();
-                assert_eq!(field.field_type, "serde_json::Value");
-                assert!(field.is_nullable, "Expected field to be nullable");
-            }
-            _ => panic!("Expected Struct"),
-        }
+        let variants = |name: &str| {
+            let Some(ModelType::Enum(e)) = models.iter().find(|m| m.name() == name) else {
+                panic!("Expected {name} enum");
+            };
+            e.variants.clone()
+        };
+        assert_eq!(variants("OrderStatus"), vec!["placed", "shipped"]);
+        assert_eq!(variants("UserStatus"), vec!["active", "banned"]);
+        assert!(!models.iter().any(|m| m.name() == "Status"));
     }
 
     #[test]
-    fn test_multiple_properties_with_x_rust_type() {
+    fn test_identical_inline_enums_are_shared() {
         let openapi_spec: OpenAPI = serde_json::from_value(json!({
             "openapi": "3.0.0",
             "info": { "title": "Test API", "version": "1.0.0" },
             "paths": {},
             "components": {
                 "schemas": {
-                    "ComplexModel": {
+                    "Order": {
                         "type": "object",
-                        "description": "Model with multiple custom-typed properties",
                         "properties": {
-                            "id": {
-                                "type": "string",
-                                "format": "uuid",
-                                "x-rust-type": "uuid::Uuid"
-                            },
-                            "price": {
-                                "type": "number",
-                                "x-rust-type": "decimal::Decimal"
-                            },
-                            "timeout": {
-                                "type": "integer",
-                                "x-rust-type": "std::time::Duration"
-                            },
-                            "regular_field": { "type": "string" }
-                        },
-                        "required": ["id", "price", "timeout"]
-                    }
+                            "status": { "type": "string", "enum": ["active", "inactive"] },
+                            "phase": { "type": "string", "enum": ["open", "closed"] }
+                        }
+                    },
+                    "User": {
+                        "type": "object",
+                        "properties": {
+                            "status": { "type": "string", "enum": ["active", "inactive"] },
+                            "stage": { "type": "string", "enum": ["open", "closed"] },
+                            "visibility": { "type": "string", "enum": ["public", "private"] }
+                        }
+                    },
+                    "Visibility": { "type": "string", "enum": ["public", "private"] }
                 }
             }
         }))
         .expect("Failed to deserialize OpenAPI spec");
 
         let (models, _, _) = parse_openapi(&openapi_spec).expect("Failed to parse OpenAPI spec");
+        let enums: Vec<&str> = models
+            .iter()
+            .filter(|m| matches!(m, ModelType::Enum(_)))
+            .map(|m| m.name())
+            .collect();
+        assert_eq!(enums, vec!["OrderPhase", "Status", "Visibility"]);
+
+        let field_type = |model: &str, field: &str| {
+            let Some(ModelType::Struct(s)) = models.iter().find(|m| m.name() == model) else {
+                panic!("Expected {model} struct");
+            };
+            s.fields
+                .iter()
+                .find(|f| f.name == field)
+                .unwrap()
+                .field_type
+                .clone()
+        };
+        assert_eq!(field_type("Order", "status"), "Status");
+        assert_eq!(field_type("User", "status"), "Status");
+        assert_eq!(field_type("User", "stage"), "OrderPhase");
+        assert_eq!(field_type("User", "visibility"), "Visibility");
+    }
 
-        let model = models.iter().find(|m| m.name() == "ComplexModel");
-        assert!(model.is_some(), "Expected ComplexModel model");
+    #[test]
+    fn test_colliding_schema_names_are_suffixed() {
+        let yaml = r#"
+openapi: 3.0.0
+info: { title: Test API, version: 1.0.0 }
+paths: {}
+components:
+  schemas:
+    UserProfile:
+      type: object
+      properties:
+        name: { type: string }
+    user_profile:
+      type: object
+      properties:
+        id: { type: integer }
+    Account:
+      type: object
+      properties:
+        profile: { $ref: '#/components/schemas/user_profile' }
+        display: { $ref: '#/components/schemas/UserProfile' }
+"#;
+        let openapi_spec = load_openapi(yaml, true).expect("Failed to load");
+        let (models, _, _) = parse_openapi(&openapi_spec).expect("Failed to parse");
+        let names: Vec<&str> = models.iter().map(|m| m.name()).collect();
+        assert_eq!(names, vec!["Account", "UserProfile", "UserProfile2"]);
+
+        let Some(ModelType::Struct(renamed)) = models.iter().find(|m| m.name() == "UserProfile2")
+        else {
+            panic!("Expected a UserProfile2 struct");
+        };
+        assert_eq!(renamed.fields[0].name, "id");
+        let Some(ModelType::Struct(account)) = models.iter().find(|m| m.name() == "Account") else {
+            panic!("Expected an Account struct");
+        };
+        let types: Vec<(&str, &str)> = account
+            .fields
+            .iter()
+            .map(|f| (f.name.as_str(), f.field_type.as_str()))
+            .collect();
+        assert_eq!(
+            types,
+            vec![("display", "UserProfile"), ("profile", "UserProfile2")]
+        );
 
-        match model.unwrap() {
-            ModelType::Struct(struct_model) => {
-                // Verify all custom types
-                let id_field = struct_model.fields.iter().find(|f| f.name == "id");
-                assert_eq!(id_field.unwrap().field_type, "uuid::Uuid");
+        assert_eq!(
+            collect_warnings(&openapi_spec),
+            vec![
+                "Schemas 'UserProfile' and 'user_profile' are both named UserProfile; \
+                 generating 'user_profile' as UserProfile2"
+            ]
+        );
+    }
 
-                let price_field = struct_model.fields.iter().find(|f| f.name == "price");
-                assert_eq!(price_field.unwrap().field_type, "decimal::Decimal");
+    #[test]
+    fn test_extensible_enum_list_is_read_as_an_open_enum() {
+        let yaml = r#"
+openapi: 3.0.0
+info: { title: Test API, version: 1.0.0 }
+paths: {}
+components:
+  schemas:
+    Color:
+      type: string
+      x-extensible-enum: [red, green]
+    Size:
+      type: string
+      enum: [small, large]
+"#;
+        let openapi_spec = load_openapi(yaml, true).expect("Failed to load");
+        let (models, _, _) = parse_openapi(&openapi_spec).expect("Failed to parse");
+        let enums: Vec<(&str, &[String], bool)> = models
+            .iter()
+            .filter_map(|m| match m {
+                ModelType::Enum(e) => Some((e.name.as_str(), e.variants.as_slice(), e.extensible)),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(
+            enums,
+            vec![
+                ("Color", &["red".to_string(), "green".to_string()][..], true),
+                (
+                    "Size",
+                    &["small".to_string(), "large".to_string()][..],
+                    false
+                )
+            ]
+        );
+    }
 
-                let timeout_field = struct_model.fields.iter().find(|f| f.name == "timeout");
-                assert_eq!(timeout_field.unwrap().field_type, "std::time::Duration");
+    #[test]
+    fn test_identifier_fields_are_wrapped_in_newtypes() {
+        let yaml = r#"
+openapi: 3.0.0
+info: { title: Test API, version: 1.0.0 }
+paths: {}
+components:
+  schemas:
+    User:
+      type: object
+      properties:
+        id: { type: string, format: uuid }
+        orgID: { type: integer, format: int64, minimum: 1 }
+        tagIds: { type: array, items: { type: string } }
+        name: { type: string }
+    Order:
+      type: object
+      properties:
+        userId: { type: string, format: uuid }
+        sku: { type: string, x-newtype: Sku }
+        createdById: { type: string }
+    CreatedById:
+      type: string
+      enum: [a]
+"#;
+        let openapi_spec = load_openapi(yaml, true).expect("Failed to load");
+        let (mut models, _, _) = parse_openapi(&openapi_spec).expect("Failed to parse");
+        let newtypes = |models: &[ModelType]| -> Vec<(String, String)> {
+            models
+                .iter()
+                .filter_map(|m| match m {
+                    ModelType::Newtype(n) => Some((n.name.clone(), n.inner_type.clone())),
+                    _ => None,
+                })
+                .collect()
+        };
+        assert_eq!(
+            newtypes(&models),
+            vec![("Sku".to_string(), "String".to_string())]
+        );
 
-                // Verify regular field
-                let regular_field = struct_model
-                    .fields
-                    .iter()
-                    .find(|f| f.name == "regular_field");
-                assert_eq!(regular_field.unwrap().field_type, "String");
+        wrap_identifiers(&mut models, true);
+        let field_types: Vec<(&str, &str)> = models
+            .iter()
+            .filter_map(|m| match m {
+                ModelType::Struct(model) => Some(&model.fields),
+                _ => None,
+            })
+            .flatten()
+            .map(|f| (f.name.as_str(), f.field_type.as_str()))
+            .collect();
+        assert_eq!(
+            field_types,
+            vec![
+                ("createdById", "String"),
+                ("sku", "Sku"),
+                ("userId", "UserId"),
+                ("id", "UserId"),
+                ("name", "String"),
+                ("orgID", "OrgId"),
+                ("tagIds", "TagId"),
+            ]
+        );
+        assert_eq!(
+            newtypes(&models),
+            vec![
+                ("Sku".to_string(), "String".to_string()),
+                ("UserId".to_string(), "Uuid".to_string()),
+                ("OrgId".to_string(), "i64".to_string()),
+                ("TagId".to_string(), "String".to_string()),
+            ]
+        );
+    }
 
-                // Verify nullable flags for required/optional fields
-                assert!(!id_field.unwrap().is_nullable, "id should not be nullable");
-                assert!(
-                    !price_field.unwrap().is_nullable,
-                    "price should not be nullable"
-                );
-                assert!(
-                    !timeout_field.unwrap().is_nullable,
-                    "timeout should not be nullable"
-                );
-                // regular_field is not in required, but generator doesn't mark it as nullable
-                // (this is expected behavior - nullable only for explicitly nullable fields)
+    #[test]
+    fn test_progress_is_reported_in_steps() {
+        let reported: Vec<usize> = (1..=120)
+            .filter(|done| should_report_progress(*done, 120))
+            .collect();
+        assert_eq!(
+            reported,
+            vec![12, 24, 36, 48, 60, 72, 84, 96, 108, 120],
+            "Expected one event per 10% of the schemas"
+        );
+
+        let reported: Vec<usize> = (1..=3)
+            .filter(|done| should_report_progress(*done, 3))
+            .collect();
+        assert_eq!(reported, vec![1, 2, 3]);
+    }
+
+    /// Runs `f` with a subscriber that records `info` events, returning the formatted logs
+    fn capture_info_logs(f: impl FnOnce()) -> String {
+        use std::io::Write;
+        use std::sync::{Arc, Mutex};
+
+        #[derive(Clone, Default)]
+        struct CaptureWriter(Arc<Mutex<Vec<u8>>>);
+
+        impl Write for CaptureWriter {
+            fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+                self.0.lock().unwrap().extend_from_slice(buf);
+                Ok(buf.len())
+            }
+
+            fn flush(&mut self) -> std::io::Result<()> {
+                Ok(())
             }
-            _ => panic!("Expected Struct"),
         }
+
+        let writer = CaptureWriter::default();
+        let make_writer = {
+            let writer = writer.clone();
+            move || writer.clone()
+        };
+        let subscriber = tracing_subscriber::fmt()
+            .with_max_level(tracing::Level::INFO)
+            .with_ansi(false)
+            .with_writer(make_writer)
+            .finish();
+
+        tracing::subscriber::with_default(subscriber, f);
+
+        let logs = writer.0.lock().unwrap().clone();
+        String::from_utf8(logs).unwrap()
+    }
+
+    #[test]
+    fn test_parse_emits_progress_events() {
+        let schemas: serde_json::Map<String, serde_json::Value> = (0..20)
+            .map(|i| {
+                (
+                    format!("Model{i}"),
+                    json!({ "type": "object", "properties": { "id": { "type": "string" } } }),
+                )
+            })
+            .collect();
+        let openapi_spec: OpenAPI = serde_json::from_value(json!({
+            "openapi": "3.0.0",
+            "info": { "title": "Test API", "version": "1.0.0" },
+            "paths": {},
+            "components": { "schemas": schemas }
+        }))
+        .expect("Failed to deserialize OpenAPI spec");
+
+        let logs = capture_info_logs(|| {
+            parse_openapi(&openapi_spec).expect("Failed to parse OpenAPI spec");
+        });
+        assert!(logs.contains("Parsing schemas: 2/20"));
+        assert!(logs.contains("Parsing schemas: 20/20"));
+        assert!(!logs.contains("Parsing schemas: 3/20"));
     }
 }
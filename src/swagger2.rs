@@ -0,0 +1,716 @@
+//! Conversion of Swagger 2.0 documents to the OpenAPI 3.0 shape accepted by `openapiv3`
+
+use std::collections::{HashMap, HashSet};
+
+/// Keywords that live directly on a Swagger 2.0 non-body parameter or header but
+/// belong in its `schema` in OpenAPI 3.0
+const SWAGGER_SCHEMA_KEYWORDS: &[&str] = &[
+    "type",
+    "format",
+    "items",
+    "default",
+    "maximum",
+    "exclusiveMaximum",
+    "minimum",
+    "exclusiveMinimum",
+    "maxLength",
+    "minLength",
+    "pattern",
+    "maxItems",
+    "minItems",
+    "uniqueItems",
+    "enum",
+    "multipleOf",
+    "nullable",
+];
+
+/// Upgrades a Swagger 2.0 document to the OpenAPI 3.0 shape accepted by `openapiv3`
+pub(crate) fn convert_swagger_2(swagger: &serde_json::Value) -> serde_json::Value {
+    use serde_json::{json, Map, Value};
+
+    let mut swagger = swagger.clone();
+    rewrite_swagger_schemas(&mut swagger);
+
+    let consumes = swagger_media_types(swagger.get("consumes"), "application/json");
+    let produces = swagger_media_types(swagger.get("produces"), "application/json");
+
+    let mut openapi = Map::new();
+    openapi.insert("openapi".to_string(), json!("3.0.3"));
+    openapi.insert(
+        "info".to_string(),
+        swagger
+            .get("info")
+            .cloned()
+            .unwrap_or_else(|| json!({ "title": "", "version": "" })),
+    );
+
+    let base_path = swagger
+        .get("basePath")
+        .and_then(Value::as_str)
+        .unwrap_or_default();
+    if let Some(host) = swagger.get("host").and_then(Value::as_str) {
+        let schemes = swagger
+            .get("schemes")
+            .and_then(Value::as_array)
+            .map(|schemes| schemes.iter().filter_map(Value::as_str).collect::<Vec<_>>())
+            .filter(|schemes| !schemes.is_empty())
+            .unwrap_or_else(|| vec!["https"]);
+        let servers: Vec<Value> = schemes
+            .iter()
+            .map(|scheme| json!({ "url": format!("{scheme}://{host}{base_path}") }))
+            .collect();
+        openapi.insert("servers".to_string(), Value::Array(servers));
+    } else if !base_path.is_empty() {
+        openapi.insert("servers".to_string(), json!([{ "url": base_path }]));
+    }
+
+    if let Some(root) = swagger.as_object() {
+        for (key, value) in root {
+            if key.starts_with("x-") || ["tags", "security", "externalDocs"].contains(&key.as_str())
+            {
+                openapi.insert(key.clone(), value.clone());
+            }
+        }
+    }
+
+    // Shared parameters: body parameters become request bodies, the rest stay parameters
+    let mut components = Map::new();
+    let mut body_parameters = HashSet::new();
+    let mut form_parameters: HashMap<String, Value> = HashMap::new();
+    if let Some(definitions) = swagger.get("definitions") {
+        components.insert("schemas".to_string(), definitions.clone());
+    }
+    if let Some(parameters) = swagger.get("parameters").and_then(Value::as_object) {
+        let mut converted = Map::new();
+        let mut request_bodies = Map::new();
+        for (name, parameter) in parameters {
+            match parameter.get("in").and_then(Value::as_str) {
+                Some("body") => {
+                    body_parameters.insert(name.clone());
+                    request_bodies.insert(
+                        name.clone(),
+                        convert_swagger_body_parameter(parameter, &consumes),
+                    );
+                }
+                Some("formData") => {
+                    form_parameters.insert(name.clone(), parameter.clone());
+                }
+                _ => {
+                    converted.insert(name.clone(), convert_swagger_parameter(parameter));
+                }
+            }
+        }
+        components.insert("parameters".to_string(), Value::Object(converted));
+        if !request_bodies.is_empty() {
+            components.insert("requestBodies".to_string(), Value::Object(request_bodies));
+        }
+    }
+    if let Some(responses) = swagger.get("responses").and_then(Value::as_object) {
+        let converted = responses
+            .iter()
+            .map(|(name, response)| (name.clone(), convert_swagger_response(response, &produces)))
+            .collect();
+        components.insert("responses".to_string(), Value::Object(converted));
+    }
+    if let Some(schemes) = swagger
+        .get("securityDefinitions")
+        .and_then(Value::as_object)
+    {
+        let converted = schemes
+            .iter()
+            .map(|(name, scheme)| (name.clone(), convert_swagger_security_scheme(scheme)))
+            .collect();
+        components.insert("securitySchemes".to_string(), Value::Object(converted));
+    }
+    openapi.insert("components".to_string(), Value::Object(components));
+
+    let mut paths = Map::new();
+    if let Some(swagger_paths) = swagger.get("paths").and_then(Value::as_object) {
+        for (path, item) in swagger_paths {
+            let Some(item) = item.as_object() else {
+                continue;
+            };
+            let shared: Vec<Value> = item
+                .get("parameters")
+                .and_then(Value::as_array)
+                .cloned()
+                .unwrap_or_default();
+
+            let mut converted = Map::new();
+            for (key, value) in item {
+                match key.as_str() {
+                    "get" | "put" | "post" | "delete" | "options" | "head" | "patch" => {
+                        let context = SwaggerOperationContext {
+                            consumes: &consumes,
+                            produces: &produces,
+                            body_parameters: &body_parameters,
+                            form_parameters: &form_parameters,
+                        };
+                        converted.insert(
+                            key.clone(),
+                            convert_swagger_operation(value, &shared, &context),
+                        );
+                    }
+                    // Shared parameters are merged into each operation above
+                    "parameters" => {}
+                    _ => {
+                        converted.insert(key.clone(), value.clone());
+                    }
+                }
+            }
+            paths.insert(path.clone(), Value::Object(converted));
+        }
+    }
+    openapi.insert("paths".to_string(), Value::Object(paths));
+
+    Value::Object(openapi)
+}
+
+/// Document-wide data needed while converting a single Swagger 2.0 operation
+struct SwaggerOperationContext<'a> {
+    consumes: &'a [String],
+    produces: &'a [String],
+    /// Names of shared parameters that were converted to request bodies
+    body_parameters: &'a HashSet<String>,
+    /// Shared `formData` parameters, inlined into the form request body
+    form_parameters: &'a HashMap<String, serde_json::Value>,
+}
+
+fn convert_swagger_operation(
+    operation: &serde_json::Value,
+    shared_parameters: &[serde_json::Value],
+    context: &SwaggerOperationContext,
+) -> serde_json::Value {
+    use serde_json::{json, Map, Value};
+
+    let consumes = match operation.get("consumes") {
+        Some(list) => swagger_media_types(Some(list), "application/json"),
+        None => context.consumes.to_vec(),
+    };
+    let produces = match operation.get("produces") {
+        Some(list) => swagger_media_types(Some(list), "application/json"),
+        None => context.produces.to_vec(),
+    };
+
+    let mut converted = Map::new();
+    let mut parameters = Vec::new();
+    let mut request_body = None;
+    let mut form_fields = Vec::new();
+
+    let operation_parameters = operation
+        .get("parameters")
+        .and_then(Value::as_array)
+        .cloned()
+        .unwrap_or_default();
+    for parameter in shared_parameters.iter().chain(&operation_parameters) {
+        if let Some(reference) = parameter.get("$ref").and_then(Value::as_str) {
+            let name = reference
+                .strip_prefix("#/components/parameters/")
+                .unwrap_or_default();
+            if context.body_parameters.contains(name) {
+                request_body =
+                    Some(json!({ "$ref": format!("#/components/requestBodies/{name}") }));
+            } else if let Some(form) = context.form_parameters.get(name) {
+                form_fields.push(form.clone());
+            } else {
+                parameters.push(parameter.clone());
+            }
+            continue;
+        }
+        match parameter.get("in").and_then(Value::as_str) {
+            Some("body") => {
+                request_body = Some(convert_swagger_body_parameter(parameter, &consumes));
+            }
+            Some("formData") => form_fields.push(parameter.clone()),
+            _ => parameters.push(convert_swagger_parameter(parameter)),
+        }
+    }
+
+    if !form_fields.is_empty() {
+        let multipart = consumes.iter().any(|m| m == "multipart/form-data")
+            || form_fields
+                .iter()
+                .any(|field| field.get("format").is_some_and(|f| f == "binary"));
+        let media_type = if multipart {
+            "multipart/form-data"
+        } else {
+            "application/x-www-form-urlencoded"
+        };
+
+        let mut properties = Map::new();
+        let mut required = Vec::new();
+        for field in &form_fields {
+            let Some(name) = field.get("name").and_then(Value::as_str) else {
+                continue;
+            };
+            let mut schema = swagger_parameter_schema(field);
+            if let Some(description) = field.get("description") {
+                schema.insert("description".to_string(), description.clone());
+            }
+            properties.insert(name.to_string(), Value::Object(schema));
+            if field.get("required").is_some_and(|r| r == true) {
+                required.push(json!(name));
+            }
+        }
+        let mut schema = json!({ "type": "object", "properties": properties });
+        if !required.is_empty() {
+            schema["required"] = Value::Array(required);
+        }
+        request_body = Some(json!({ "content": { media_type: { "schema": schema } } }));
+    }
+
+    if let Some(operation) = operation.as_object() {
+        for (key, value) in operation {
+            match key.as_str() {
+                "parameters" | "consumes" | "produces" | "schemes" => {}
+                "responses" => {
+                    let responses = value
+                        .as_object()
+                        .map(|responses| {
+                            responses
+                                .iter()
+                                .map(|(status, response)| {
+                                    (
+                                        status.clone(),
+                                        convert_swagger_response(response, &produces),
+                                    )
+                                })
+                                .collect()
+                        })
+                        .unwrap_or_default();
+                    converted.insert(key.clone(), Value::Object(responses));
+                }
+                _ => {
+                    converted.insert(key.clone(), value.clone());
+                }
+            }
+        }
+    }
+    if !parameters.is_empty() {
+        converted.insert("parameters".to_string(), Value::Array(parameters));
+    }
+    if let Some(request_body) = request_body {
+        converted.insert("requestBody".to_string(), request_body);
+    }
+    converted.entry("responses").or_insert_with(|| json!({}));
+
+    Value::Object(converted)
+}
+
+/// Converts an `in: body` parameter into a request body with one entry per media type
+fn convert_swagger_body_parameter(
+    parameter: &serde_json::Value,
+    consumes: &[String],
+) -> serde_json::Value {
+    let schema = parameter
+        .get("schema")
+        .cloned()
+        .unwrap_or_else(|| serde_json::json!({}));
+    let content: serde_json::Map<String, serde_json::Value> = consumes
+        .iter()
+        .map(|media_type| (media_type.clone(), serde_json::json!({ "schema": schema })))
+        .collect();
+
+    let mut body = serde_json::json!({ "content": content });
+    for key in ["description", "required"] {
+        if let Some(value) = parameter.get(key) {
+            body[key] = value.clone();
+        }
+    }
+    body
+}
+
+/// Moves the inline type keywords of a non-body parameter into `schema`
+fn convert_swagger_parameter(parameter: &serde_json::Value) -> serde_json::Value {
+    if parameter.get("$ref").is_some() {
+        return parameter.clone();
+    }
+    let Some(fields) = parameter.as_object() else {
+        return parameter.clone();
+    };
+
+    let mut converted: serde_json::Map<String, serde_json::Value> = fields
+        .iter()
+        .filter(|(key, _)| {
+            !SWAGGER_SCHEMA_KEYWORDS.contains(&key.as_str()) && key.as_str() != "collectionFormat"
+        })
+        .map(|(key, value)| (key.clone(), value.clone()))
+        .collect();
+    converted.insert(
+        "schema".to_string(),
+        serde_json::Value::Object(swagger_parameter_schema(parameter)),
+    );
+    serde_json::Value::Object(converted)
+}
+
+/// Collects the schema keywords of a non-body parameter or header
+fn swagger_parameter_schema(
+    parameter: &serde_json::Value,
+) -> serde_json::Map<String, serde_json::Value> {
+    SWAGGER_SCHEMA_KEYWORDS
+        .iter()
+        .filter_map(|key| {
+            parameter
+                .get(*key)
+                .map(|value| (key.to_string(), value.clone()))
+        })
+        .collect()
+}
+
+fn convert_swagger_response(
+    response: &serde_json::Value,
+    produces: &[String],
+) -> serde_json::Value {
+    use serde_json::{json, Map, Value};
+
+    if response.get("$ref").is_some() {
+        return response.clone();
+    }
+
+    let mut converted = Map::new();
+    converted.insert(
+        "description".to_string(),
+        response.get("description").cloned().unwrap_or(json!("")),
+    );
+    if let Some(schema) = response.get("schema") {
+        let content: Map<String, Value> = produces
+            .iter()
+            .map(|media_type| {
+                let mut entry = json!({ "schema": schema });
+                if let Some(example) = response.get("examples").and_then(|e| e.get(media_type)) {
+                    entry["example"] = example.clone();
+                }
+                (media_type.clone(), entry)
+            })
+            .collect();
+        converted.insert("content".to_string(), Value::Object(content));
+    }
+    if let Some(headers) = response.get("headers").and_then(Value::as_object) {
+        let headers: Map<String, Value> = headers
+            .iter()
+            .map(|(name, header)| {
+                let mut converted = json!({ "schema": swagger_parameter_schema(header) });
+                if let Some(description) = header.get("description") {
+                    converted["description"] = description.clone();
+                }
+                (name.clone(), converted)
+            })
+            .collect();
+        converted.insert("headers".to_string(), Value::Object(headers));
+    }
+    if let Some(fields) = response.as_object() {
+        for (key, value) in fields.iter().filter(|(key, _)| key.starts_with("x-")) {
+            converted.insert(key.clone(), value.clone());
+        }
+    }
+    Value::Object(converted)
+}
+
+fn convert_swagger_security_scheme(scheme: &serde_json::Value) -> serde_json::Value {
+    use serde_json::json;
+
+    let mut converted = match scheme.get("type").and_then(|t| t.as_str()) {
+        Some("basic") => json!({ "type": "http", "scheme": "basic" }),
+        Some("oauth2") => {
+            let flow = match scheme.get("flow").and_then(|f| f.as_str()) {
+                Some("application") => "clientCredentials",
+                Some("accessCode") => "authorizationCode",
+                Some("password") => "password",
+                _ => "implicit",
+            };
+            let mut details = json!({
+                "scopes": scheme.get("scopes").cloned().unwrap_or_else(|| json!({}))
+            });
+            for key in ["authorizationUrl", "tokenUrl"] {
+                if let Some(url) = scheme.get(key) {
+                    details[key] = url.clone();
+                }
+            }
+            json!({ "type": "oauth2", "flows": { flow: details } })
+        }
+        _ => {
+            let mut api_key = json!({ "type": "apiKey" });
+            for key in ["name", "in"] {
+                if let Some(value) = scheme.get(key) {
+                    api_key[key] = value.clone();
+                }
+            }
+            api_key
+        }
+    };
+    if let Some(description) = scheme.get("description") {
+        converted["description"] = description.clone();
+    }
+    converted
+}
+
+/// Reads a `consumes`/`produces` list, falling back to `default` when absent or empty
+fn swagger_media_types(list: Option<&serde_json::Value>, default: &str) -> Vec<String> {
+    let media_types: Vec<String> = list
+        .and_then(|list| list.as_array())
+        .map(|list| {
+            list.iter()
+                .filter_map(|m| m.as_str().map(|m| m.to_string()))
+                .collect()
+        })
+        .unwrap_or_default();
+    if media_types.is_empty() {
+        vec![default.to_string()]
+    } else {
+        media_types
+    }
+}
+
+/// Rewrites 2.0 references to their 3.0 locations, `x-nullable` to `nullable`
+/// and `type: file` to a binary string
+fn rewrite_swagger_schemas(value: &mut serde_json::Value) {
+    match value {
+        serde_json::Value::Object(map) => {
+            if let Some(serde_json::Value::String(reference)) = map.get_mut("$ref") {
+                for (from, to) in [
+                    ("#/definitions/", "#/components/schemas/"),
+                    ("#/parameters/", "#/components/parameters/"),
+                    ("#/responses/", "#/components/responses/"),
+                ] {
+                    if let Some(name) = reference.strip_prefix(from) {
+                        *reference = format!("{to}{name}");
+                        break;
+                    }
+                }
+            }
+            if let Some(nullable) = map.remove("x-nullable") {
+                map.insert("nullable".to_string(), nullable);
+            }
+            if map.get("type").is_some_and(|t| t == "file") {
+                map.insert("type".to_string(), serde_json::json!("string"));
+                map.insert("format".to_string(), serde_json::json!("binary"));
+            }
+            for inner in map.values_mut() {
+                rewrite_swagger_schemas(inner);
+            }
+        }
+        serde_json::Value::Array(items) => {
+            for inner in items {
+                rewrite_swagger_schemas(inner);
+            }
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_body_and_form_data_parameters_become_request_bodies() {
+        let swagger = json!({
+            "swagger": "2.0",
+            "info": { "title": "Test API", "version": "1.0.0" },
+            "paths": {
+                "/pets": {
+                    "post": {
+                        "parameters": [
+                            {
+                                "in": "body",
+                                "name": "pet",
+                                "description": "Pet to add",
+                                "required": true,
+                                "schema": { "$ref": "#/definitions/Pet" }
+                            },
+                            { "in": "query", "name": "dryRun", "type": "boolean" }
+                        ],
+                        "responses": { "201": { "description": "created" } }
+                    }
+                },
+                "/pets/{petId}/photo": {
+                    "put": {
+                        "parameters": [
+                            { "in": "path", "name": "petId", "required": true, "type": "string" },
+                            {
+                                "in": "formData",
+                                "name": "caption",
+                                "required": true,
+                                "type": "string"
+                            },
+                            { "in": "formData", "name": "file", "type": "file" }
+                        ],
+                        "responses": { "204": { "description": "stored" } }
+                    }
+                },
+                "/pets/{petId}/rename": {
+                    "post": {
+                        "parameters": [
+                            { "in": "formData", "name": "name", "type": "string" }
+                        ],
+                        "responses": { "204": { "description": "renamed" } }
+                    }
+                }
+            },
+            "definitions": { "Pet": { "type": "object" } }
+        });
+
+        let openapi = convert_swagger_2(&swagger);
+        let paths = &openapi["paths"];
+
+        let create = &paths["/pets"]["post"];
+        assert_eq!(
+            create["requestBody"],
+            json!({
+                "description": "Pet to add",
+                "required": true,
+                "content": {
+                    "application/json": { "schema": { "$ref": "#/components/schemas/Pet" } }
+                }
+            })
+        );
+        assert_eq!(
+            create["parameters"],
+            json!([{ "in": "query", "name": "dryRun", "schema": { "type": "boolean" } }])
+        );
+
+        // A file field switches the form to multipart, as a binary string
+        let photo = &paths["/pets/{petId}/photo"]["put"];
+        let photo_schema = &photo["requestBody"]["content"]["multipart/form-data"]["schema"];
+        assert_eq!(
+            photo_schema["properties"]["file"],
+            json!({ "type": "string", "format": "binary" })
+        );
+        assert_eq!(photo_schema["required"], json!(["caption"]));
+        assert_eq!(photo["parameters"].as_array().unwrap().len(), 1);
+
+        let rename = &paths["/pets/{petId}/rename"]["post"];
+        assert_eq!(
+            rename["requestBody"]["content"]["application/x-www-form-urlencoded"]["schema"],
+            json!({ "type": "object", "properties": { "name": { "type": "string" } } })
+        );
+    }
+
+    #[test]
+    fn test_definitions_move_to_components_and_refs_follow() {
+        let swagger = json!({
+            "swagger": "2.0",
+            "info": { "title": "Test API", "version": "1.0.0" },
+            "parameters": {
+                "Limit": { "in": "query", "name": "limit", "type": "integer" }
+            },
+            "responses": {
+                "NotFound": {
+                    "description": "missing",
+                    "schema": { "$ref": "#/definitions/Error" }
+                }
+            },
+            "paths": {
+                "/pets": {
+                    "get": {
+                        "parameters": [{ "$ref": "#/parameters/Limit" }],
+                        "responses": {
+                            "200": {
+                                "description": "ok",
+                                "schema": {
+                                    "type": "array",
+                                    "items": { "$ref": "#/definitions/Pet" }
+                                }
+                            },
+                            "404": { "$ref": "#/responses/NotFound" }
+                        }
+                    }
+                }
+            },
+            "definitions": {
+                "Pet": {
+                    "type": "object",
+                    "properties": { "owner": { "$ref": "#/definitions/Owner" } }
+                },
+                "Owner": { "type": "object" },
+                "Error": { "type": "object" }
+            }
+        });
+
+        let openapi = convert_swagger_2(&swagger);
+        let schemas = &openapi["components"]["schemas"];
+
+        assert_eq!(openapi.get("definitions"), None);
+        assert_eq!(
+            schemas["Pet"]["properties"]["owner"],
+            json!({ "$ref": "#/components/schemas/Owner" })
+        );
+        assert_eq!(
+            openapi["components"]["responses"]["NotFound"]["content"]["application/json"]["schema"],
+            json!({ "$ref": "#/components/schemas/Error" })
+        );
+
+        let list = &openapi["paths"]["/pets"]["get"];
+        assert_eq!(
+            list["parameters"],
+            json!([{ "$ref": "#/components/parameters/Limit" }])
+        );
+        assert_eq!(
+            list["responses"]["200"]["content"]["application/json"]["schema"]["items"],
+            json!({ "$ref": "#/components/schemas/Pet" })
+        );
+        assert_eq!(
+            list["responses"]["404"],
+            json!({ "$ref": "#/components/responses/NotFound" })
+        );
+    }
+
+    #[test]
+    fn test_produces_and_consumes_set_media_types() {
+        let swagger = json!({
+            "swagger": "2.0",
+            "info": { "title": "Test API", "version": "1.0.0" },
+            "consumes": ["application/json", "application/xml"],
+            "produces": ["application/xml"],
+            "paths": {
+                "/pets": {
+                    "post": {
+                        "parameters": [
+                            { "in": "body", "name": "pet", "schema": { "type": "object" } }
+                        ],
+                        "responses": {
+                            "200": { "description": "ok", "schema": { "type": "object" } }
+                        }
+                    },
+                    "put": {
+                        "consumes": ["text/plain"],
+                        "produces": ["application/json"],
+                        "parameters": [
+                            { "in": "body", "name": "pet", "schema": { "type": "string" } }
+                        ],
+                        "responses": {
+                            "200": { "description": "ok", "schema": { "type": "string" } }
+                        }
+                    }
+                }
+            }
+        });
+
+        let openapi = convert_swagger_2(&swagger);
+        let pets = &openapi["paths"]["/pets"];
+        let media_types = |value: &serde_json::Value| -> Vec<String> {
+            value.as_object().unwrap().keys().cloned().collect()
+        };
+
+        // Document-wide lists apply to every operation that sets none of its own
+        assert_eq!(
+            media_types(&pets["post"]["requestBody"]["content"]),
+            ["application/json", "application/xml"]
+        );
+        assert_eq!(
+            media_types(&pets["post"]["responses"]["200"]["content"]),
+            ["application/xml"]
+        );
+        assert_eq!(
+            media_types(&pets["put"]["requestBody"]["content"]),
+            ["text/plain"]
+        );
+        assert_eq!(
+            media_types(&pets["put"]["responses"]["200"]["content"]),
+            ["application/json"]
+        );
+        assert_eq!(pets["put"].get("consumes"), None);
+        assert_eq!(pets["put"].get("produces"), None);
+    }
+}